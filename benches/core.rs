@@ -0,0 +1,133 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use regex::Regex;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use stylish_stringlike::text::Direction;
+use stylish_stringlike::text::{
+    Joinable, Pushable, RegexReplaceable, Replaceable, Sliceable, Span, Spans, Tag, WidthSliceable,
+};
+use stylish_stringlike::widget::{HBox, TextWidget, TruncationStrategy, TruncationStyle};
+
+/// Counts the bytes allocated, to show how much copying columns straight
+/// into the output saves over slicing them out first.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATED.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Returns the bytes allocated while running a function.
+fn allocated<R>(f: impl FnOnce() -> R) -> usize {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATED.load(Ordering::Relaxed) - before
+}
+
+/// Number of styled runs in the large log.
+const RUNS: usize = 10_000;
+
+/// Builds a styled log of about a megabyte, changing style every line.
+fn build_log(styles: &[Tag]) -> Spans<Tag> {
+    let mut log: Spans<Tag> = Default::default();
+    for index in 0..RUNS {
+        let style = &styles[index % styles.len()];
+        log.push(&Span::borrowed(
+            style,
+            &format!(
+                "{:>6} INFO request handled in {:>4}ms: GET /api/v1/items?page={} 🙈 done\n",
+                index,
+                index % 1000,
+                index
+            ),
+        ));
+    }
+    log
+}
+
+fn core(c: &mut Criterion) {
+    let styles: Vec<Tag> = (0..8)
+        .map(|i| Tag::new(&format!("<{}>", i), &format!("</{}>", i)))
+        .collect();
+    let log = build_log(&styles);
+    let len = log.len();
+    let mut group = c.benchmark_group("1MB styled log");
+    group.sample_size(20);
+    group.bench_function("push", |b| b.iter(|| black_box(build_log(&styles))));
+    group.bench_function("join", |b| b.iter(|| black_box(log.join(&log))));
+    group.bench_function("spans", |b| b.iter(|| black_box(log.spans().count())));
+    group.bench_function("slice", |b| {
+        b.iter(|| black_box(log.slice(len / 4..len / 4 * 3)))
+    });
+    group.bench_function("slice_width", |b| {
+        b.iter(|| black_box(log.slice_width(1000..200_000)))
+    });
+    let slice_and_push = || {
+        let mut output: Spans<Tag> = Spans::from("> ");
+        output.push(&log.slice_width(1000..200_000).unwrap());
+        output
+    };
+    let push_width = || {
+        let mut output: Spans<Tag> = Spans::from("> ");
+        log.push_width(1000..200_000, &mut output);
+        output
+    };
+    let truncation = TruncationStyle::Inner("…");
+    let truncate_into = || {
+        let mut output: Spans<Tag> = Spans::from("> ");
+        truncation.truncate_into(&log, 200_000, Direction::Ltr, &mut output);
+        output
+    };
+    println!(
+        "bytes allocated: slice_width and push {}, push_width {}, truncate_into {}",
+        allocated(slice_and_push),
+        allocated(push_width),
+        allocated(truncate_into)
+    );
+    group.bench_function("slice_width and push", |b| b.iter(slice_and_push));
+    group.bench_function("push_width", |b| b.iter(push_width));
+    group.bench_function("truncate_into", |b| b.iter(truncate_into));
+    group.bench_function("replace", |b| {
+        b.iter(|| black_box(log.replace("INFO", "WARN")))
+    });
+    let regex = Regex::new(r"page=(\d+)").unwrap();
+    group.bench_function("replace_regex", |b| {
+        b.iter(|| black_box(log.replace_regex(&regex, "page=[$1]")))
+    });
+    group.bench_function("HBox::truncate", |b| {
+        let truncation = TruncationStyle::Left("…");
+        b.iter_batched(
+            || {
+                let mut hbox: HBox<Spans<Tag>> = HBox::new();
+                for _ in 0..4 {
+                    hbox.push(Box::new(TextWidget::new(
+                        Cow::Borrowed(&log),
+                        Cow::Borrowed(&truncation),
+                    )));
+                }
+                hbox
+            },
+            |hbox| black_box(hbox.truncate(200)),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, core);
+criterion_main!(benches);
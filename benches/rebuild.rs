@@ -0,0 +1,48 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+
+fn build_line(spans: &mut Spans<Tag>, styles: &[Tag], frame: usize) {
+    for (index, style) in styles.iter().enumerate() {
+        spans.push(&Span::borrowed(style, "status"));
+        spans.push_fmt(style.clone(), format_args!(" {}:{} ", index, frame));
+    }
+}
+
+fn rebuild(c: &mut Criterion) {
+    let styles: Vec<Tag> = (0..16)
+        .map(|i| Tag::new(&format!("<{}>", i), &format!("</{}>", i)))
+        .collect();
+    let mut group = c.benchmark_group("rebuild status line");
+    group.bench_function("new each frame", |b| {
+        let mut frame = 0;
+        b.iter(|| {
+            let mut spans: Spans<Tag> = Default::default();
+            build_line(&mut spans, &styles, frame);
+            frame += 1;
+            black_box(spans)
+        })
+    });
+    group.bench_function("clear and reuse", |b| {
+        let mut spans: Spans<Tag> = Default::default();
+        let mut frame = 0;
+        b.iter(|| {
+            spans.clear();
+            build_line(&mut spans, &styles, frame);
+            frame += 1;
+            black_box(&spans);
+        })
+    });
+    group.bench_function("clone_from", |b| {
+        let mut source: Spans<Tag> = Default::default();
+        build_line(&mut source, &styles, 0);
+        let mut spans: Spans<Tag> = Default::default();
+        b.iter(|| {
+            spans.clone_from(&source);
+            black_box(&spans);
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, rebuild);
+criterion_main!(benches);
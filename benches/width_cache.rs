@@ -0,0 +1,61 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::borrow::Cow;
+use stylish_stringlike::text::{Cached, Pushable, Span, Spans, Tag, WidthCache};
+use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle};
+
+const SEGMENTS: usize = 32;
+
+/// Builds the segments of a status bar, alternating labels with a separator.
+fn build_segments(styles: &[Tag]) -> Vec<Spans<Tag>> {
+    (0..SEGMENTS)
+        .map(|index| {
+            let mut spans: Spans<Tag> = Default::default();
+            let style = &styles[index % styles.len()];
+            if index % 2 == 0 {
+                spans.push(&Span::borrowed(style, "branch: main 🙈"));
+            } else {
+                spans.push(&Span::borrowed(style, " │ "));
+            }
+            spans
+        })
+        .collect()
+}
+
+fn status_bar(c: &mut Criterion) {
+    let styles: Vec<Tag> = (0..4)
+        .map(|i| Tag::new(&format!("<{}>", i), &format!("</{}>", i)))
+        .collect();
+    let truncation = TruncationStyle::Left("…");
+    let mut group = c.benchmark_group("status bar with repeated segments");
+    group.bench_function("uncached", |b| {
+        b.iter(|| {
+            let segments = build_segments(&styles);
+            let mut hbox: HBox<Spans<Tag>> = HBox::new();
+            for segment in &segments {
+                hbox.push(Box::new(TextWidget::new(
+                    Cow::Borrowed(segment),
+                    Cow::Borrowed(&truncation),
+                )));
+            }
+            black_box(hbox.truncate(200))
+        })
+    });
+    group.bench_function("cached", |b| {
+        let cache = WidthCache::new();
+        b.iter(|| {
+            let segments = build_segments(&styles);
+            let mut hbox: HBox<Spans<Tag>> = HBox::new();
+            for segment in &segments {
+                let text: Cow<Cached<_>> = Cow::Owned(cache.wrap(segment));
+                hbox.push(Box::new(TextWidget::new(text, Cow::Borrowed(&truncation))));
+            }
+            let result = hbox.truncate(200);
+            cache.clear();
+            black_box(result)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, status_bar);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+use std::fmt;
+
+/// The reason slicing or truncating a text object failed, returned by the
+/// `try_` variants of methods that otherwise return [`Option`].
+///
+/// Indices are byte offsets when slicing by bytes and columns when slicing by
+/// width.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::Error;
+/// use stylish_stringlike::text::{CheckedSliceable, HasWidth, WidthSliceable};
+/// assert_eq!("aé".try_slice(..2), Err(Error::NonCharBoundary { index: 2 }));
+/// let monkeys = String::from("🙈🙉");
+/// assert_eq!(monkeys.try_slice_width(..1), Err(Error::WidthTooSmall { width: 1 }));
+/// assert_eq!(
+///     monkeys.try_slice_width(5..),
+///     Err(Error::OutOfBounds { index: 5, len: 4 })
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+    /// An index is past the end of the text.
+    OutOfBounds { index: usize, len: usize },
+    /// A byte index falls inside a multi-byte character.
+    NonCharBoundary { index: usize },
+    /// The start of a range is after its end.
+    InvertedRange { start: usize, end: usize },
+    /// A range has no end but the text has unbounded width, so the slice
+    /// would never end.
+    UnboundedRange,
+    /// No grapheme fits in the given number of columns, such as when slicing
+    /// one column of a two-column character or truncating to no width.
+    WidthTooSmall { width: usize },
+    /// Text that is repeated or used to fill space has no width, so it can
+    /// never fill any.
+    ZeroWidthSymbol,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::OutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds of length {}", index, len)
+            }
+            Error::NonCharBoundary { index } => {
+                write!(f, "byte index {} is not a char boundary", index)
+            }
+            Error::InvertedRange { start, end } => {
+                write!(f, "range starts at {} but ends at {}", start, end)
+            }
+            Error::UnboundedRange => write!(f, "range of unbounded text has no end"),
+            Error::WidthTooSmall { width } => {
+                write!(f, "no grapheme fits in a width of {}", width)
+            }
+            Error::ZeroWidthSymbol => write!(f, "symbol has no width"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
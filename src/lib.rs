@@ -13,6 +13,37 @@
 //! [`widget`] provides functionality for displaying text objects in useful ways,
 //! such as truncation with a symbol, or repeating a sequence.
 //!
+//! [`text::stream`] parses ANSI-styled text line by line, for processing logs
+//! too large to load at once.
+//!
+//! Methods that return [`Option`] when text cannot be sliced or truncated have
+//! `try_` variants returning an [`Error`] that says why.
+//!
+//! ## Features
+//! - `ansi` (default): implements [`text::Paintable`] for [`ansi_term`] styles,
+//!   so `Spans<ansi_term::Style>` can be displayed.
+//! - `anstyle`: implements [`text::Paintable`] for `anstyle::Style`.
+//! - `bidi`: adds `Direction::of` and `Spans::direction` to find
+//!   whether text is read right to left, for direction-aware truncation.
+//! - `crossterm`: implements [`text::Paintable`] for
+//!   `crossterm::style::ContentStyle`, and converts `StyledContent` into
+//!   [`text::Span`] and [`text::Spans`].
+//! - `owo-colors`: implements [`text::Paintable`] for `owo_colors::Style`, and
+//!   converts between `owo_colors::Styled<&str>` and [`text::Span`].
+//! - `ratatui`: converts between [`text::Spans`] and `ratatui::text::Line`,
+//!   so the text can be processed here and drawn by a TUI.
+//! - `regex` (default): adds the `RegexReplaceable`, `ExpandStyled` and
+//!   `Expandable` traits for replacing regex matches. Literal replacement and splitting work without it.
+//! - `rope`: adds [`text::RopeSpans`], styled text stored in a `ropey::Rope`
+//!   for editing large documents.
+//! - `syntect`: implements [`text::Paintable`] for
+//!   `syntect::highlighting::Style` with 24-bit color, and collects
+//!   highlighted regions into [`text::Spans`].
+//! - `termcolor`: adds [`text::Spans::write_to`] for writing through a
+//!   `termcolor::WriteColor` sink, with styles implementing `PaintWrite`.
+//! - `term-size`: adds `HBox::truncate_to_terminal` to fit a [`widget::HBox`]
+//!   to the terminal.
+//!
 //! ## Usage
 //!
 //! ```rust
@@ -48,17 +79,14 @@
 //! assert_eq!(format!("{}", foob), "<i>foo</i><b>b</b>");
 //!
 //! // Use the `HBox` widget to truncate multiple spans of text to fit in a desired width.
-//! fn make_spans(style: &Tag, text: &str) -> Spans<Tag> {
-//!     let mut spans: Spans<Tag> = Default::default();
-//!     let span: Span<Tag> = Span::new(Cow::Borrowed(style), Cow::Borrowed(text));
-//!     spans = spans.join(&span);
-//!     spans
-//! }
 //! let truncation = TruncationStyle::Inner(Some(Span::new(
 //!     Cow::Borrowed(&underline),
 //!     Cow::Borrowed("…"),
 //! )));
-//! let spans = vec![make_spans(&italic, "abcdefg"), make_spans(&bold, "12345678")];
+//! let spans = vec![
+//!     Spans::styled(italic.clone(), "abcdefg"),
+//!     Spans::styled(bold.clone(), "12345678"),
+//! ];
 //! let hbox = spans
 //!     .iter()
 //!     .map(|s| {
@@ -75,8 +103,12 @@
 //!     "<i>ab</i><u>…</u><i>fg</i><b>12</b><u>…</u><b>78</b>"
 //! );
 //! ```
+mod error;
+#[macro_use]
+mod macros;
 pub mod text;
 pub mod widget;
+pub use error::Error;
 
 #[cfg(test)]
 mod test {
@@ -94,7 +126,7 @@ mod test {
     }
     #[test]
     fn split_path() {
-        let texts = vec![
+        let texts = [
             Color::Black.paint("::"),
             Color::Red.paint("SomeExtremelyLong"),
             Color::Blue.paint("::"),
@@ -110,8 +142,7 @@ mod test {
 
         let actual = spans
             .split("::")
-            .map(|Split { segment, delim }| vec![segment, delim])
-            .flatten()
+            .flat_map(|Split { segment, delim }| vec![segment, delim])
             .flatten()
             .map(|s| {
                 let foo: Box<dyn Fitable<_>> =
@@ -155,8 +186,7 @@ mod test {
 
         let actual = spans
             .split("::")
-            .map(|Split { segment, delim }| vec![segment, delim])
-            .flatten()
+            .flat_map(|Split { segment, delim }| vec![segment, delim])
             .flatten()
             .map(|s| {
                 let foo: Box<dyn Fitable<_>> =
@@ -0,0 +1,45 @@
+/// Creates [`Spans`](crate::text::Spans) from pairs of styles and text.
+///
+/// Each style is cloned, so a style can be used in several pairs and after
+/// the macro. Text can be anything that is `AsRef<str>`.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::spans;
+/// use stylish_stringlike::text::{Spans, Tag};
+/// let italic = Tag::new("<i>", "</i>");
+/// let bold = Tag::new("<b>", "</b>");
+/// let spans = spans![italic => "foo", bold => String::from("bar"), italic => "baz"];
+/// assert_eq!(format!("{}", spans), "<i>foo</i><b>bar</b><i>baz</i>");
+/// let empty: Spans<Tag> = spans![];
+/// assert_eq!(format!("{}", empty), "");
+/// ```
+#[macro_export]
+macro_rules! spans {
+    ($($style:expr => $text:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut spans = $crate::text::Spans::default();
+        $(
+            spans.push_styled(
+                ::std::clone::Clone::clone(&$style),
+                ::std::convert::AsRef::<str>::as_ref(&$text),
+            );
+        )*
+        spans
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use crate::text::{Pushable, Span, Spans, Tag};
+    #[test]
+    fn spans_macro_matches_pushes() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let actual = spans![fmt_1 => "a", fmt_1 => "b", fmt_2 => "c",];
+        let mut expected: Spans<Tag> = Default::default();
+        expected.push(&Span::borrowed(&fmt_1, "ab"));
+        expected.push(&Span::borrowed(&fmt_2, "c"));
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,12 @@
+/// Trait for styles that can carry user-visible annotations, such as tooltips.
+pub trait Annotatable {
+    /// Returns a copy of this style annotated with a key and value.
+    ///
+    /// # Example
+    /// ```rust
+    /// use stylish_stringlike::text::{Annotatable, Paintable, Tag};
+    /// let link = Tag::new("<a>", "</a>").annotate("title", "tooltip");
+    /// assert_eq!(link.paint("foo"), "<a title=\"tooltip\">foo</a>");
+    /// ```
+    fn annotate(&self, key: &str, value: &str) -> Self;
+}
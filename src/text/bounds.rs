@@ -0,0 +1,88 @@
+//! Normalization of range bounds shared by the slicing implementations.
+use std::ops::{Bound, RangeBounds};
+
+/// Converts the bounds of a range into an inclusive start and an exclusive
+/// end, with [`None`] representing an unbounded end.
+///
+/// The conversion never overflows. An excluded start of [`usize::MAX`] stays
+/// at [`usize::MAX`], which is past the end of any text. An included end of
+/// [`usize::MAX`] is unbounded, since no larger index exists.
+pub(crate) fn normalize<R>(range: &R) -> (usize, Option<usize>)
+where
+    R: RangeBounds<usize> + ?Sized,
+{
+    let start = match range.start_bound() {
+        Bound::Included(s) => *s,
+        Bound::Excluded(s) => s.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(e) => e.checked_add(1),
+        Bound::Excluded(e) => Some(*e),
+        Bound::Unbounded => None,
+    };
+    (start, end)
+}
+
+/// Like [`normalize`], but resolving an unbounded end to `len`.
+pub(crate) fn normalize_within<R>(range: &R, len: usize) -> (usize, usize)
+where
+    R: RangeBounds<usize> + ?Sized,
+{
+    let (start, end) = normalize(range);
+    (start, end.unwrap_or(len))
+}
+
+/// Returns whether the columns from `column` to `column + width` lie within
+/// a range normalized by [`normalize`].
+pub(crate) fn contains(start: usize, end: Option<usize>, column: usize, width: usize) -> bool {
+    column >= start
+        && match end {
+            Some(end) => column + width <= end,
+            None => true,
+        }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn normalize_bounded() {
+        assert_eq!((1, Some(4)), normalize(&(1..4)));
+        assert_eq!((1, Some(5)), normalize(&(1..=4)));
+        assert_eq!(
+            (2, Some(4)),
+            normalize(&(Bound::Excluded(1), Bound::Excluded(4)))
+        );
+    }
+    #[test]
+    fn normalize_unbounded() {
+        assert_eq!((0, None), normalize(&(..)));
+        assert_eq!((3, None), normalize(&(3..)));
+        assert_eq!((0, Some(3)), normalize(&(..3)));
+    }
+    #[test]
+    fn normalize_max() {
+        assert_eq!((0, None), normalize(&(..=usize::MAX)));
+        assert_eq!(
+            (usize::MAX, None),
+            normalize(&(Bound::Excluded(usize::MAX), Bound::Unbounded))
+        );
+        assert_eq!(
+            (usize::MAX, Some(usize::MAX)),
+            normalize(&(usize::MAX..usize::MAX))
+        );
+    }
+    #[test]
+    fn normalize_within_len() {
+        assert_eq!((2, 7), normalize_within(&(2..), 7));
+        assert_eq!((0, 3), normalize_within(&(..3), 7));
+    }
+    #[test]
+    fn contains_columns() {
+        assert!(contains(1, Some(4), 1, 3));
+        assert!(!contains(1, Some(4), 2, 3));
+        assert!(!contains(1, None, 0, 1));
+        assert!(contains(1, None, usize::MAX - 1, 1));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::text::RawText;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// Wraps a text object so that it is compared, ordered and hashed by its raw
+/// contents alone, ignoring its styles. This lets styled text be
+/// deduplicated in a set or used as a map key by what it says.
+///
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+/// use stylish_stringlike::text::{ByContent, Spans, Tag};
+/// let bold = Tag::new("<b>", "</b>");
+/// let lines: Vec<Spans<Tag>> = vec![
+///     Spans::styled(bold.clone(), "error"),
+///     Spans::from("error"),
+///     Spans::styled(bold, "warning"),
+/// ];
+/// let unique: HashSet<ByContent<&Spans<Tag>>> = lines.iter().map(ByContent).collect();
+/// assert_eq!(unique.len(), 2);
+/// assert!(unique.contains(&ByContent(&Spans::from("warning"))));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ByContent<T>(pub T);
+
+impl<T> ByContent<T> {
+    /// Returns the wrapped text.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: RawText> RawText for ByContent<T> {
+    fn raw(&self) -> String {
+        self.0.raw()
+    }
+    fn raw_ref(&self) -> &str {
+        self.0.raw_ref()
+    }
+}
+
+impl<T: RawText> PartialEq for ByContent<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw_ref() == other.raw_ref()
+    }
+}
+
+impl<T: RawText> Eq for ByContent<T> {}
+
+impl<T: RawText> PartialOrd for ByContent<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: RawText> Ord for ByContent<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.raw_ref().cmp(other.raw_ref())
+    }
+}
+
+impl<T: RawText> Hash for ByContent<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.raw_ref().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Span, Spans, Tag};
+    #[test]
+    fn orders_by_content() {
+        let bold = Tag::new("<b>", "</b>");
+        let mut texts = vec![
+            ByContent(Spans::styled(bold.clone(), "b")),
+            ByContent(Spans::from("a")),
+            ByContent(Spans::from("b")),
+        ];
+        texts.sort();
+        texts.dedup();
+        let texts: Vec<String> = texts
+            .into_iter()
+            .map(|t| t.into_inner().to_string())
+            .collect();
+        assert_eq!(texts, vec!["a", "<b>b</b>"]);
+        let span = Span::borrowed(&bold, "a");
+        assert!(span.content_eq(&Spans::<Tag>::from("a")));
+    }
+}
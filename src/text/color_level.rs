@@ -0,0 +1,60 @@
+use std::cell::Cell;
+
+thread_local! {
+    static CURRENT: Cell<ColorLevel> = const { Cell::new(ColorLevel::TrueColor) };
+}
+
+/// How many colors a terminal can show.
+///
+/// A [`crate::text::Format`] painted on the calling thread has its colors
+/// downgraded to the nearest color the current level supports, which is
+/// [`ColorLevel::TrueColor`] unless changed with [`ColorLevel::scope`]. The
+/// level is not shared with other threads, so text painted on several threads
+/// should either set it on each or pass it explicitly with
+/// [`crate::text::Format::downgrade`].
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{Color, ColorLevel, Paintable};
+/// let orange = Color::Rgb(255, 135, 0).normal();
+/// assert_eq!(orange.paint("x"), "\x1b[38;2;255;135;0mx\x1b[0m");
+/// ColorLevel::Ansi256.scope(|| {
+///     assert_eq!(orange.paint("x"), "\x1b[38;5;208mx\x1b[0m");
+/// });
+/// ColorLevel::Basic.scope(|| {
+///     assert_eq!(orange.paint("x"), "\x1b[33mx\x1b[0m");
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ColorLevel {
+    /// The sixteen standard colors.
+    Basic,
+    /// The 256 color palette.
+    Ansi256,
+    /// 24-bit color.
+    #[default]
+    TrueColor,
+}
+
+impl ColorLevel {
+    /// Returns the level current on this thread.
+    pub fn current() -> Self {
+        CURRENT.with(Cell::get)
+    }
+    /// Runs a function with this level current on this thread, restoring the
+    /// previous level afterwards.
+    pub fn scope<F, R>(self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        /// Restores the previous level even if `f` panics.
+        struct Restore(ColorLevel);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+        let _restore = Restore(CURRENT.with(|current| current.replace(self)));
+        f()
+    }
+}
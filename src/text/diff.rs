@@ -0,0 +1,135 @@
+use crate::text::{Pushable, RawText, Span, Spans};
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Styles for the parts of a [`diff`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiffStyles<T> {
+    /// Style of text in both the old and new text.
+    pub unchanged: T,
+    /// Style of text only in the new text.
+    pub inserted: T,
+    /// Style of text only in the old text.
+    pub deleted: T,
+}
+
+impl<T> DiffStyles<T> {
+    /// Creates styles for unchanged, inserted and deleted text.
+    pub fn new(unchanged: T, inserted: T, deleted: T) -> Self {
+        DiffStyles {
+            unchanged,
+            inserted,
+            deleted,
+        }
+    }
+}
+
+/// Shows how the raw text of `new` differs from the raw text of `old`,
+/// giving unchanged text, deleted text and inserted text the styles in
+/// `styles`. Deleted text comes before the text inserted in its place.
+///
+/// Texts are compared grapheme by grapheme through their longest common
+/// subsequence, which takes time and memory proportional to the product of
+/// the lengths of the parts of the texts after their common prefix and
+/// suffix.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{diff, DiffStyles, Tag};
+/// let styles = DiffStyles::new(Tag::default(), Tag::new("[+", "]"), Tag::new("[-", "]"));
+/// let changes = diff(&"expected: 42", &"expected: 24!", &styles);
+/// assert_eq!(changes.to_string(), "expected: [-4]2[+4!]");
+/// ```
+pub fn diff<T, A, B>(old: &A, new: &B, styles: &DiffStyles<T>) -> Spans<T>
+where
+    T: Clone + Default + PartialEq,
+    A: RawText,
+    B: RawText,
+{
+    let old: Vec<&str> = old.raw_ref().graphemes(true).collect();
+    let new: Vec<&str> = new.raw_ref().graphemes(true).collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old_middle, new_middle) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+    let mut result: Spans<T> = Default::default();
+    let mut push = |style: &T, grapheme: &str| {
+        result.push(&Span::new(Cow::Borrowed(style), Cow::Borrowed(grapheme)));
+    };
+    for grapheme in &old[..prefix] {
+        push(&styles.unchanged, grapheme);
+    }
+    // lengths[i][j] is the length of the longest common subsequence of
+    // old_middle[i..] and new_middle[j..].
+    let width = new_middle.len() + 1;
+    let mut lengths = vec![0usize; (old_middle.len() + 1) * width];
+    for i in (0..old_middle.len()).rev() {
+        for j in (0..new_middle.len()).rev() {
+            lengths[i * width + j] = if old_middle[i] == new_middle[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < old_middle.len() || j < new_middle.len() {
+        if i < old_middle.len() && j < new_middle.len() && old_middle[i] == new_middle[j] {
+            push(&styles.unchanged, old_middle[i]);
+            i += 1;
+            j += 1;
+        } else if j == new_middle.len()
+            || (i < old_middle.len() && lengths[(i + 1) * width + j] >= lengths[i * width + j + 1])
+        {
+            push(&styles.deleted, old_middle[i]);
+            i += 1;
+        } else {
+            push(&styles.inserted, new_middle[j]);
+            j += 1;
+        }
+    }
+    for grapheme in &old[old.len() - suffix..] {
+        push(&styles.unchanged, grapheme);
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::Tag;
+    #[test]
+    fn diff_keeps_both_texts() {
+        let styles = DiffStyles::new(
+            Tag::new("<=>", "</=>"),
+            Tag::new("<+>", "</+>"),
+            Tag::new("<->", "</->"),
+        );
+        let old: Spans<Tag> = Spans::from("kitten 🙈");
+        let cases = [
+            ("sitting 🙉", "<->k</-><+>s</+><=>itt</=><->e</-><+>i</+><=>n</=><+>g</+><=> </=><->🙈</-><+>🙉</+>"),
+            ("kitten 🙈", "<=>kitten 🙈</=>"),
+            ("", "<->kitten 🙈</->"),
+        ];
+        for &(new, expected) in cases.iter() {
+            let changes = diff(&old, &new, &styles);
+            assert_eq!(changes.to_string(), expected);
+            let kept = |style: &Tag| {
+                changes
+                    .spans()
+                    .filter(|span| span.style().as_ref() != style)
+                    .map(|span| span.raw())
+                    .collect::<String>()
+            };
+            assert_eq!(kept(&styles.inserted), "kitten 🙈");
+            assert_eq!(kept(&styles.deleted), new);
+        }
+    }
+}
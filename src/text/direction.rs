@@ -0,0 +1,51 @@
+#[cfg(feature = "bidi")]
+use crate::text::{RawText, Spans};
+
+/// The direction text is read in, which decides which visual end of the text
+/// its start is on.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Direction {
+    /// Left to right, as in English.
+    #[default]
+    Ltr,
+    /// Right to left, as in Arabic or Hebrew.
+    Rtl,
+}
+
+#[cfg(feature = "bidi")]
+impl Direction {
+    /// Finds the direction of a paragraph from its first strongly directional
+    /// character, as the Unicode Bidirectional Algorithm does. Returns
+    /// [`None`] if no character has a strong direction.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::Direction;
+    /// assert_eq!(Direction::of("123 abc"), Some(Direction::Ltr));
+    /// assert_eq!(Direction::of("123 שלום abc"), Some(Direction::Rtl));
+    /// assert_eq!(Direction::of("123"), None);
+    /// ```
+    pub fn of(text: &str) -> Option<Direction> {
+        match unicode_bidi::get_base_direction(text) {
+            unicode_bidi::Direction::Ltr => Some(Direction::Ltr),
+            unicode_bidi::Direction::Rtl => Some(Direction::Rtl),
+            unicode_bidi::Direction::Mixed => None,
+        }
+    }
+}
+
+#[cfg(feature = "bidi")]
+impl<T> Spans<T> {
+    /// Returns the paragraph direction of the text, which is left to right if
+    /// no character has a strong direction.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Direction, Spans, Tag};
+    /// let text: Spans<Tag> = Spans::from("مرحبا بالعالم");
+    /// assert_eq!(text.direction(), Direction::Rtl);
+    /// ```
+    pub fn direction(&self) -> Direction {
+        Direction::of(self.raw_ref()).unwrap_or_default()
+    }
+}
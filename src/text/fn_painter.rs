@@ -0,0 +1,85 @@
+use crate::text::Paintable;
+use std::fmt;
+use std::sync::Arc;
+
+/// A shared function that marks up text.
+type PaintFn = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Paints text with a function, for one-off markup that does not need its own
+/// style type.
+///
+/// The default painter paints text unchanged. Painters are equal when they
+/// are clones of each other, so adjacent spans painted by clones of one
+/// painter are merged, while painters created separately from the same
+/// closure are not.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{FnPainter, Spans};
+/// let strong = FnPainter::new(|s| format!("**{}**", s));
+/// let code = FnPainter::new(|s| format!("`{}`", s));
+/// let text = Spans::styled(strong.clone(), "Note:")
+///     .then(FnPainter::default(), " run ")
+///     .then(code, "cargo test")
+///     .then(strong, "!");
+/// assert_eq!(text.to_string(), "**Note:** run `cargo test`**!**");
+/// ```
+#[derive(Clone, Default)]
+pub struct FnPainter {
+    paint: Option<PaintFn>,
+}
+
+impl FnPainter {
+    /// Creates a painter from a function that marks up text.
+    pub fn new<F>(paint: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        FnPainter {
+            paint: Some(Arc::new(paint)),
+        }
+    }
+}
+
+impl PartialEq for FnPainter {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.paint, &other.paint) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for FnPainter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnPainter")
+            .field("plain", &self.paint.is_none())
+            .finish()
+    }
+}
+
+impl Paintable for FnPainter {
+    fn paint(&self, target: &str) -> String {
+        match &self.paint {
+            Some(paint) => paint(target),
+            None => target.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Spans, WidthSliceable};
+    #[test]
+    fn clones_merge_runs() {
+        let upper = FnPainter::new(|s| s.to_uppercase());
+        let text = Spans::styled(upper.clone(), "ab").then(upper, "cd");
+        assert_eq!(text.span_count(), 1);
+        assert_eq!(text.slice_width(1..3).unwrap().to_string(), "BC");
+        let other = FnPainter::new(|s| s.to_uppercase());
+        assert_ne!(text.style_at(0), Some(&other));
+        assert_eq!(FnPainter::default().paint("x"), "x");
+    }
+}
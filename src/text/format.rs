@@ -0,0 +1,415 @@
+use super::style_key::stable_hash;
+use crate::text::{ColorLevel, Paintable, StyleKey};
+use std::borrow::Borrow;
+use std::fmt;
+
+/// A terminal color: one of the standard sixteen, an index into the 256
+/// color palette or a 24-bit color.
+///
+/// Colors are downgraded to the nearest color the current
+/// [`crate::text::ColorLevel`] supports when they are painted.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    /// A color from the 256 color palette.
+    Fixed(u8),
+    /// A 24-bit color.
+    Rgb(u8, u8, u8),
+}
+
+/// The standard sixteen colors, in palette order.
+const NAMED: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
+];
+
+/// The xterm defaults for the standard sixteen colors, in palette order.
+const NAMED_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The levels of each channel in the 6×6×6 color cube of the 256 color
+/// palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Returns the squared distance between two colors.
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// Returns the index of the cube level nearest to a channel value.
+fn nearest_level(value: u8) -> u8 {
+    (0..CUBE_LEVELS.len() as u8)
+        .min_by_key(|&i| (i32::from(CUBE_LEVELS[i as usize]) - i32::from(value)).abs())
+        .unwrap_or(0)
+}
+
+impl Color {
+    /// A format with this as the foreground color.
+    pub fn normal(self) -> Format {
+        Format::new().fg(self)
+    }
+    /// A bold format with this as the foreground color.
+    pub fn bold(self) -> Format {
+        Format::new().fg(self).bold()
+    }
+    /// A format with this as the foreground color and another as the
+    /// background color.
+    pub fn on(self, background: Color) -> Format {
+        Format::new().fg(self).bg(background)
+    }
+    /// Returns the nearest color a terminal with a color level can show.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Color, ColorLevel};
+    /// let gray = Color::Rgb(130, 130, 130);
+    /// assert_eq!(gray.downgrade(ColorLevel::TrueColor), gray);
+    /// assert_eq!(gray.downgrade(ColorLevel::Ansi256), Color::Fixed(244));
+    /// assert_eq!(gray.downgrade(ColorLevel::Basic), Color::BrightBlack);
+    /// assert_eq!(Color::Fixed(9).downgrade(ColorLevel::Basic), Color::BrightRed);
+    /// ```
+    pub fn downgrade(self, level: ColorLevel) -> Color {
+        match (self, level) {
+            (Color::Fixed(index), _) if index < 16 => NAMED[index as usize],
+            (Color::Fixed(_), ColorLevel::Basic) | (Color::Rgb(..), ColorLevel::Basic) => {
+                let rgb = self.to_rgb();
+                (0..16)
+                    .min_by_key(|&i| distance(rgb, NAMED_RGB[i]))
+                    .map_or(self, |i| NAMED[i])
+            }
+            (Color::Rgb(r, g, b), ColorLevel::Ansi256) => {
+                let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+                let cube = 16 + 36 * ri + 6 * gi + bi;
+                let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+                let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+                [cube, gray]
+                    .iter()
+                    .copied()
+                    .min_by_key(|&index| distance((r, g, b), Color::Fixed(index).to_rgb()))
+                    .map_or(self, Color::Fixed)
+            }
+            _ => self,
+        }
+    }
+    /// Returns the red, green and blue channels of this color, using the
+    /// xterm defaults for the palette.
+    fn to_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Fixed(index) if index < 16 => NAMED_RGB[index as usize],
+            Color::Fixed(index) if index < 232 => {
+                let index = index - 16;
+                (
+                    CUBE_LEVELS[(index / 36) as usize],
+                    CUBE_LEVELS[(index / 6 % 6) as usize],
+                    CUBE_LEVELS[(index % 6) as usize],
+                )
+            }
+            Color::Fixed(index) => {
+                let level = 8 + 10 * (index - 232);
+                (level, level, level)
+            }
+            named => NAMED_RGB[NAMED.iter().position(|&c| c == named).unwrap_or(0)],
+        }
+    }
+    /// Writes the SGR parameters selecting this color, with `base` being 30
+    /// for the foreground and 40 for the background.
+    fn write_code<W: fmt::Write>(self, base: u8, w: &mut W) -> fmt::Result {
+        match self {
+            Color::Fixed(index) => write!(w, "{};5;{}", base + 8, index),
+            Color::Rgb(r, g, b) => write!(w, "{};2;{};{};{}", base + 8, r, g, b),
+            named => {
+                let index = NAMED.iter().position(|&c| c == named).unwrap_or(0) as u8;
+                if index < 8 {
+                    write!(w, "{}", base + index)
+                } else {
+                    write!(w, "{}", base + 60 + index - 8)
+                }
+            }
+        }
+    }
+}
+
+/// A terminal color and format, painted with ANSI SGR escape sequences.
+///
+/// The default format is plain and paints text unchanged.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{Color, Format, Paintable};
+/// let warning = Format::new().fg(Color::Yellow).bold();
+/// assert_eq!(warning.paint("careful"), "\x1b[1;33mcareful\x1b[0m");
+/// assert_eq!(Format::new().paint("plain"), "plain");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Format {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+impl Format {
+    /// Creates a plain format.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Sets the foreground color.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+    /// Sets the background color.
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+    /// Makes the text bold.
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+    /// Makes the text italic.
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+    /// Underlines the text.
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+    /// Whether the format changes nothing, so no escape sequences are needed.
+    pub fn is_plain(&self) -> bool {
+        *self == Format::default()
+    }
+    /// Returns this format with its colors downgraded to a level, so that it
+    /// paints the same on any thread whatever level is current there.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Color, ColorLevel, Format, Paintable};
+    /// let orange = Color::Rgb(255, 135, 0).normal().bg(Color::Rgb(0, 0, 0));
+    /// let basic = orange.downgrade(ColorLevel::Basic);
+    /// assert_eq!(basic, Format::new().fg(Color::Yellow).bg(Color::Black));
+    /// assert_eq!(basic.paint("x"), ColorLevel::Basic.scope(|| orange.paint("x")));
+    /// ```
+    pub fn downgrade(self, level: ColorLevel) -> Format {
+        Format {
+            fg: self.fg.map(|color| color.downgrade(level)),
+            bg: self.bg.map(|color| color.downgrade(level)),
+            ..self
+        }
+    }
+    /// Writes the escape sequence that switches a terminal to this format,
+    /// downgrading colors to the current [`ColorLevel`].
+    fn write_prefix<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let level = ColorLevel::current();
+        w.write_str("\x1b[")?;
+        let mut separator = "";
+        for (enabled, code) in [(self.bold, "1"), (self.italic, "3"), (self.underline, "4")].iter()
+        {
+            if *enabled {
+                w.write_str(separator)?;
+                w.write_str(code)?;
+                separator = ";";
+            }
+        }
+        for (color, base) in [(self.fg, 30), (self.bg, 40)].iter() {
+            if let Some(color) = color {
+                w.write_str(separator)?;
+                color.downgrade(level).write_code(*base, w)?;
+                separator = ";";
+            }
+        }
+        w.write_char('m')
+    }
+    /// Writes groups of text, switching format only where it changes and
+    /// skipping empty text.
+    fn write_many<'a, T, U, V, W>(groups: T, w: &mut W) -> fmt::Result
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+        W: fmt::Write,
+    {
+        let mut current = Format::default();
+        for (format, text) in groups {
+            let (format, text) = (*format.borrow(), text.borrow());
+            if text.is_empty() {
+                continue;
+            }
+            if format != current {
+                if !current.is_plain() {
+                    w.write_str(RESET)?;
+                }
+                if !format.is_plain() {
+                    format.write_prefix(w)?;
+                }
+                current = format;
+            }
+            w.write_str(text)?;
+        }
+        if !current.is_plain() {
+            w.write_str(RESET)?;
+        }
+        Ok(())
+    }
+}
+
+/// The escape sequence that resets a terminal to its default format.
+const RESET: &str = "\x1b[0m";
+
+impl From<Color> for Format {
+    fn from(color: Color) -> Self {
+        color.normal()
+    }
+}
+
+impl StyleKey for Format {
+    fn style_key(&self) -> u64 {
+        let mut prefix = String::new();
+        if !self.is_plain() {
+            // Writing into a `String` cannot fail.
+            let _ = self.write_prefix(&mut prefix);
+        }
+        stable_hash(&[prefix.as_bytes()])
+    }
+}
+
+impl Paintable for Format {
+    fn paint(&self, target: &str) -> String {
+        Format::paint_many(std::iter::once((self, target)))
+    }
+    fn paint_many<'a, T, U, V>(groups: T) -> String
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        let mut result = String::new();
+        // Writing into a `String` cannot fail.
+        let _ = Format::write_many(groups, &mut result);
+        result
+    }
+    fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Format::write_many(std::iter::once((self, target)), f)
+    }
+    fn paint_many_fmt<'a, T, U, V>(groups: T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        Format::write_many(groups, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Pushable, Span, Spans};
+    #[test]
+    fn format_codes() {
+        let format = Format::new()
+            .fg(Color::BrightRed)
+            .bg(Color::Blue)
+            .bold()
+            .italic()
+            .underline();
+        assert_eq!(format.paint("x"), "\x1b[1;3;4;91;44mx\x1b[0m");
+        assert_eq!(
+            Color::Green.on(Color::Black).paint("x"),
+            "\x1b[32;40mx\x1b[0m"
+        );
+    }
+    #[test]
+    fn extended_colors_downgrade() {
+        let format = Color::Rgb(0, 0, 200).on(Color::Fixed(196));
+        assert_eq!(format.paint("x"), "\x1b[38;2;0;0;200;48;5;196mx\x1b[0m");
+        let painted = ColorLevel::Ansi256.scope(|| format.paint("x"));
+        assert_eq!(painted, "\x1b[38;5;20;48;5;196mx\x1b[0m");
+        let painted = ColorLevel::Basic.scope(|| format.paint("x"));
+        assert_eq!(painted, "\x1b[34;101mx\x1b[0m");
+        for index in 16..=255 {
+            let color = Color::Fixed(index);
+            assert_eq!(
+                color.downgrade(ColorLevel::TrueColor),
+                color.downgrade(ColorLevel::Ansi256)
+            );
+            let (r, g, b) = color.to_rgb();
+            let nearest = Color::Rgb(r, g, b).downgrade(ColorLevel::Ansi256).to_rgb();
+            assert_eq!(nearest, (r, g, b));
+        }
+    }
+    #[test]
+    fn format_many_merges_runs() {
+        let red = Color::Red.normal();
+        let plain = Format::new();
+        let texts = vec![
+            (&red, "a"),
+            (&red, "b"),
+            (&plain, "c"),
+            (&red, ""),
+            (&red, "d"),
+        ];
+        assert_eq!(
+            Format::paint_many(texts),
+            "\x1b[31mab\x1b[0mc\x1b[31md\x1b[0m"
+        );
+    }
+    #[test]
+    fn format_spans_display() {
+        let mut spans: Spans<Format> = Default::default();
+        spans.push(&Span::borrowed(&Color::Blue.bold(), "foo"));
+        spans.push(&Span::borrowed(&Format::new(), "bar"));
+        assert_eq!(spans.to_string(), "\x1b[1;34mfoo\x1b[0mbar");
+    }
+}
@@ -0,0 +1,176 @@
+use super::tag::escape_into;
+use crate::text::{RawText, Spans};
+
+/// The attributes of an HTML element wrapping a run of styled text.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct HtmlAttributes {
+    classes: Vec<String>,
+    style: Option<String>,
+}
+
+impl HtmlAttributes {
+    /// Creates attributes with no classes and no inline style.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Adds a class.
+    pub fn class(mut self, class: &str) -> Self {
+        self.classes.push(class.to_string());
+        self
+    }
+    /// Adds inline CSS declarations, such as `color: red`.
+    pub fn style(mut self, css: &str) -> Self {
+        match self.style {
+            Some(ref mut style) => {
+                style.push_str("; ");
+                style.push_str(css);
+            }
+            None => self.style = Some(css.to_string()),
+        }
+        self
+    }
+    /// Whether there are no attributes, so text needs no wrapping element.
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty() && self.style.is_none()
+    }
+    /// Writes the attributes, each preceded by a space.
+    fn write_into(&self, target: &mut String) {
+        if !self.classes.is_empty() {
+            target.push_str(" class=\"");
+            escape_into(target, &self.classes.join(" "));
+            target.push('"');
+        }
+        if let Some(ref style) = self.style {
+            target.push_str(" style=\"");
+            escape_into(target, style);
+            target.push('"');
+        }
+    }
+}
+
+/// Renders [`Spans`] as HTML, wrapping each run of text in an element with
+/// classes or inline CSS chosen by a function of its style.
+///
+/// Unlike painting with a [`crate::text::Tag`], text and attribute values are
+/// escaped, and adjacent runs whose styles map to the same attributes are
+/// merged into one element. Runs mapped to no attributes are written bare.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{Color, Format, HtmlAttributes, HtmlRenderer, Pushable, Span, Spans};
+/// let mut spans: Spans<Format> = Default::default();
+/// spans.push(&Span::borrowed(&Color::Red.normal(), "<a>"));
+/// spans.push(&Span::borrowed(&Color::Red.bold(), " & "));
+/// spans.push(&Span::borrowed(&Format::new(), "b"));
+/// let renderer = HtmlRenderer::new(|format: &Format| match format.fg {
+///     Some(Color::Red) => HtmlAttributes::new().class("error"),
+///     _ => HtmlAttributes::new(),
+/// });
+/// assert_eq!(
+///     renderer.render(&spans),
+///     "<span class=\"error\">&lt;a&gt; &amp; </span>b"
+/// );
+/// ```
+pub struct HtmlRenderer<F> {
+    attributes: F,
+    element: String,
+}
+
+impl<F> HtmlRenderer<F> {
+    /// Creates a renderer that wraps runs in `span` elements with the
+    /// attributes given by a function of their style.
+    pub fn new(attributes: F) -> Self {
+        HtmlRenderer {
+            attributes,
+            element: String::from("span"),
+        }
+    }
+    /// Sets the name of the element that wraps each run.
+    pub fn set_element(&mut self, element: &str) {
+        self.element = element.to_string();
+    }
+    /// Renders text as HTML.
+    pub fn render<T>(&self, spans: &Spans<T>) -> String
+    where
+        T: Clone + Default,
+        F: Fn(&T) -> HtmlAttributes,
+    {
+        let mut result = String::new();
+        let mut current: Option<(HtmlAttributes, String)> = None;
+        for span in spans.spans() {
+            let text = span.raw_ref();
+            if text.is_empty() {
+                continue;
+            }
+            let attributes = (self.attributes)(span.style());
+            match current {
+                Some((ref previous, ref mut buffer)) if *previous == attributes => {
+                    buffer.push_str(text)
+                }
+                _ => {
+                    if let Some((previous, buffer)) = current.take() {
+                        self.write_element(&mut result, &previous, &buffer);
+                    }
+                    current = Some((attributes, text.to_string()));
+                }
+            }
+        }
+        if let Some((previous, buffer)) = current {
+            self.write_element(&mut result, &previous, &buffer);
+        }
+        result
+    }
+    /// Writes escaped text, wrapped in an element if it has any attributes.
+    fn write_element(&self, target: &mut String, attributes: &HtmlAttributes, text: &str) {
+        if attributes.is_empty() {
+            escape_into(target, text);
+            return;
+        }
+        target.push('<');
+        target.push_str(&self.element);
+        attributes.write_into(target);
+        target.push('>');
+        escape_into(target, text);
+        target.push_str("</");
+        target.push_str(&self.element);
+        target.push('>');
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Pushable, Span, Tag};
+    #[test]
+    fn render_inline_styles() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans.push(&Span::borrowed(&fmt_1, "a\"b"));
+        spans.push(&Span::borrowed(&fmt_2, "c"));
+        spans.push(&Span::borrowed(&fmt_1, "d"));
+        let mut renderer = HtmlRenderer::new(|tag: &Tag| {
+            if *tag == fmt_1 {
+                HtmlAttributes::new()
+                    .style("color: red")
+                    .style("font-weight: bold")
+            } else {
+                HtmlAttributes::new().class("x").class("\"y\"")
+            }
+        });
+        renderer.set_element("code");
+        let expected = concat!(
+            "<code style=\"color: red; font-weight: bold\">a&quot;b</code>",
+            "<code class=\"x &quot;y&quot;\">c</code>",
+            "<code style=\"color: red; font-weight: bold\">d</code>",
+        );
+        assert_eq!(expected, renderer.render(&spans));
+    }
+    #[test]
+    fn render_unstyled() {
+        let spans: Spans<Tag> = Spans::from("1 < 2");
+        let renderer = HtmlRenderer::new(|_: &Tag| HtmlAttributes::new());
+        assert_eq!("1 &lt; 2", renderer.render(&spans));
+        assert_eq!("", renderer.render(&Spans::<Tag>::default()));
+    }
+}
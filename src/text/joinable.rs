@@ -1,3 +1,6 @@
+use crate::text::Pushable;
+use std::borrow::Borrow;
+
 /// Provides functionality for joining text objects together.
 pub trait Joinable<T> {
     type Output: Sized;
@@ -19,3 +22,47 @@ impl Joinable<String> for String {
         [self, other].iter().map(|x| x.as_str()).collect::<String>()
     }
 }
+
+/// Joining the items of an iterator into one text object with a separator
+/// between them.
+pub trait JoinWith<T>: Iterator {
+    /// Pushes every item onto an empty object, with the separator between
+    /// consecutive items.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{JoinWith, Spans, Tag};
+    /// let dir = Tag::new("<d>", "</d>");
+    /// let sep = Tag::new("<s>", "</s>");
+    /// let parts = ["usr", "lib", "rustlib"];
+    /// let path: Spans<Tag> = parts
+    ///     .iter()
+    ///     .map(|part| Spans::styled(dir.clone(), part))
+    ///     .join_with(&Spans::styled(sep, "/"));
+    /// assert_eq!(
+    ///     path.to_string(),
+    ///     "<d>usr</d><s>/</s><d>lib</d><s>/</s><d>rustlib</d>"
+    /// );
+    /// let words = vec![String::from("a"), String::from("b")];
+    /// assert_eq!(words.iter().join_with(&String::from(", ")), "a, b");
+    /// ```
+    fn join_with(self, separator: &T) -> T;
+}
+
+impl<I, U, T> JoinWith<T> for I
+where
+    I: Iterator<Item = U>,
+    U: Borrow<T>,
+    T: Default + Pushable<T>,
+{
+    fn join_with(self, separator: &T) -> T {
+        let mut result: T = Default::default();
+        for (index, item) in self.enumerate() {
+            if index > 0 {
+                result.push(separator);
+            }
+            result.push(item.borrow());
+        }
+        result
+    }
+}
@@ -0,0 +1,203 @@
+use crate::text::{RawText, Spans};
+
+/// The Markdown emphasis applied to a run of styled text.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct MarkdownAttributes {
+    pub bold: bool,
+    pub italic: bool,
+    pub code: bool,
+    pub strikethrough: bool,
+}
+
+impl MarkdownAttributes {
+    /// Creates attributes for plain text.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Makes the text bold.
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+    /// Makes the text italic.
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+    /// Makes the text inline code.
+    pub fn code(mut self) -> Self {
+        self.code = true;
+        self
+    }
+    /// Strikes the text through.
+    pub fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+    /// The delimiters written before the text, outermost first.
+    fn opening(&self) -> String {
+        let mut opening = String::new();
+        if self.strikethrough {
+            opening.push_str("~~");
+        }
+        if self.bold {
+            opening.push_str("**");
+        }
+        if self.italic {
+            opening.push('*');
+        }
+        opening
+    }
+}
+
+/// Renders [`Spans`] as Markdown, with emphasis chosen by a function of each
+/// run's style.
+///
+/// Adjacent runs whose styles map to the same attributes are merged, and
+/// characters Markdown would treat as syntax are escaped. Whitespace at the
+/// ends of an emphasized run is written outside the delimiters, where
+/// Markdown expects it.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{Format, MarkdownAttributes, MarkdownRenderer, Pushable, Span, Spans};
+/// let mut spans: Spans<Format> = Default::default();
+/// spans.push(&Span::borrowed(&Format::new(), "see "));
+/// spans.push(&Span::borrowed(&Format::new().bold(), "*note* "));
+/// spans.push(&Span::borrowed(&Format::new().underline(), "cargo_test"));
+/// let renderer = MarkdownRenderer::new(|format: &Format| MarkdownAttributes {
+///     bold: format.bold,
+///     code: format.underline,
+///     ..Default::default()
+/// });
+/// assert_eq!(renderer.render(&spans), "see **\\*note\\*** `cargo_test`");
+/// ```
+pub struct MarkdownRenderer<F> {
+    attributes: F,
+}
+
+impl<F> MarkdownRenderer<F> {
+    /// Creates a renderer with the attributes given by a function of each
+    /// style.
+    pub fn new(attributes: F) -> Self {
+        MarkdownRenderer { attributes }
+    }
+    /// Renders text as Markdown.
+    pub fn render<T>(&self, spans: &Spans<T>) -> String
+    where
+        T: Clone + Default,
+        F: Fn(&T) -> MarkdownAttributes,
+    {
+        let mut result = String::new();
+        let mut current: Option<(MarkdownAttributes, String)> = None;
+        for span in spans.spans() {
+            let text = span.raw_ref();
+            if text.is_empty() {
+                continue;
+            }
+            let attributes = (self.attributes)(span.style());
+            match current {
+                Some((previous, ref mut buffer)) if previous == attributes => buffer.push_str(text),
+                _ => {
+                    if let Some((previous, buffer)) = current.take() {
+                        write_run(&mut result, previous, &buffer);
+                    }
+                    current = Some((attributes, text.to_string()));
+                }
+            }
+        }
+        if let Some((previous, buffer)) = current {
+            write_run(&mut result, previous, &buffer);
+        }
+        result
+    }
+}
+
+/// Writes a run of text with its delimiters, keeping surrounding whitespace
+/// outside of them.
+fn write_run(target: &mut String, attributes: MarkdownAttributes, text: &str) {
+    let trimmed = text.trim();
+    let emphasized = attributes.bold || attributes.italic || attributes.strikethrough;
+    if trimmed.is_empty() || !(emphasized || attributes.code) {
+        escape_into(target, text);
+        return;
+    }
+    let start = text.len() - text.trim_start().len();
+    let end = start + trimmed.len();
+    target.push_str(&text[..start]);
+    let opening = attributes.opening();
+    target.push_str(&opening);
+    if attributes.code {
+        write_code(target, trimmed);
+    } else {
+        escape_into(target, trimmed);
+    }
+    target.extend(opening.chars().rev());
+    target.push_str(&text[end..]);
+}
+
+/// Writes inline code, fenced with more backticks than it contains in a row.
+fn write_code(target: &mut String, text: &str) {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest + 1);
+    let padding = if text.starts_with('`') || text.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    target.push_str(&fence);
+    target.push_str(padding);
+    target.push_str(text);
+    target.push_str(padding);
+    target.push_str(&fence);
+}
+
+/// Escapes characters that Markdown would read as inline syntax.
+fn escape_into(target: &mut String, text: &str) {
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '~' | '[' | ']' | '<' | '>') {
+            target.push('\\');
+        }
+        target.push(c);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Pushable, Span, Tag};
+    #[test]
+    fn render_nested_emphasis() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let fmt_3 = Tag::new("<3>", "</3>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans.push(&Span::borrowed(&fmt_1, " a_b"));
+        spans.push(&Span::borrowed(&fmt_2, "c "));
+        spans.push(&Span::borrowed(&fmt_3, "d``e"));
+        spans.push(&Span::borrowed(&fmt_3, "`"));
+        let renderer = MarkdownRenderer::new(|tag: &Tag| {
+            if *tag == fmt_3 {
+                MarkdownAttributes::new().code().bold()
+            } else {
+                MarkdownAttributes::new().bold().italic().strikethrough()
+            }
+        });
+        let expected = " ~~***a\\_bc***~~ **``` d``e` ```**";
+        assert_eq!(expected, renderer.render(&spans));
+    }
+    #[test]
+    fn render_whitespace() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans.push(&Span::borrowed(&fmt_1, "  "));
+        let renderer = MarkdownRenderer::new(|_: &Tag| MarkdownAttributes::new().italic());
+        assert_eq!("  ", renderer.render(&spans));
+        let plain = MarkdownRenderer::new(|_: &Tag| MarkdownAttributes::new());
+        assert_eq!("1 \\* 2", plain.render(&Spans::<Tag>::from("1 * 2")));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::text::{Pushable, Span, Spans, Tag};
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+/// An error in markup read by a [`MarkupParser`]. Positions are byte offsets
+/// into the markup.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MarkupError {
+    /// A tag was opened but its closing delimiter is missing.
+    UnterminatedTag { position: usize },
+    /// A closing tag does not match the innermost open tag, or closes a tag
+    /// that was never opened.
+    UnexpectedClosingTag { name: String, position: usize },
+    /// A tag is still open at the end of the markup.
+    UnclosedTag { name: String },
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarkupError::UnterminatedTag { position } => {
+                write!(f, "unterminated tag at byte {}", position)
+            }
+            MarkupError::UnexpectedClosingTag { name, position } => {
+                write!(f, "unexpected closing tag `{}` at byte {}", name, position)
+            }
+            MarkupError::UnclosedTag { name } => write!(f, "tag `{}` is never closed", name),
+        }
+    }
+}
+
+impl Error for MarkupError {}
+
+/// Reads simple tagged markup, such as `<i>foo<b>bar</b></i>` or
+/// `[i]foo[/i]`, into [`Spans<Tag>`].
+///
+/// Each run of text is styled with a [`Tag`] made from the tags it is nested
+/// in, so painting the result reproduces equivalent markup. Opening tags are
+/// kept as written, including any attributes. Text read by
+/// [`MarkupParser::html`] is escaped again when painted, with
+/// [`Tag::set_escape_html`]. A delimiter that is not followed by a tag name,
+/// as in `1 < 2`, is read as text. Empty elements, written as `<br/>`, are
+/// accepted but add no text.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{MarkupParser, RawText};
+/// let spans = MarkupParser::html().parse("<i>foo<b>bar</b></i> &amp; baz").unwrap();
+/// assert_eq!(spans.raw(), "foobar & baz");
+/// assert_eq!(spans.to_string(), "<i>foo</i><i><b>bar</b></i> &amp; baz");
+/// let spans = MarkupParser::bbcode().parse("[color=red]foo[/color]").unwrap();
+/// assert_eq!(spans.to_string(), "[color=red]foo[/color]");
+/// ```
+#[derive(Clone, Debug)]
+pub struct MarkupParser {
+    open: char,
+    close: char,
+    entities: bool,
+}
+
+impl MarkupParser {
+    /// A parser for HTML-like tags in angle brackets, which also decodes the
+    /// `&amp;`, `&lt;`, `&gt;`, `&quot;` and `&apos;` entities in text.
+    pub fn html() -> Self {
+        MarkupParser {
+            open: '<',
+            close: '>',
+            entities: true,
+        }
+    }
+    /// A parser for BBCode-like tags in square brackets.
+    pub fn bbcode() -> Self {
+        MarkupParser {
+            open: '[',
+            close: ']',
+            entities: false,
+        }
+    }
+    /// Parses markup into styled text.
+    pub fn parse(&self, markup: &str) -> Result<Spans<Tag>, MarkupError> {
+        let mut result: Spans<Tag> = Default::default();
+        // The name and opening tag of each open tag, outermost first.
+        let mut open: Vec<(&str, &str)> = vec![];
+        let mut style = self.nested_tag(&open);
+        let mut position = 0;
+        while let Some(offset) = markup[position..].find(self.open) {
+            let start = position + offset;
+            let inner_start = start + self.open.len_utf8();
+            let next = markup[inner_start..].chars().next();
+            if !matches!(next, Some(c) if c.is_alphabetic() || c == '/') {
+                // Not a tag, so the delimiter is text.
+                self.push_text(&mut result, &style, &markup[position..inner_start]);
+                position = inner_start;
+                continue;
+            }
+            self.push_text(&mut result, &style, &markup[position..start]);
+            let inner_end = markup[inner_start..]
+                .find(self.close)
+                .map(|end| inner_start + end)
+                .ok_or(MarkupError::UnterminatedTag { position: start })?;
+            let end = inner_end + self.close.len_utf8();
+            let inner = &markup[inner_start..inner_end];
+            if let Some(name) = inner.strip_prefix('/') {
+                let name = name.trim();
+                match open.last() {
+                    Some((open_name, _)) if *open_name == name => {
+                        open.pop();
+                    }
+                    _ => {
+                        return Err(MarkupError::UnexpectedClosingTag {
+                            name: name.to_string(),
+                            position: start,
+                        })
+                    }
+                }
+            } else if !inner.ends_with('/') {
+                let name = inner
+                    .split(|c: char| c.is_whitespace() || c == '=' || c == '/')
+                    .next()
+                    .unwrap_or_default();
+                open.push((name, &markup[start..end]));
+            }
+            style = self.nested_tag(&open);
+            position = end;
+        }
+        self.push_text(&mut result, &style, &markup[position..]);
+        match open.pop() {
+            Some((name, _)) => Err(MarkupError::UnclosedTag {
+                name: name.to_string(),
+            }),
+            None => Ok(result),
+        }
+    }
+    /// Makes a tag that opens and closes each of the open tags in order.
+    fn nested_tag(&self, open: &[(&str, &str)]) -> Tag {
+        let mut opening = String::new();
+        let mut closing = String::new();
+        for (name, tag) in open {
+            opening.push_str(tag);
+            let mut close = String::new();
+            close.push(self.open);
+            close.push('/');
+            close.push_str(name);
+            close.push(self.close);
+            closing.insert_str(0, &close);
+        }
+        let mut tag = Tag::new(&opening, &closing);
+        tag.set_escape_html(self.entities);
+        tag
+    }
+    /// Pushes text in a style, decoding entities if needed.
+    fn push_text(&self, target: &mut Spans<Tag>, style: &Tag, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let text = if self.entities {
+            decode_entities(text)
+        } else {
+            Cow::Borrowed(text)
+        };
+        target.push(&Span::new(Cow::Borrowed(style), text));
+    }
+}
+
+/// Decodes the entities that are needed to write markup characters as text.
+fn decode_entities(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = [
+            ("&amp;", '&'),
+            ("&lt;", '<'),
+            ("&gt;", '>'),
+            ("&quot;", '"'),
+            ("&apos;", '\''),
+        ]
+        .iter()
+        .find(|(entity, _)| rest.starts_with(entity));
+        match decoded {
+            Some((entity, c)) => {
+                result.push(*c);
+                rest = &rest[entity.len()..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::RawText;
+    #[test]
+    fn parse_round_trip() {
+        let parser = MarkupParser::html();
+        let spans = parser
+            .parse("a <b>b<i class=\"x\">c</i>&lt;</b> 1 < 2 &copy;")
+            .unwrap();
+        assert_eq!("a bc< 1 < 2 &copy;", spans.raw());
+        let painted = spans.to_string();
+        assert_eq!(
+            "a <b>b</b><b><i class=\"x\">c</i></b><b>&lt;</b> 1 &lt; 2 &amp;copy;",
+            painted
+        );
+        assert_eq!(parser.parse(&painted), Ok(spans));
+        let bb = MarkupParser::bbcode().parse("[b]x[/b] [i]y[/i]").unwrap();
+        assert_eq!(MarkupParser::bbcode().parse(&bb.to_string()), Ok(bb));
+    }
+    #[test]
+    fn parse_empty_elements() {
+        let parser = MarkupParser::html();
+        let spans = parser.parse("a<br/>b <b>c<img src=\"x\" /></b>").unwrap();
+        assert_eq!("ab c", spans.raw());
+        assert_eq!("ab <b>c</b>", spans.to_string());
+        let bb = MarkupParser::bbcode().parse("a[hr/]b").unwrap();
+        assert_eq!("ab", bb.raw());
+    }
+    #[test]
+    fn parse_errors() {
+        let parser = MarkupParser::html();
+        assert_eq!(
+            parser.parse("<b>foo</i>"),
+            Err(MarkupError::UnexpectedClosingTag {
+                name: String::from("i"),
+                position: 6
+            })
+        );
+        assert_eq!(
+            parser.parse("<b><i>foo</i>"),
+            Err(MarkupError::UnclosedTag {
+                name: String::from("b")
+            })
+        );
+        assert_eq!(
+            parser.parse("foo <b"),
+            Err(MarkupError::UnterminatedTag { position: 4 })
+        );
+    }
+}
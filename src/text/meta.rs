@@ -0,0 +1,136 @@
+use crate::text::{Paintable, Spans, StyleKey};
+use std::borrow::Borrow;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A style with metadata attached, such as the source offset or token id of
+/// the text it styles. The text is painted with the style alone, so runs
+/// that differ only in their metadata are painted as one, but the metadata
+/// stays with the text through slicing, truncation and editing.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{Rounding, Spans, Tag, WithMeta};
+/// let bold = Tag::new("<b>", "</b>");
+/// // Each token remembers the byte offset it came from in the source.
+/// let line = Spans::styled(WithMeta::new(bold.clone(), 10), "fn")
+///     .then(WithMeta::new(Tag::default(), 12), " ")
+///     .then(WithMeta::new(bold, 13), "main");
+/// assert_eq!(line.to_string(), "<b>fn</b> <b>main</b>");
+/// // Map the fifth column of the rendered line back to the source.
+/// let byte = line.width_to_byte(5, Rounding::Floor);
+/// assert_eq!(line.style_at(byte).map(|style| style.meta), Some(13));
+/// assert_eq!(line.meta().style_at(2), Some(&12));
+/// assert_eq!(line.without_meta().to_string(), "<b>fn</b> <b>main</b>");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct WithMeta<S, M> {
+    /// The style the text is painted with
+    pub style: S,
+    /// Metadata that is not painted
+    pub meta: M,
+}
+
+impl<S, M> WithMeta<S, M> {
+    /// Attaches metadata to a style.
+    pub fn new(style: S, meta: M) -> Self {
+        WithMeta { style, meta }
+    }
+}
+
+/// Borrows the style of a [`WithMeta`], so that groups of styles with
+/// metadata can be painted by the style type.
+struct StyleOf<U, S, M>(U, PhantomData<(S, M)>);
+
+impl<U: Borrow<WithMeta<S, M>>, S, M> Borrow<S> for StyleOf<U, S, M> {
+    fn borrow(&self) -> &S {
+        &self.0.borrow().style
+    }
+}
+
+fn styles_of<'a, T, U, V, S, M>(groups: T) -> impl Iterator<Item = (StyleOf<U, S, M>, V)> + 'a
+where
+    T: IntoIterator<Item = (U, V)> + 'a,
+    U: Borrow<WithMeta<S, M>> + 'a,
+    S: 'a,
+    M: 'a,
+{
+    groups
+        .into_iter()
+        .map(|(style, text)| (StyleOf(style, PhantomData), text))
+}
+
+impl<S: Paintable, M> Paintable for WithMeta<S, M> {
+    fn paint(&self, target: &str) -> String {
+        self.style.paint(target)
+    }
+    fn paint_many<'a, T, U, V>(groups: T) -> String
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        S::paint_many(styles_of(groups))
+    }
+    fn markup_width(&self) -> usize {
+        self.style.markup_width()
+    }
+    fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.style.paint_fmt(target, f)
+    }
+    fn paint_many_fmt<'a, T, U, V>(groups: T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        S::paint_many_fmt(styles_of(groups), f)
+    }
+}
+
+impl<S: StyleKey, M> StyleKey for WithMeta<S, M> {
+    fn style_key(&self) -> u64 {
+        self.style.style_key()
+    }
+}
+
+impl<S: Default, M: Default> Spans<WithMeta<S, M>> {
+    /// Returns the text with its metadata removed.
+    pub fn without_meta(&self) -> Spans<S>
+    where
+        S: Clone + PartialEq,
+    {
+        self.map_style(|style| style.style.clone())
+    }
+    /// Returns the text styled with its metadata.
+    pub fn meta(&self) -> Spans<M>
+    where
+        M: Clone + PartialEq,
+    {
+        self.map_style(|style| style.meta.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Color, Pushable, Sliceable, Tag};
+    #[test]
+    fn meta_survives_slicing() {
+        let red = Color::Red.normal();
+        let text = Spans::styled(WithMeta::new(red, 0), "ab").then(WithMeta::new(red, 1), "cd");
+        assert_eq!(text.to_string(), "\x1b[31mabcd\x1b[0m");
+        assert_eq!(text.span_count(), 2);
+        let sliced = text.slice(1..3).unwrap();
+        assert_eq!(sliced.style_at(1).map(|style| style.meta), Some(1));
+        assert_eq!(sliced.without_meta(), Spans::styled(red, "bc"));
+        assert_eq!(text.style_at(0).unwrap().style_key(), red.style_key());
+    }
+    #[test]
+    fn meta_of_implicit_style() {
+        let mut text: Spans<WithMeta<Tag, u8>> = Default::default();
+        text.push(&"ab");
+        assert_eq!(text.meta().style_at(0), Some(&0));
+        assert_eq!(text.without_meta().style_at(1), Some(&Tag::default()));
+    }
+}
@@ -2,27 +2,79 @@
 //! of styled spans, as well as traits providing support for string-like
 //! methods on structs.
 
+mod annotatable;
+pub(crate) mod bounds;
+mod by_content;
+mod color_level;
+mod diff;
+mod direction;
+#[cfg(feature = "regex")]
 mod expandable;
+mod fn_painter;
+mod format;
+mod html;
 mod joinable;
+mod markdown;
+mod markup;
+mod meta;
+#[cfg(feature = "termcolor")]
+mod paint_write;
 mod paintable;
 mod pushable;
+#[cfg(feature = "regex")]
+mod regex_replaceable;
 mod replaceable;
+#[cfg(feature = "rope")]
+mod rope_spans;
+mod sanitize;
 mod sliceable;
 mod spans;
 mod splitable;
+pub mod stream;
+mod style_key;
+#[cfg(feature = "syntect")]
+mod syntax_highlighting;
 mod tag;
+mod template;
+mod theme;
 mod width;
+mod width_cache;
+mod width_policy;
 mod width_sliceable;
+pub use annotatable::Annotatable;
+pub use by_content::ByContent;
+pub use color_level::ColorLevel;
+pub use diff::{diff, DiffStyles};
+pub use direction::Direction;
+#[cfg(feature = "regex")]
 pub use expandable::Expandable;
-pub use joinable::Joinable;
+pub use fn_painter::FnPainter;
+pub use format::{Color, Format};
+pub use html::{HtmlAttributes, HtmlRenderer};
+pub use joinable::{JoinWith, Joinable};
+pub use markdown::{MarkdownAttributes, MarkdownRenderer};
+pub use markup::{MarkupError, MarkupParser};
+pub use meta::WithMeta;
+#[cfg(feature = "termcolor")]
+pub use paint_write::PaintWrite;
 pub use paintable::Paintable;
 pub use pushable::Pushable;
+#[cfg(feature = "regex")]
+pub use regex_replaceable::RegexReplaceable;
 pub use replaceable::*;
+#[cfg(feature = "rope")]
+pub use rope_spans::RopeSpans;
+pub use sanitize::ControlPolicy;
 pub use sliceable::*;
 pub use spans::*;
 pub use splitable::*;
+pub use style_key::StyleKey;
 pub use tag::*;
+pub use template::{Template, TemplateError};
+pub use theme::Theme;
 pub use width::*;
+pub use width_cache::{Cached, WidthCache};
+pub use width_policy::WidthPolicy;
 pub use width_sliceable::*;
 
 /// Support for converting a text object into a raw, unstyled string
@@ -45,6 +97,23 @@ pub trait RawText {
     /// assert_eq!(foo.raw_ref(), &foo);
     /// ```
     fn raw_ref(&self) -> &str;
+    /// Returns whether two text objects have the same raw contents, whatever
+    /// their styles.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{RawText, Spans, Tag};
+    /// let bold: Spans<Tag> = Spans::styled(Tag::new("<b>", "</b>"), "foo");
+    /// assert!(bold.content_eq(&Spans::<Tag>::from("foo")));
+    /// assert!(bold.content_eq(&"foo"));
+    /// assert!(!bold.content_eq(&"Foo"));
+    /// ```
+    fn content_eq<S: RawText>(&self, other: &S) -> bool
+    where
+        Self: Sized,
+    {
+        self.raw_ref() == other.raw_ref()
+    }
 }
 
 impl RawText for String {
@@ -56,11 +125,20 @@ impl RawText for String {
     }
 }
 
+impl<T: RawText> RawText for &T {
+    fn raw(&self) -> String {
+        (*self).raw()
+    }
+    fn raw_ref(&self) -> &str {
+        (*self).raw_ref()
+    }
+}
+
 impl RawText for &str {
     fn raw(&self) -> String {
         self.to_string()
     }
     fn raw_ref(&self) -> &str {
-        *self
+        self
     }
 }
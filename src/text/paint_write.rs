@@ -0,0 +1,116 @@
+use crate::text::{Color, Format, RawText, Spans};
+use std::io;
+use termcolor::{ColorSpec, WriteColor};
+
+/// Provides functionality to write styled text through a [`WriteColor`] sink,
+/// such as termcolor's Windows console aware standard streams, rather than as
+/// escape sequences through [`Display`](std::fmt::Display).
+pub trait PaintWrite {
+    /// Writes a string in this style to a color-aware writer, leaving the
+    /// writer's color reset afterwards.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::PaintWrite;
+    /// use termcolor::{Buffer, Color, ColorSpec};
+    /// let mut red = ColorSpec::new();
+    /// red.set_fg(Some(Color::Red));
+    /// let mut buffer = Buffer::ansi();
+    /// red.paint_write("foo", &mut buffer).unwrap();
+    /// assert_eq!(buffer.as_slice(), b"\x1b[0m\x1b[31mfoo\x1b[0m");
+    /// ```
+    fn paint_write(&self, target: &str, w: &mut dyn WriteColor) -> io::Result<()>;
+}
+
+impl PaintWrite for ColorSpec {
+    fn paint_write(&self, target: &str, w: &mut dyn WriteColor) -> io::Result<()> {
+        w.set_color(self)?;
+        w.write_all(target.as_bytes())?;
+        w.reset()
+    }
+}
+
+impl PaintWrite for Format {
+    fn paint_write(&self, target: &str, w: &mut dyn WriteColor) -> io::Result<()> {
+        ColorSpec::from(*self).paint_write(target, w)
+    }
+}
+
+impl From<Color> for termcolor::Color {
+    /// Converts a color, using the 256 color palette for bright colors since
+    /// termcolor's intensity applies to the foreground and background alike.
+    fn from(color: Color) -> Self {
+        use termcolor::Color as C;
+        match color {
+            Color::Black => C::Black,
+            Color::Red => C::Red,
+            Color::Green => C::Green,
+            Color::Yellow => C::Yellow,
+            Color::Blue => C::Blue,
+            Color::Magenta => C::Magenta,
+            Color::Cyan => C::Cyan,
+            Color::White => C::White,
+            Color::BrightBlack => C::Ansi256(8),
+            Color::BrightRed => C::Ansi256(9),
+            Color::BrightGreen => C::Ansi256(10),
+            Color::BrightYellow => C::Ansi256(11),
+            Color::BrightBlue => C::Ansi256(12),
+            Color::BrightMagenta => C::Ansi256(13),
+            Color::BrightCyan => C::Ansi256(14),
+            Color::BrightWhite => C::Ansi256(15),
+            Color::Fixed(index) => C::Ansi256(index),
+            Color::Rgb(r, g, b) => C::Rgb(r, g, b),
+        }
+    }
+}
+
+impl From<Format> for ColorSpec {
+    fn from(format: Format) -> Self {
+        let mut spec = ColorSpec::new();
+        spec.set_fg(format.fg.map(Into::into))
+            .set_bg(format.bg.map(Into::into))
+            .set_bold(format.bold)
+            .set_italic(format.italic)
+            .set_underline(format.underline);
+        spec
+    }
+}
+
+impl<T: PaintWrite + Clone + Default> Spans<T> {
+    /// Writes the text through a color-aware writer, one run at a time.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Color, Pushable, Span, Spans};
+    /// use termcolor::Buffer;
+    /// let red = Color::Red.normal();
+    /// let mut spans: Spans<_> = Default::default();
+    /// spans.push(&Span::borrowed(&red, "foo"));
+    /// let mut buffer = Buffer::no_color();
+    /// spans.write_to(&mut buffer).unwrap();
+    /// assert_eq!(buffer.as_slice(), b"foo");
+    /// ```
+    pub fn write_to(&self, w: &mut dyn WriteColor) -> io::Result<()> {
+        for span in self.spans() {
+            span.style().paint_write(span.raw_ref(), w)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Pushable, Span};
+    use termcolor::Buffer;
+    #[test]
+    fn write_format_spans() {
+        let mut spans: Spans<Format> = Default::default();
+        spans.push(&Span::borrowed(&Color::BrightRed.bold(), "foo"));
+        spans.push(&Span::borrowed(&Format::new(), "bar"));
+        let mut buffer = Buffer::ansi();
+        spans.write_to(&mut buffer).unwrap();
+        let expected = "\x1b[0m\x1b[1m\x1b[38;5;9mfoo\x1b[0m\x1b[0mbar\x1b[0m";
+        assert_eq!(String::from_utf8_lossy(buffer.as_slice()), expected);
+    }
+}
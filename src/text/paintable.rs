@@ -1,6 +1,7 @@
-#[cfg(test)]
+#[cfg(any(test, feature = "ansi"))]
 use ansi_term::{ANSIStrings, Style};
 use std::borrow::Borrow;
+use std::fmt;
 /// Provides functionality to display strings with markup.
 pub trait Paintable {
     /// Applies markup to a given string.
@@ -112,9 +113,100 @@ pub trait Paintable {
         }
         result
     }
+    /// Returns the number of columns occupied by the markup this applies, for
+    /// backends where markup is displayed literally. Defaults to zero, which is
+    /// correct for invisible markup such as ANSI escape codes.
+    fn markup_width(&self) -> usize {
+        0
+    }
+    /// Writes markup applied to a given string directly into a formatter.
+    /// Provide an implementation for this to avoid allocating an intermediate
+    /// [`String`] when displaying text objects.
+    ///
+    /// # Example
+    /// ```
+    /// use std::fmt;
+    /// use stylish_stringlike::text::Paintable;
+    /// struct Bold;
+    ///
+    /// impl Paintable for Bold {
+    ///     fn paint(&self, target: &str) -> String {
+    ///         format!("<b>{}</b>", target)
+    ///     }
+    ///     fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "<b>{}</b>", target)
+    ///     }
+    /// }
+    /// struct Painted(&'static str);
+    /// impl fmt::Display for Painted {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         Bold.paint_fmt(self.0, f)
+    ///     }
+    /// }
+    /// assert_eq!(Painted("foo").to_string(), String::from("<b>foo</b>"));
+    /// ```
+    fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.paint(target))
+    }
+    /// Writes markup applied to a given iterator of ([`Paintable`], [`str`])
+    /// objects directly into a formatter. This is what [`Display`](fmt::Display)
+    /// uses for text objects.
+    ///
+    /// The default implementation writes the output of [`Paintable::paint_many`].
+    /// Provide an implementation for this that streams into the formatter to
+    /// avoid allocating when displaying text objects.
+    fn paint_many_fmt<'a, T, U, V>(groups: T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        f.write_str(&Self::paint_many(groups))
+    }
 }
 
-#[cfg(test)]
+/// Paints text unchanged, for rendering text without styles, as returned by
+/// [`crate::text::Spans::strip`].
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::Paintable;
+/// assert_eq!(().paint("foo"), "foo");
+/// assert_eq!(<()>::paint_many(vec![((), "foo"), ((), "bar")]), "foobar");
+/// ```
+impl Paintable for () {
+    fn paint(&self, target: &str) -> String {
+        target.to_string()
+    }
+    fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(target)
+    }
+    fn paint_many_fmt<'a, T, U, V>(groups: T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        for (_, text) in groups {
+            f.write_str(text.borrow())?;
+        }
+        Ok(())
+    }
+}
+
+/// Paints with [`ansi_term`] styles. Requires the `ansi` feature, which is on by
+/// default.
+///
+/// # Example
+/// ```
+/// use ansi_term::Color;
+/// use stylish_stringlike::text::{Pushable, Span, Spans};
+/// let red = Color::Red.normal();
+/// let mut spans: Spans<_> = Default::default();
+/// spans.push(&Span::borrowed(&red, "foo"));
+/// assert_eq!(spans.to_string(), red.paint("foo").to_string());
+/// ```
+#[cfg(any(test, feature = "ansi"))]
 impl Paintable for Style {
     fn paint(&self, target: &str) -> String {
         Style::paint(*self, target).to_string()
@@ -132,4 +224,185 @@ impl Paintable for Style {
         }
         format!("{}", ANSIStrings(strings.as_slice()))
     }
+    fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Style::paint(*self, target))
+    }
+    fn paint_many_fmt<'a, T, U, V>(groups: T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Style> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        // Mirrors the output of `ANSIStrings` without collecting the groups.
+        let mut previous: Option<Style> = None;
+        for (style, text) in groups {
+            let style = *style.borrow();
+            match previous {
+                Some(p) => write!(f, "{}", p.infix(style))?,
+                None => write!(f, "{}", style.prefix())?,
+            }
+            f.write_str(text.borrow())?;
+            previous = Some(style);
+        }
+        if let Some(p) = previous {
+            write!(f, "{}", p.suffix())?;
+        }
+        Ok(())
+    }
+}
+
+/// Paints with [`anstyle`] styles. Requires the `anstyle` feature.
+///
+/// # Example
+/// ```
+/// use anstyle::{AnsiColor, Style};
+/// use stylish_stringlike::text::Paintable;
+/// let red = Style::new().fg_color(Some(AnsiColor::Red.into()));
+/// assert_eq!(red.paint("foo"), "\x1b[31mfoo\x1b[0m");
+/// ```
+#[cfg(feature = "anstyle")]
+impl Paintable for anstyle::Style {
+    fn paint(&self, target: &str) -> String {
+        format!("{}{}{}", self.render(), target, self.render_reset())
+    }
+    fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.render(), target, self.render_reset())
+    }
+    fn paint_many<'a, T, U, V>(groups: T) -> String
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        let mut result = String::new();
+        // Writing into a `String` cannot fail.
+        let _ = write_anstyle(groups, &mut result);
+        result
+    }
+    fn paint_many_fmt<'a, T, U, V>(groups: T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        write_anstyle(groups, f)
+    }
+}
+
+/// Writes groups of text, switching style only where it changes. Unlike
+/// `ansi_term`, `anstyle` cannot compute the difference between two styles,
+/// so each change resets the previous style with
+/// [`anstyle::Style::render_reset`], which writes nothing for a plain style,
+/// before rendering the next one in full.
+#[cfg(feature = "anstyle")]
+fn write_anstyle<'a, T, U, V, W>(groups: T, w: &mut W) -> fmt::Result
+where
+    T: IntoIterator<Item = (U, V)> + 'a,
+    U: Borrow<anstyle::Style> + 'a,
+    V: Borrow<str> + 'a,
+    W: fmt::Write,
+{
+    let mut previous: Option<anstyle::Style> = None;
+    for (style, text) in groups {
+        let style = *style.borrow();
+        if previous != Some(style) {
+            if let Some(p) = previous {
+                write!(w, "{}", p.render_reset())?;
+            }
+            write!(w, "{}", style.render())?;
+            previous = Some(style);
+        }
+        w.write_str(text.borrow())?;
+    }
+    if let Some(p) = previous {
+        write!(w, "{}", p.render_reset())?;
+    }
+    Ok(())
+}
+
+/// Paints with [`owo_colors`] styles. Requires the `owo-colors` feature.
+///
+/// # Example
+/// ```
+/// use owo_colors::Style;
+/// use stylish_stringlike::text::Paintable;
+/// let red = Style::new().red();
+/// assert_eq!(red.paint("foo"), "\x1b[31mfoo\x1b[0m");
+/// ```
+#[cfg(feature = "owo-colors")]
+impl Paintable for owo_colors::Style {
+    fn paint(&self, target: &str) -> String {
+        self.style(target).to_string()
+    }
+    fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.style(target))
+    }
+    fn paint_many<'a, T, U, V>(groups: T) -> String
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        let mut result = String::new();
+        // Writing into a `String` cannot fail.
+        let _ = write_owo_colors(groups, &mut result);
+        result
+    }
+    fn paint_many_fmt<'a, T, U, V>(groups: T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        write_owo_colors(groups, f)
+    }
+}
+
+/// Writes groups of text, switching style only where it changes. Each change
+/// ends the previous style with its suffix, a reset that is only written for
+/// styles that set anything, and then writes the prefix of the next style in
+/// full.
+#[cfg(feature = "owo-colors")]
+fn write_owo_colors<'a, T, U, V, W>(groups: T, w: &mut W) -> fmt::Result
+where
+    T: IntoIterator<Item = (U, V)> + 'a,
+    U: Borrow<owo_colors::Style> + 'a,
+    V: Borrow<str> + 'a,
+    W: fmt::Write,
+{
+    let mut previous: Option<owo_colors::Style> = None;
+    for (style, text) in groups {
+        let style = *style.borrow();
+        if previous != Some(style) {
+            if let Some(p) = previous {
+                write!(w, "{}", p.suffix_formatter())?;
+            }
+            write!(w, "{}", style.prefix_formatter())?;
+            previous = Some(style);
+        }
+        w.write_str(text.borrow())?;
+    }
+    if let Some(p) = previous {
+        write!(w, "{}", p.suffix_formatter())?;
+    }
+    Ok(())
+}
+
+/// Paints with [`crossterm`] styles. Requires the `crossterm` feature.
+///
+/// # Example
+/// ```
+/// use crossterm::style::{ContentStyle, Stylize};
+/// use stylish_stringlike::text::Paintable;
+/// let red = ContentStyle::new().red();
+/// assert_eq!(red.paint("foo"), red.apply("foo").to_string());
+/// ```
+#[cfg(feature = "crossterm")]
+impl Paintable for crossterm::style::ContentStyle {
+    fn paint(&self, target: &str) -> String {
+        self.apply(target).to_string()
+    }
+    fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.apply(target))
+    }
 }
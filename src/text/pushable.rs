@@ -1,3 +1,6 @@
+use crate::text::Sliceable;
+use std::ops::RangeBounds;
+
 /// Trait for text objects that can have content pushed into them without changing type.
 pub trait Pushable<T: ?Sized> {
     /// Pushes another text object onto this one. [`String`] implements this
@@ -12,6 +15,31 @@ pub trait Pushable<T: ?Sized> {
     /// assert_eq!(foobar, String::from("foobar"));
     /// ```
     fn push(&mut self, other: &T);
+    /// Pushes a byte range of another text object onto this one, as pushing
+    /// the output of [`Sliceable::slice`] would. Returns whether the range
+    /// could be sliced. Text that can copy the range straight from the other
+    /// object does so without building the slice first.
+    ///
+    /// # Example
+    /// ```rust
+    /// use stylish_stringlike::text::Pushable;
+    /// let mut foo = String::from("> ");
+    /// assert!(foo.push_slice(&String::from("foobar"), 1..4));
+    /// assert_eq!(foo, String::from("> oob"));
+    /// ```
+    fn push_slice<R>(&mut self, other: &T, range: R) -> bool
+    where
+        T: Sliceable + Sized,
+        R: RangeBounds<usize> + Clone,
+    {
+        match other.slice(range) {
+            Some(slice) => {
+                self.push(&slice);
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 impl Pushable<str> for String {
@@ -20,10 +48,28 @@ impl Pushable<str> for String {
     }
 }
 
+impl Pushable<&str> for String {
+    fn push(&mut self, other: &&str) {
+        self.push_str(other);
+    }
+}
+
 impl Pushable<String> for String {
     fn push(&mut self, other: &String) {
         self.push_str(other.as_str());
     }
+    fn push_slice<R>(&mut self, other: &String, range: R) -> bool
+    where
+        R: RangeBounds<usize> + Clone,
+    {
+        match other.as_str().slice(range) {
+            Some(slice) => {
+                self.push_str(slice);
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 impl<S, O: Sized> Pushable<Option<O>> for S
@@ -31,9 +77,8 @@ where
     S: Pushable<O>,
 {
     fn push(&mut self, other: &Option<O>) {
-        match other {
-            Some(ref o) => self.push(o),
-            None => {}
+        if let Some(ref o) = other {
+            self.push(o)
         }
     }
 }
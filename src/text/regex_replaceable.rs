@@ -0,0 +1,59 @@
+use super::{Expandable, Pushable, RawText, Sliceable};
+use regex::Regex;
+/// Replacing regex matches in text-like objects.
+///
+/// This is implemented for [`String`] by default.
+pub trait RegexReplaceable<T> {
+    /// Perform regex string replacement.
+    ///
+    /// # Example
+    /// ```rust
+    /// use regex::Regex;
+    /// use stylish_stringlike::text::*;
+    /// let foooo = String::from("foooo");
+    /// let re = Regex::new("fo+").unwrap();
+    /// let bar = RegexReplaceable::<&String>::replace_regex(&foooo, &re, &String::from("bar"));
+    /// assert_eq!(bar, String::from("bar"));
+    /// ```
+    fn replace_regex(&self, searcher: &Regex, replacer: T) -> Self;
+}
+
+impl<'a, T> RegexReplaceable<&'a T> for T
+where
+    T: Default + RawText + Sliceable + Pushable<T> + Expandable,
+{
+    fn replace_regex(&self, searcher: &Regex, replacer: &'a T) -> Self {
+        let mut result: T = Default::default();
+        let mut last_end = 0;
+        let captures = searcher.captures_iter(self.raw_ref());
+        for capture in captures {
+            let mat = capture
+                .get(0)
+                .expect("Captures are always supposed to have at least one match");
+            if let Some(slice) = self.slice(last_end..mat.start()) {
+                result.push(&slice);
+                if let Some(_original) = self.slice(mat.start()..mat.end()) {
+                    let expanded = replacer.expand(&capture);
+                    result.push(&expanded);
+                }
+            }
+            last_end = mat.end();
+        }
+        if let Some(spans) = self.slice(last_end..) {
+            result.push(&spans);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn test_string_regex_replace() {
+        let foooo = String::from("foooo");
+        let re = Regex::new("fo+").unwrap();
+        let bar = RegexReplaceable::<&String>::replace_regex(&foooo, &re, &String::from("bar"));
+        assert_eq!(bar, String::from("bar"));
+    }
+}
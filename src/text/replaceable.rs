@@ -1,8 +1,8 @@
-use super::{Expandable, Pushable, RawText, Sliceable};
-use regex::Regex;
+use super::{Pushable, RawText, Sliceable};
 /// Replacing text in text-like objects.
 ///
-/// This is implemented for [`String`] by default.
+/// This is implemented for [`String`] by default. Replacing regex matches is
+/// provided by [`crate::text::RegexReplaceable`] with the `regex` feature.
 pub trait Replaceable<T> {
     /// Perform literal string replacement.
     ///
@@ -14,23 +14,11 @@ pub trait Replaceable<T> {
     /// assert_eq!(String::from("bar"), bar);
     /// ```
     fn replace(&self, from: &str, replacer: T) -> Self;
-    /// Perform regex string replacement.
-    ///
-    /// # Example
-    /// ```rust
-    /// use regex::Regex;
-    /// use stylish_stringlike::text::*;
-    /// let foooo = String::from("foooo");
-    /// let re = Regex::new("fo+").unwrap();
-    /// let bar = Replaceable::<&String>::replace_regex(&foooo, &re, &String::from("bar"));
-    /// assert_eq!(bar, String::from("bar"));
-    /// ```
-    fn replace_regex(&self, searcher: &Regex, replacer: T) -> Self;
 }
 
 impl<'a, T> Replaceable<&'a T> for T
 where
-    T: Default + RawText + Sliceable + Pushable<T> + Expandable,
+    T: Default + RawText + Sliceable + Pushable<T>,
 {
     fn replace(&self, from: &str, replacer: &'a T) -> Self {
         let mut result: T = Default::default();
@@ -54,28 +42,6 @@ where
         }
         result
     }
-    fn replace_regex(&self, searcher: &Regex, replacer: &'a T) -> Self {
-        let mut result: T = Default::default();
-        let mut last_end = 0;
-        let captures = searcher.captures_iter(self.raw_ref());
-        for capture in captures {
-            let mat = capture
-                .get(0)
-                .expect("Captures are always supposed to have at least one match");
-            if let Some(slice) = self.slice(last_end..mat.start()) {
-                result.push(&slice);
-                if let Some(_original) = self.slice(mat.start()..mat.end()) {
-                    let expanded = replacer.expand(&capture);
-                    result.push(&expanded);
-                }
-            }
-            last_end = mat.end();
-        }
-        if let Some(spans) = self.slice(last_end..) {
-            result.push(&spans);
-        }
-        result
-    }
 }
 
 #[cfg(test)]
@@ -88,11 +54,4 @@ mod test {
         eprintln!("bar: {}", bar);
         assert_eq!(bar, String::from("bar"));
     }
-    #[test]
-    fn test_string_regex_replace() {
-        let foooo = String::from("foooo");
-        let re = Regex::new("fo+").unwrap();
-        let bar = Replaceable::<&String>::replace_regex(&foooo, &re, &String::from("bar"));
-        assert_eq!(bar, String::from("bar"));
-    }
 }
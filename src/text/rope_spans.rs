@@ -0,0 +1,393 @@
+//! Styled text backed by a rope, for editing large documents. Requires the
+//! `rope` feature.
+use crate::text::{bounds, Paintable, RawText, Span, Spans};
+use ropey::Rope;
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::RangeBounds;
+use std::sync::Arc;
+
+/// A run of text in one style, as a node of a treap ordered by position.
+/// Nodes store lengths rather than offsets, so that editing the text only
+/// touches the nodes on the path to the edit.
+#[derive(Clone)]
+struct Run<T> {
+    style: Arc<T>,
+    /// Length of the run in bytes
+    len: usize,
+    /// Length in bytes of the runs in this subtree
+    total: usize,
+    priority: u32,
+    left: Tree<T>,
+    right: Tree<T>,
+}
+
+type Tree<T> = Option<Box<Run<T>>>;
+
+fn total<T>(tree: &Tree<T>) -> usize {
+    tree.as_ref().map_or(0, |run| run.total)
+}
+
+impl<T> Run<T> {
+    fn new(style: Arc<T>, len: usize, priority: u32) -> Box<Self> {
+        Box::new(Run {
+            style,
+            len,
+            total: len,
+            priority,
+            left: None,
+            right: None,
+        })
+    }
+    fn update(&mut self) {
+        self.total = total(&self.left) + self.len + total(&self.right);
+    }
+}
+
+/// Joins two trees, with every run of `a` before every run of `b`.
+fn merge<T>(a: Tree<T>, b: Tree<T>) -> Tree<T> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => {
+            if a.priority >= b.priority {
+                a.right = merge(a.right.take(), Some(b));
+                a.update();
+                Some(a)
+            } else {
+                b.left = merge(Some(a), b.left.take());
+                b.update();
+                Some(b)
+            }
+        }
+    }
+}
+
+/// Splits a tree at a byte index, cutting the run that spans it in two.
+fn split<T>(tree: Tree<T>, index: usize) -> (Tree<T>, Tree<T>) {
+    let mut run = match tree {
+        Some(run) => run,
+        None => return (None, None),
+    };
+    let start = total(&run.left);
+    if index <= start {
+        let (left, right) = split(run.left.take(), index);
+        run.left = right;
+        run.update();
+        (left, Some(run))
+    } else if index >= start + run.len {
+        let (left, right) = split(run.right.take(), index - start - run.len);
+        run.right = left;
+        run.update();
+        (Some(run), right)
+    } else {
+        // The tail keeps the priority of the run, which is at least that of
+        // the right subtree it takes.
+        let mut tail = Run::new(run.style.clone(), start + run.len - index, run.priority);
+        tail.right = run.right.take();
+        tail.update();
+        run.len = index - start;
+        run.update();
+        (Some(run), Some(tail))
+    }
+}
+
+/// Lengthens the run containing a byte index.
+fn grow<T>(run: &mut Run<T>, index: usize, by: usize) {
+    let start = total(&run.left);
+    match (&mut run.left, &mut run.right) {
+        (Some(left), _) if index < start => grow(left, index, by),
+        (_, Some(right)) if index >= start + run.len => grow(right, index - start - run.len, by),
+        _ => run.len += by,
+    }
+    run.total += by;
+}
+
+/// Collects the style and byte range of each run overlapping a byte range,
+/// clipped to it.
+fn collect<'a, T>(
+    tree: &'a Tree<T>,
+    offset: usize,
+    range: (usize, usize),
+    out: &mut Vec<(&'a T, usize, usize)>,
+) {
+    let run = match tree {
+        Some(run) if offset < range.1 && range.0 < offset + run.total => run,
+        _ => return,
+    };
+    let start = offset + total(&run.left);
+    collect(&run.left, offset, range, out);
+    let (from, to) = (start.max(range.0), (start + run.len).min(range.1));
+    if from < to {
+        out.push((&run.style, from, to));
+    }
+    collect(&run.right, start + run.len, range, out);
+}
+
+/// Styled text stored in a rope, so that inserting and removing text in the
+/// middle of a large document takes time logarithmic in its length rather
+/// than copying the text after the edit, as [`Spans`] does. Requires the
+/// `rope` feature.
+///
+/// Styles are kept in a balanced tree of runs, so that editing the styles
+/// takes time logarithmic in the number of runs. Indices are byte indices,
+/// as for [`Spans`]. Text is read out of the rope as [`Spans`] with
+/// [`RopeSpans::get`] or [`RopeSpans::to_spans`].
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{RopeSpans, Span, Spans, Tag};
+/// let bold = Tag::new("<b>", "</b>");
+/// let mut doc = RopeSpans::from(&Spans::styled(Tag::default(), "hello world"));
+/// doc.insert(6, &Span::borrowed(&bold, "big "));
+/// doc.insert_str(5, ",");
+/// assert_eq!(doc.to_string(), "hello, <b>big </b>world");
+/// doc.remove(0..7);
+/// assert_eq!(doc.get(..5).unwrap().to_string(), "<b>big </b>w");
+/// assert_eq!(doc.len(), 9);
+/// ```
+#[derive(Clone)]
+pub struct RopeSpans<T> {
+    rope: Rope,
+    runs: Tree<T>,
+    /// State of the generator for tree priorities
+    seed: u32,
+}
+
+impl<T> Default for RopeSpans<T> {
+    fn default() -> Self {
+        RopeSpans {
+            rope: Rope::new(),
+            runs: None,
+            seed: 0x9E37_79B9,
+        }
+    }
+}
+
+impl<T> RopeSpans<T> {
+    /// Creates an empty document.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Returns the text of the document without its styles.
+    pub fn rope(&self) -> &Rope {
+        &self.rope
+    }
+    /// Returns the length of the text in bytes.
+    pub fn len(&self) -> usize {
+        self.rope.len_bytes()
+    }
+    /// Whether the document has no text.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn priority(&mut self) -> u32 {
+        // xorshift32
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        self.seed
+    }
+    /// Converts a byte index into a char index, panicking with `message` if
+    /// it is out of bounds or not on a [`char`] boundary.
+    fn char_index(&self, index: usize, message: &str) -> usize {
+        let char_index = self.rope.try_byte_to_char(index).expect(message);
+        assert_eq!(self.rope.char_to_byte(char_index), index, "{}", message);
+        char_index
+    }
+    /// Inserts text in one style at a byte index.
+    fn insert_run(&mut self, index: usize, style: Arc<T>, text: &str) {
+        let char_index = self.char_index(index, "insertion index is not a char boundary");
+        if text.is_empty() {
+            return;
+        }
+        self.rope.insert(char_index, text);
+        let priority = self.priority();
+        let (before, after) = split(self.runs.take(), index);
+        let run = Some(Run::new(style, text.len(), priority));
+        self.runs = merge(merge(before, run), after);
+    }
+    /// Inserts a styled span at a byte index, shifting the text after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is larger than the length of the text or does not
+    /// lie on a [`char`] boundary.
+    pub fn insert(&mut self, index: usize, span: &Span<'_, T>)
+    where
+        T: Clone,
+    {
+        let style = Arc::new(span.style().clone().into_owned());
+        self.insert_run(index, style, span.raw_ref());
+    }
+    /// Inserts styled text at a byte index, shifting the text after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is larger than the length of the text or does not
+    /// lie on a [`char`] boundary.
+    pub fn insert_spans(&mut self, index: usize, other: &Spans<T>)
+    where
+        T: Clone + Default,
+    {
+        let mut index = index;
+        for span in other.spans() {
+            let text = span.raw_ref();
+            self.insert(index, &span);
+            index += text.len();
+        }
+    }
+    /// Inserts unstyled text at a byte index. The new text takes the style
+    /// of the text preceding it, or of the text following it if inserted
+    /// at the start.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is larger than the length of the text or does not
+    /// lie on a [`char`] boundary.
+    pub fn insert_str(&mut self, index: usize, text: &str)
+    where
+        T: Default,
+    {
+        if self.runs.is_none() {
+            return self.insert_run(index, Arc::new(T::default()), text);
+        }
+        let char_index = self.char_index(index, "insertion index is not a char boundary");
+        self.rope.insert(char_index, text);
+        if let Some(runs) = &mut self.runs {
+            grow(runs, index.saturating_sub(1), text.len());
+        }
+    }
+    /// Removes the text in a byte range, shifting the text after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or does not lie on [`char`]
+    /// boundaries.
+    pub fn remove<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let message = "removal range is out of bounds or not on char boundaries";
+        let (start, end) = bounds::normalize_within(&range, self.len());
+        let (char_start, char_end) = (
+            self.char_index(start, message),
+            self.char_index(end, message),
+        );
+        assert!(start <= end, "{}", message);
+        self.rope.remove(char_start..char_end);
+        let (before, rest) = split(self.runs.take(), start);
+        let (_, after) = split(rest, end - start);
+        self.runs = merge(before, after);
+    }
+    /// Copies the text in a byte range, or returns [`None`] if the range is
+    /// out of bounds or does not lie on [`char`] boundaries.
+    pub fn get<R>(&self, range: R) -> Option<Spans<T>>
+    where
+        R: RangeBounds<usize>,
+        T: Clone + PartialEq,
+    {
+        let (start, end) = bounds::normalize_within(&range, self.len());
+        self.rope.get_byte_slice(start..end)?;
+        let mut runs = vec![];
+        collect(&self.runs, 0, (start, end), &mut runs);
+        Some(
+            runs.into_iter()
+                .map(|(style, from, to)| {
+                    let text = self.rope.byte_slice(from..to).to_string();
+                    Span::new(Cow::Borrowed(style), Cow::Owned(text))
+                })
+                .collect(),
+        )
+    }
+    /// Copies the document into a [`Spans`].
+    pub fn to_spans(&self) -> Spans<T>
+    where
+        T: Clone + PartialEq,
+    {
+        self.get(..).expect("the whole document is a valid range")
+    }
+}
+
+impl<T: Clone + Default> From<&Spans<T>> for RopeSpans<T> {
+    fn from(spans: &Spans<T>) -> Self {
+        let mut result = RopeSpans::new();
+        result.insert_spans(0, spans);
+        result
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RopeSpans<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut runs = vec![];
+        collect(&self.runs, 0, (0, self.len()), &mut runs);
+        f.debug_list()
+            .entries(
+                runs.into_iter()
+                    .map(|(style, from, to)| (style, self.rope.byte_slice(from..to).to_string())),
+            )
+            .finish()
+    }
+}
+
+impl<T: Paintable + Clone + Default + PartialEq> fmt::Display for RopeSpans<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_spans().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Sliceable, Tag};
+    #[test]
+    fn edits_match_spans() {
+        let styles: Vec<Tag> = (0..4)
+            .map(|i| Tag::new(&format!("<{}>", i), &format!("</{}>", i)))
+            .collect();
+        let mut rope: RopeSpans<Tag> = RopeSpans::new();
+        let mut spans: Spans<Tag> = Spans::default();
+        let mut seed = 7_usize;
+        for step in 0..500 {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            let index = if spans.is_empty() {
+                0
+            } else {
+                let mut index = (seed >> 33) % (spans.len() + 1);
+                while !spans.raw_ref().is_char_boundary(index) {
+                    index -= 1;
+                }
+                index
+            };
+            let text = ["ab", "é", "🙈x", ""][step % 4];
+            match step % 5 {
+                0 | 1 => {
+                    let span = Span::borrowed(&styles[step % 3], text);
+                    rope.insert(index, &span);
+                    spans.insert(index, &span);
+                }
+                2 => {
+                    rope.insert_str(index, text);
+                    spans.insert_str(index, text);
+                }
+                _ => {
+                    let end = spans.len().min(index + 3);
+                    let end = (end..=spans.len())
+                        .find(|&end| spans.raw_ref().is_char_boundary(end))
+                        .unwrap();
+                    rope.remove(index..end);
+                    spans.remove(index..end);
+                }
+            }
+            assert_eq!(rope.to_spans(), spans, "step {}", step);
+        }
+        assert_eq!(rope.rope().to_string(), spans.raw());
+        assert_eq!(rope.get(1..), Sliceable::slice(&spans, 1..));
+    }
+    #[test]
+    #[should_panic(expected = "not a char boundary")]
+    fn insert_inside_char_panics() {
+        let mut rope = RopeSpans::from(&Spans::styled(Tag::default(), "é"));
+        rope.insert_str(1, "x");
+    }
+}
@@ -0,0 +1,102 @@
+use crate::text::{RawText, Span, Spans};
+use std::borrow::Cow;
+
+/// What [`Spans::sanitize_with`] does with control characters.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ControlPolicy {
+    /// Replaces control characters with their Unicode control pictures, such
+    /// as `␇` for a bell. Characters without a picture become `�`.
+    #[default]
+    Visualize,
+    /// Removes control characters.
+    Strip,
+    /// Replaces each control character with a given character.
+    Replace(char),
+}
+
+impl ControlPolicy {
+    /// Returns what a control character is replaced with.
+    fn replacement(self, c: char) -> Option<char> {
+        match self {
+            ControlPolicy::Visualize => Some(match c {
+                '\0'..='\x1f' => char::from_u32(0x2400 + c as u32).unwrap_or('\u{fffd}'),
+                '\x7f' => '\u{2421}',
+                _ => '\u{fffd}',
+            }),
+            ControlPolicy::Strip => None,
+            ControlPolicy::Replace(replacement) => Some(replacement),
+        }
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Spans<T> {
+    /// Visualizes control characters, which have no width of their own and
+    /// can corrupt terminal output, keeping line feeds so that text can still
+    /// be split with [`Spans::lines`].
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::spans;
+    /// use stylish_stringlike::text::Tag;
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let text = spans![bold => "ding\x07\n", Tag::default() => "\x1b[31mred"];
+    /// assert_eq!(text.sanitize().to_string(), "<b>ding␇\n</b>␛[31mred");
+    /// ```
+    pub fn sanitize(&self) -> Spans<T> {
+        self.sanitize_with(ControlPolicy::default())
+    }
+    /// Strips or replaces control characters other than line feeds, keeping
+    /// the style of the text they are in.
+    ///
+    /// Tabs are control characters too, so they should be expanded with
+    /// [`Spans::expand_tabs`] first if they are meant to be kept.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{ControlPolicy, RawText, Spans, Tag};
+    /// let text: Spans<Tag> = Spans::from("a\tb\r\n");
+    /// assert_eq!(text.sanitize_with(ControlPolicy::Strip).raw(), "ab\n");
+    /// assert_eq!(text.sanitize_with(ControlPolicy::Replace('?')).raw(), "a?b?\n");
+    /// ```
+    pub fn sanitize_with(&self, policy: ControlPolicy) -> Spans<T> {
+        let is_unsafe = |c: char| c.is_control() && c != '\n';
+        if !self.raw_ref().contains(is_unsafe) {
+            return self.clone();
+        }
+        self.spans()
+            .map(|span| {
+                let sanitized: String = span
+                    .raw_ref()
+                    .chars()
+                    .filter_map(|c| {
+                        if is_unsafe(c) {
+                            policy.replacement(c)
+                        } else {
+                            Some(c)
+                        }
+                    })
+                    .collect();
+                Span::new(span.style().clone(), Cow::Owned(sanitized))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{HasWidth, Tag, Width};
+    #[test]
+    fn sanitize_keeps_styles() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = Spans::styled(fmt_1, "a\x00\x7f").then(fmt_2, "\u{85}b");
+        let sanitized = text.sanitize();
+        assert_eq!("<1>a␀␡</1><2>�b</2>", sanitized.to_string());
+        assert_eq!(Width::Bounded(5), sanitized.width());
+        let stripped = text.sanitize_with(ControlPolicy::Strip);
+        assert_eq!("<1>a</1><2>b</2>", stripped.to_string());
+        let plain: Spans<Tag> = Spans::from("plain\n");
+        assert_eq!(plain, plain.sanitize());
+    }
+}
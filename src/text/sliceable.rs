@@ -1,4 +1,6 @@
-use std::ops::{Bound, RangeBounds};
+use super::{bounds, RawText};
+use crate::Error;
+use std::ops::RangeBounds;
 /// Provides function for slicing a text object on byte index (like [`str::get`])
 pub trait Sliceable {
     /// Slice an underlying text object by bytes.
@@ -16,22 +18,15 @@ pub trait Sliceable {
         Self: Sized;
 }
 
-impl<'a> Sliceable for &'a str {
+impl Sliceable for &str {
     fn slice<R>(&self, range: R) -> Option<Self>
     where
         R: RangeBounds<usize> + Clone,
         Self: Sized,
     {
-        match (range.start_bound(), range.end_bound()) {
-            (Bound::Unbounded, Bound::Unbounded) => self.get(..),
-            (Bound::Unbounded, Bound::Excluded(e)) => self.get(..*e),
-            (Bound::Unbounded, Bound::Included(e)) => self.get(..=*e),
-            (Bound::Excluded(s), Bound::Unbounded) => self.get((*s + 1)..),
-            (Bound::Excluded(s), Bound::Excluded(e)) => self.get((*s + 1)..*e),
-            (Bound::Excluded(s), Bound::Included(e)) => self.get((*s + 1)..=*e),
-            (Bound::Included(s), Bound::Unbounded) => self.get(*s..),
-            (Bound::Included(s), Bound::Excluded(e)) => self.get(*s..*e),
-            (Bound::Included(s), Bound::Included(e)) => self.get(*s..=*e),
+        match bounds::normalize(&range) {
+            (start, Some(end)) => self.get(start..end),
+            (start, None) => self.get(start..),
         }
     }
 }
@@ -45,3 +40,140 @@ impl Sliceable for String {
         self.as_str().slice(range).map(String::from)
     }
 }
+
+/// How [`CheckedSliceable::slice_rounded`] moves indices that fall inside a
+/// character onto a character boundary.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rounding {
+    /// Moves indices back to the start of the character.
+    Floor,
+    /// Moves indices forward to the end of the character.
+    Ceil,
+}
+
+/// Provides byte slicing that explains or corrects indices that
+/// [`Sliceable::slice`] would reject.
+pub trait CheckedSliceable: Sized {
+    /// Slices by bytes, returning why the range is invalid if it is.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::Error;
+    /// use stylish_stringlike::text::CheckedSliceable;
+    /// let text = "aé";
+    /// assert_eq!(text.try_slice(..1), Ok("a"));
+    /// assert_eq!(text.try_slice(..2), Err(Error::NonCharBoundary { index: 2 }));
+    /// assert_eq!(text.try_slice(..4), Err(Error::OutOfBounds { index: 4, len: 3 }));
+    /// ```
+    fn try_slice<R>(&self, range: R) -> Result<Self, Error>
+    where
+        R: RangeBounds<usize>;
+    /// Slices by bytes, clamping indices to the text and rounding indices
+    /// inside a character to a character boundary. A range that starts after
+    /// it ends gives empty text.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{CheckedSliceable, Rounding};
+    /// let text = "aéb";
+    /// assert_eq!(text.slice_rounded(..2, Rounding::Floor), "a");
+    /// assert_eq!(text.slice_rounded(..2, Rounding::Ceil), "aé");
+    /// assert_eq!(text.slice_rounded(2..9, Rounding::Floor), "éb");
+    /// ```
+    fn slice_rounded<R>(&self, range: R, rounding: Rounding) -> Self
+    where
+        R: RangeBounds<usize>;
+}
+
+impl<T: RawText + Sliceable> CheckedSliceable for T {
+    fn try_slice<R>(&self, range: R) -> Result<Self, Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let raw = self.raw_ref();
+        let (start, end) = bounds::normalize_within(&range, raw.len());
+        for index in [start, end].iter().copied() {
+            if index > raw.len() {
+                return Err(Error::OutOfBounds {
+                    index,
+                    len: raw.len(),
+                });
+            }
+            if !raw.is_char_boundary(index) {
+                return Err(Error::NonCharBoundary { index });
+            }
+        }
+        if start > end {
+            return Err(Error::InvertedRange { start, end });
+        }
+        self.slice(start..end)
+            .ok_or(Error::NonCharBoundary { index: start })
+    }
+    fn slice_rounded<R>(&self, range: R, rounding: Rounding) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        let raw = self.raw_ref();
+        let round = |index: usize| {
+            let mut index = index.min(raw.len());
+            while !raw.is_char_boundary(index) {
+                match rounding {
+                    Rounding::Floor => index -= 1,
+                    Rounding::Ceil => index += 1,
+                }
+            }
+            index
+        };
+        let (start, end) = bounds::normalize_within(&range, raw.len());
+        let (start, end) = (round(start), round(end));
+        self.slice(start..end.max(start))
+            .expect("indices are rounded to char boundaries")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ops::Bound;
+    #[test]
+    fn slice_included_max() {
+        let foo = "foobar";
+        assert_eq!(Some("bar"), foo.slice(3..=usize::MAX));
+    }
+    #[test]
+    fn slice_excluded_max() {
+        let foo = "foobar";
+        assert_eq!(
+            None,
+            foo.slice((Bound::Excluded(usize::MAX), Bound::Unbounded))
+        );
+    }
+    #[test]
+    fn slice_spans_on_boundaries() {
+        use crate::text::{Spans, Tag};
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let text = Spans::styled(fmt_1, "🙈x");
+        assert_eq!(
+            Err(Error::NonCharBoundary { index: 1 }),
+            text.try_slice(1..)
+        );
+        let (start, end) = (5, 4);
+        assert_eq!(
+            Err(Error::InvertedRange { start, end }),
+            text.try_slice(start..end)
+        );
+        assert_eq!(
+            "<1>x</1>",
+            text.slice_rounded(1.., Rounding::Ceil).to_string()
+        );
+        assert_eq!(
+            "<1>🙈x</1>",
+            text.slice_rounded(1.., Rounding::Floor).to_string()
+        );
+        assert_eq!(
+            "",
+            text.slice_rounded(start - 2..end - 3, Rounding::Ceil)
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use super::Spans;
+use crate::text::{Sliceable, WidthPolicy};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A position in styled text that moves forward as the text is consumed,
+/// tracking both its byte index and its column so that neither has to be
+/// recomputed from the start of the text.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{Spans, Tag};
+/// let prompt = Tag::new("<p>", "</p>");
+/// let text = Spans::styled(prompt, "> ").then(Tag::default(), "ls 🙈 -la");
+/// let mut cursor = text.cursor();
+/// assert_eq!(cursor.advance_by_width(2).to_string(), "<p>> </p>");
+/// assert_eq!(cursor.advance_to_pattern(" -").unwrap().to_string(), "ls 🙈");
+/// assert_eq!((cursor.byte(), cursor.column()), (9, 7));
+/// assert_eq!(cursor.take_rest().to_string(), " -la");
+/// assert!(cursor.is_done());
+/// ```
+#[derive(Debug)]
+pub struct SpansCursor<'a, T> {
+    spans: &'a Spans<T>,
+    byte: usize,
+    column: usize,
+}
+
+impl<T> Clone for SpansCursor<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SpansCursor<'_, T> {}
+
+impl<'a, T> SpansCursor<'a, T> {
+    /// Creates a cursor at the start of some text.
+    pub fn new(spans: &'a Spans<T>) -> Self {
+        SpansCursor {
+            spans,
+            byte: 0,
+            column: 0,
+        }
+    }
+    /// Returns the byte index of the cursor.
+    pub fn byte(&self) -> usize {
+        self.byte
+    }
+    /// Returns the number of columns before the cursor.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+    /// Whether the cursor is at the end of the text.
+    pub fn is_done(&self) -> bool {
+        self.byte == self.spans.content.len()
+    }
+    /// Returns the text after the cursor without its styles.
+    pub fn rest(&self) -> &'a str {
+        &self.spans.content[self.byte..]
+    }
+    /// Moves the cursor to a byte index at or after it, returning the text
+    /// it passed over.
+    fn advance_to(&mut self, byte: usize) -> Spans<T>
+    where
+        T: Clone,
+    {
+        let passed = &self.spans.content[self.byte..byte];
+        self.column += WidthPolicy::current().width(passed);
+        let start = self.byte;
+        self.byte = byte;
+        self.spans
+            .slice(start..byte)
+            .expect("cursor positions are char boundaries")
+    }
+    /// Moves the cursor over the graphemes that fit entirely within `width`
+    /// columns, returning them. A wide grapheme that does not fit is left
+    /// for the next call.
+    pub fn advance_by_width(&mut self, width: usize) -> Spans<T>
+    where
+        T: Clone,
+    {
+        let policy = WidthPolicy::current();
+        let mut end = self.spans.content.len();
+        let mut used = 0;
+        for (byte, grapheme) in self.rest().grapheme_indices(true) {
+            used += policy.grapheme_width(grapheme);
+            if used > width {
+                end = self.byte + byte;
+                break;
+            }
+        }
+        self.advance_to(end)
+    }
+    /// Moves the cursor to the start of the next match of `pattern`,
+    /// returning the text before it, or returns [`None`] without moving if
+    /// the rest of the text does not contain `pattern`.
+    pub fn advance_to_pattern(&mut self, pattern: &str) -> Option<Spans<T>>
+    where
+        T: Clone,
+    {
+        let found = self.rest().find(pattern)?;
+        Some(self.advance_to(self.byte + found))
+    }
+    /// Moves the cursor to the end of the text, returning the rest of it.
+    pub fn take_rest(&mut self) -> Spans<T>
+    where
+        T: Clone,
+    {
+        self.advance_to(self.spans.content.len())
+    }
+}
+
+impl<T> Spans<T> {
+    /// Returns a cursor at the start of the text.
+    pub fn cursor(&self) -> SpansCursor<'_, T> {
+        SpansCursor::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{RawText, Tag};
+    #[test]
+    fn cursor_consumes_words() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let text = Spans::styled(fmt_1, "ab🙈").then(Tag::default(), "c d");
+        let mut cursor = text.cursor();
+        assert_eq!(cursor.advance_by_width(3).to_string(), "<1>ab</1>");
+        assert_eq!(cursor.advance_by_width(1).raw(), "");
+        assert_eq!((cursor.byte(), cursor.column()), (2, 2));
+        let saved = cursor;
+        assert!(cursor.advance_to_pattern("x").is_none());
+        assert_eq!(cursor.advance_to_pattern(" ").unwrap().raw(), "🙈c");
+        assert_eq!((cursor.byte(), cursor.column()), (7, 5));
+        assert_eq!(saved.rest(), "🙈c d");
+        assert_eq!(cursor.advance_by_width(9).raw(), " d");
+        assert!(cursor.is_done());
+        assert_eq!(cursor.take_rest().raw(), "");
+    }
+}
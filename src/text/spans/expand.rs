@@ -0,0 +1,178 @@
+use super::Spans;
+use crate::text::{Pushable, RawText, Sliceable, Span};
+use regex::{Captures, Regex};
+use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A piece of a replacement string.
+enum Part<'a> {
+    Text(&'a str),
+    Group(&'a str),
+}
+
+/// Splits a replacement string into text and references to capture groups,
+/// following the syntax of [`Captures::expand`].
+fn parts(replacement: &str) -> impl Iterator<Item = Part<'_>> {
+    let mut rest = replacement;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        match rest.find('$') {
+            Some(0) => {}
+            Some(dollar) => {
+                let (text, tail) = rest.split_at(dollar);
+                rest = tail;
+                return Some(Part::Text(text));
+            }
+            None => return Some(Part::Text(std::mem::take(&mut rest))),
+        }
+        let after = &rest[1..];
+        let (part, tail) = if let Some(tail) = after.strip_prefix('$') {
+            (Part::Text("$"), tail)
+        } else if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) if end > 0 => (Part::Group(&braced[..end]), &braced[end + 1..]),
+                _ => (Part::Text("$"), after),
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            if end == 0 {
+                (Part::Text("$"), after)
+            } else {
+                (Part::Group(&after[..end]), &after[end..])
+            }
+        };
+        rest = tail;
+        Some(part)
+    })
+}
+
+/// Regex replacement in [`Spans`] that styles the text of each capture group.
+pub trait ExpandStyled<T>: Sized {
+    /// Expands references to capture groups in this text, as
+    /// [`crate::text::Expandable::expand`] does, giving the text of each
+    /// group the style assigned to its name, or to its number for unnamed
+    /// groups. Groups without a style take the style of the reference.
+    ///
+    /// # Example
+    /// ```
+    /// use regex::Regex;
+    /// use std::collections::HashMap;
+    /// use stylish_stringlike::text::{ExpandStyled, Spans, Tag};
+    /// let re = Regex::new(r"(?P<area>\d{3})[-. ](\d{3})[-. ](\d{4})").unwrap();
+    /// let captures = re.captures("555.123.4567").unwrap();
+    /// let styles: HashMap<&str, Tag> = [
+    ///     ("area", Tag::new("<b>", "</b>")),
+    ///     ("3", Tag::new("<i>", "</i>")),
+    /// ]
+    /// .iter()
+    /// .cloned()
+    /// .collect();
+    /// let template: Spans<Tag> = Spans::from("(${area}) $2-$3 $$");
+    /// let expanded = template.expand_styled(&captures, &styles);
+    /// assert_eq!(expanded.to_string(), "(<b>555</b>) 123-<i>4567</i> $");
+    /// ```
+    fn expand_styled<S>(&self, capture: &Captures, styles: &HashMap<S, T>) -> Self
+    where
+        S: Borrow<str> + Hash + Eq;
+    /// Replaces every match of a regex with this text's styled expansion of
+    /// a replacement, as [`ExpandStyled::expand_styled`] expands it.
+    ///
+    /// # Example
+    /// ```
+    /// use regex::Regex;
+    /// use std::collections::HashMap;
+    /// use stylish_stringlike::text::{ExpandStyled, Spans, Tag};
+    /// let re = Regex::new(r"(?P<key>\w+)=(?P<value>\w+)").unwrap();
+    /// let mut styles = HashMap::new();
+    /// styles.insert("key", Tag::new("<k>", "</k>"));
+    /// styles.insert("value", Tag::new("<v>", "</v>"));
+    /// let text: Spans<Tag> = Spans::from("a=1, b=2");
+    /// let highlighted = text.replace_regex_styled(&re, &Spans::from("$key: $value"), &styles);
+    /// assert_eq!(highlighted.to_string(), "<k>a</k>: <v>1</v>, <k>b</k>: <v>2</v>");
+    /// ```
+    fn replace_regex_styled<S>(
+        &self,
+        searcher: &Regex,
+        replacement: &Self,
+        styles: &HashMap<S, T>,
+    ) -> Self
+    where
+        S: Borrow<str> + Hash + Eq;
+}
+
+impl<T: Clone + Default + PartialEq> ExpandStyled<T> for Spans<T> {
+    fn expand_styled<S>(&self, capture: &Captures, styles: &HashMap<S, T>) -> Spans<T>
+    where
+        S: Borrow<str> + Hash + Eq,
+    {
+        let mut result: Spans<T> = Default::default();
+        for span in self.spans() {
+            for part in parts(span.raw_ref()) {
+                let (style, text) = match part {
+                    Part::Text(text) => (span.style().clone(), text),
+                    Part::Group(name) => {
+                        let group = match name.parse::<usize>() {
+                            Ok(index) => capture.get(index),
+                            Err(_) => capture.name(name),
+                        };
+                        let style = styles
+                            .get(name)
+                            .map_or_else(|| span.style().clone(), Cow::Borrowed);
+                        (style, group.map_or("", |group| group.as_str()))
+                    }
+                };
+                result.push(&Span::new(style, Cow::Borrowed(text)));
+            }
+        }
+        result
+    }
+    fn replace_regex_styled<S>(
+        &self,
+        searcher: &Regex,
+        replacement: &Spans<T>,
+        styles: &HashMap<S, T>,
+    ) -> Spans<T>
+    where
+        S: Borrow<str> + Hash + Eq,
+    {
+        let mut result: Spans<T> = Default::default();
+        let mut last_end = 0;
+        for capture in searcher.captures_iter(self.raw_ref()) {
+            let mat = capture
+                .get(0)
+                .expect("Captures are always supposed to have one match");
+            result.push(&self.slice(last_end..mat.start()));
+            result.push(&replacement.expand_styled(&capture, styles));
+            last_end = mat.end();
+        }
+        result.push(&self.slice(last_end..));
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Expandable, Tag};
+    #[test]
+    fn expand_styled_matches_expand() {
+        let re = Regex::new(r"(?P<first>\w+) (\w+)").unwrap();
+        let captures = re.captures("hello world").unwrap();
+        let no_styles: HashMap<String, Tag> = HashMap::new();
+        let bold = Tag::new("<b>", "</b>");
+        for replacement in ["$2 $first", "${2}x $1x $$ $ ${", "$9$missing"].iter() {
+            let template = Spans::styled(bold.clone(), replacement);
+            assert_eq!(
+                template.expand_styled(&captures, &no_styles),
+                template.expand(&captures),
+                "{}",
+                replacement
+            );
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use super::Spans;
+use crate::text::Sliceable;
+
+/// Text matched by a fuzzy pattern, as found by [`Spans::find_fuzzy`].
+///
+/// The characters of the pattern appear in the text in order, but not
+/// necessarily together. Matches score higher the more of their characters
+/// are consecutive or start words, and lower the more characters they skip,
+/// so that candidates can be ranked by [`FuzzyMatch::score`].
+#[derive(Clone, Debug)]
+pub struct FuzzyMatch<'a, T> {
+    spans: &'a Spans<T>,
+    score: i64,
+    positions: Vec<usize>,
+}
+
+impl<'a, T: Clone + Default + PartialEq> FuzzyMatch<'a, T> {
+    /// Returns how well the pattern matched. Higher is better.
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+    /// Returns the byte indices of the matched characters.
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+    /// Returns the text from the first matched character to the last, with
+    /// its styles.
+    pub fn slice(&self) -> Spans<T> {
+        match (self.positions.first(), self.positions.last()) {
+            (Some(&first), Some(&last)) => self
+                .spans
+                .slice(first..self.char_end(last))
+                .unwrap_or_default(),
+            _ => Spans::default(),
+        }
+    }
+    /// Returns the whole text with the graphemes containing matched
+    /// characters restyled, keeping the styles of the rest.
+    pub fn highlight<F>(&self, style: F) -> Spans<T>
+    where
+        F: FnMut(&T) -> T,
+    {
+        let mut positions = self.positions.iter().peekable();
+        self.spans.overlay_graphemes(
+            |start, grapheme| {
+                let mut hit = false;
+                while let Some(&&position) = positions.peek() {
+                    if position >= start + grapheme.len() {
+                        break;
+                    }
+                    hit = true;
+                    positions.next();
+                }
+                hit
+            },
+            style,
+        )
+    }
+    /// Returns the end of the character starting at a byte index.
+    fn char_end(&self, position: usize) -> usize {
+        position
+            + self.spans.content[position..]
+                .chars()
+                .next()
+                .map_or(0, char::len_utf8)
+    }
+}
+
+/// Returns whether a character starts a word: it follows a character that
+/// is not alphanumeric, or it is an uppercase letter following a lowercase
+/// one.
+fn starts_word(previous: Option<char>, current: char) -> bool {
+    match previous {
+        None => true,
+        Some(previous) => {
+            !previous.is_alphanumeric() || (previous.is_lowercase() && current.is_uppercase())
+        }
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Spans<T> {
+    /// Highlights the characters of a search query as the user types it,
+    /// restyling the graphemes of the best fuzzy match found by
+    /// [`Spans::find_fuzzy`] and keeping the styles of everything else. The
+    /// highlight is computed from each grapheme's own style, so it can add
+    /// to the style rather than replace it. Text the query does not match
+    /// is returned unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Color, Format, Spans};
+    /// let name = Format::new().fg(Color::Blue);
+    /// let text = Spans::styled(name, "Cargo").then(Format::new(), ".toml");
+    /// let actual = text.highlight_query("ct", |style| style.underline());
+    /// assert_eq!(actual.style_at(0), Some(&name.underline()));
+    /// assert_eq!(actual.style_at(1), Some(&name));
+    /// assert_eq!(actual.style_at(6), Some(&Format::new().underline()));
+    /// assert_eq!(text.highlight_query("xyz", |style| style.underline()), text);
+    /// ```
+    pub fn highlight_query<F>(&self, query: &str, style: F) -> Spans<T>
+    where
+        F: FnMut(&T) -> T,
+    {
+        match self.find_fuzzy(query) {
+            Some(found) => found.highlight(style),
+            None => self.clone(),
+        }
+    }
+    /// Finds the best match of a fuzzy pattern, whose characters must appear
+    /// in the text in order. Matching ignores case unless the pattern
+    /// contains an uppercase letter. Returns [`None`] if the pattern does not
+    /// match.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let dir = Tag::new("<d>", "</d>");
+    /// let hit = Tag::new("<m>", "</m>");
+    /// let candidates: Vec<Spans<Tag>> = vec![
+    ///     Spans::styled(dir.clone(), "src/").then(Tag::default(), "text/mod.rs"),
+    ///     Spans::styled(dir, "src/").then(Tag::default(), "main.rs"),
+    /// ];
+    /// let mut matches: Vec<_> = candidates
+    ///     .iter()
+    ///     .filter_map(|candidate| candidate.find_fuzzy("mrs"))
+    ///     .collect();
+    /// matches.sort_by_key(|found| -found.score());
+    /// assert_eq!(
+    ///     matches[0].highlight(|_| hit.clone()).to_string(),
+    ///     "<d>src/</d>text/<m>m</m>od.<m>rs</m>"
+    /// );
+    /// assert_eq!(matches[1].slice().to_string(), "main.rs");
+    /// ```
+    pub fn find_fuzzy(&self, pattern: &str) -> Option<FuzzyMatch<'_, T>> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let case_sensitive = pattern.iter().any(|c| c.is_uppercase());
+        let matches = |a: char, b: char| {
+            if case_sensitive {
+                a == b
+            } else {
+                a.to_lowercase().eq(b.to_lowercase())
+            }
+        };
+        let chars: Vec<(usize, char)> = self.content.char_indices().collect();
+        let first = match pattern.first() {
+            Some(&first) => first,
+            None => {
+                return Some(FuzzyMatch {
+                    spans: self,
+                    score: 0,
+                    positions: vec![],
+                })
+            }
+        };
+        let mut best: Option<(i64, Vec<usize>)> = None;
+        for start in (0..chars.len()).filter(|&i| matches(chars[i].1, first)) {
+            // Match the rest greedily, then score the characters matched.
+            let mut found = vec![start];
+            let mut next = start + 1;
+            for &wanted in &pattern[1..] {
+                match (next..chars.len()).find(|&i| matches(chars[i].1, wanted)) {
+                    Some(i) => {
+                        found.push(i);
+                        next = i + 1;
+                    }
+                    None => break,
+                }
+            }
+            if found.len() < pattern.len() {
+                // Later starts cannot match more.
+                break;
+            }
+            let mut score = 0;
+            for (n, &i) in found.iter().enumerate() {
+                score += 1;
+                if n > 0 && found[n - 1] + 1 == i {
+                    score += 2;
+                }
+                if starts_word(i.checked_sub(1).map(|i| chars[i].1), chars[i].1) {
+                    score += 2;
+                }
+            }
+            let skipped = found[found.len() - 1] - start + 1 - found.len();
+            score -= skipped as i64;
+            if !matches!(&best, Some((best, _)) if score <= *best) {
+                best = Some((score, found));
+            }
+        }
+        best.map(|(score, found)| FuzzyMatch {
+            spans: self,
+            score,
+            positions: found.into_iter().map(|i| chars[i].0).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Pushable, Tag};
+    #[test]
+    fn fuzzy_prefers_word_starts() {
+        let bold = Tag::new("<b>", "</b>");
+        let text = Spans::styled(bold, "fööBar").then(Tag::default(), " foo_bar");
+        let found = text.find_fuzzy("fb").unwrap();
+        assert_eq!(found.positions(), [0, 5]);
+        assert_eq!(found.slice().to_string(), "<b>fööB</b>");
+        let found = text.find_fuzzy("f_b").unwrap();
+        assert_eq!(found.slice().to_string(), "foo_b");
+        assert!(text.find_fuzzy("FB").is_none());
+        assert!(text.find_fuzzy("rbf").is_none());
+        assert_eq!(text.find_fuzzy("").unwrap().highlight(|s| s.clone()), text);
+    }
+    #[test]
+    fn highlight_whole_graphemes() {
+        let bold = Tag::new("<b>", "</b>");
+        let hit = Tag::new("<m>", "</m>");
+        // The query matches the "e" but not the combining accent after it.
+        let text = Spans::styled(bold, "cafe\u{301}s").then(Tag::default(), " menu");
+        let actual = text.highlight_query("es", |_| hit.clone());
+        assert_eq!(actual.to_string(), "<b>caf</b><m>e\u{301}s</m> menu");
+    }
+    #[test]
+    fn highlight_implicitly_styled_text() {
+        let hit = Tag::new("<m>", "</m>");
+        let mut text: Spans<Tag> = Default::default();
+        text.push(&"cargo");
+        let actual = text.highlight_query("co", |_| hit.clone());
+        assert_eq!(actual.to_string(), "<m>c</m>arg<m>o</m>");
+    }
+}
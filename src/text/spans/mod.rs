@@ -1,25 +1,64 @@
+mod cursor;
+#[cfg(feature = "regex")]
+mod expand;
+mod fuzzy;
 mod search_tree;
+mod shared;
 mod span;
+mod styled_grapheme;
 use super::{
-    BoundedWidth, Expandable, HasWidth, Joinable, Paintable, Pushable, RawText, Replaceable,
-    Sliceable, Width,
+    bounds, Annotatable, BoundedWidth, HasWidth, JoinWith, Joinable, Paintable, PaintedWidth,
+    Pushable, RawText, Replaceable, Rounding, Sliceable, StyleKey, Width, WidthPolicy,
+    WidthSliceable,
 };
+#[cfg(feature = "regex")]
+use super::{Expandable, RegexReplaceable};
 
+pub use cursor::SpansCursor;
+#[cfg(feature = "regex")]
+pub use expand::ExpandStyled;
+pub use fuzzy::FuzzyMatch;
+#[cfg(feature = "regex")]
 use regex::{Captures, Regex, Replacer};
 use search_tree::SearchTree;
+pub use shared::SharedSpans;
 pub use span::Span;
 use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
 use std::fmt;
-use std::iter::{once, repeat, FromIterator};
-use std::ops::{Deref, RangeBounds};
+use std::hash::{Hash, Hasher};
+use std::iter::{once, FromIterator, Sum};
+use std::ops::{Add, AddAssign, Deref, RangeBounds};
+use std::sync::OnceLock;
+pub use styled_grapheme::StyledGrapheme;
+use unicode_segmentation::UnicodeSegmentation;
 /// A string with various styles applied to the span.
 /// Styles do not not cascade. Only the most recent style
 /// applies to the current character.
-#[derive(Clone, Debug)]
+///
+/// # Performance
+///
+/// Styles are kept in a tree keyed by the byte index where each one starts,
+/// and are shared between copies rather than cloned. For text of `n` bytes
+/// with `s` style changes:
+///
+/// - pushing text or spans takes time proportional to what is pushed, plus
+///   `O(log s)`, so building text by pushing is linear overall;
+/// - [`Spans::spans`] walks the styles once, in `O(s)`;
+/// - [`Sliceable::slice`] copies the slice, in `O(log s)` plus the length of
+///   the slice and the number of styles in it;
+/// - [`WidthSliceable::slice_width`] also measures graphemes up to the end of
+///   the slice;
+/// - replacing text builds new text in `O(n + s)` plus the cost of matching.
+///
+/// Use [`SharedSpans`] to clone and slice large text without copying it.
+#[derive(Debug)]
 pub struct Spans<T> {
     content: String,
     /// Byte-indexed map of spans
     spans: SearchTree<T>,
+    /// Lazily computed width of the content
+    width: OnceLock<usize>,
 }
 
 impl<T> Default for Spans<T> {
@@ -27,10 +66,27 @@ impl<T> Default for Spans<T> {
         Self {
             content: String::new(),
             spans: Default::default(),
+            width: Default::default(),
         }
     }
 }
 
+impl<T: Clone> Clone for Spans<T> {
+    fn clone(&self) -> Self {
+        Self {
+            content: self.content.clone(),
+            spans: self.spans.clone(),
+            width: self.width.clone(),
+        }
+    }
+    /// Copies `source` into `self`, reusing the allocation of the text.
+    fn clone_from(&mut self, source: &Self) {
+        self.content.clone_from(&source.content);
+        self.spans.clone_from(&source.spans);
+        self.width.clone_from(&source.width);
+    }
+}
+
 impl<T: PartialEq> Eq for Spans<T> {}
 
 impl<T: PartialEq> PartialEq for Spans<T> {
@@ -39,67 +95,1229 @@ impl<T: PartialEq> PartialEq for Spans<T> {
     }
 }
 
+impl<T: Hash> Hash for Spans<T> {
+    /// Hashes the content and styles. The cached width is not hashed, so
+    /// spans are safe to use as keys although it is computed lazily.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.content.hash(state);
+        for style in self.spans.iter() {
+            style.hash(state);
+        }
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Spans<T> {
+    /// Orders by raw content, then by styles in order of appearance.
+    fn partial_cmp(&self, other: &Spans<T>) -> Option<Ordering> {
+        match self.content.cmp(&other.content) {
+            Ordering::Equal => self.spans.iter().partial_cmp(other.spans.iter()),
+            ordering => Some(ordering),
+        }
+    }
+}
+
+impl<T: Ord> Ord for Spans<T> {
+    /// Orders by raw content, then by styles in order of appearance.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{RawText, Span, Spans};
+    /// let mut names: Vec<Spans<String>> = vec![
+    ///     Spans::from("bob"),
+    ///     Spans::default() + &Span::borrowed(&String::from("b"), "alice"),
+    ///     Spans::from("alice"),
+    /// ];
+    /// names.sort();
+    /// let raw: Vec<_> = names.iter().map(|n| n.raw()).collect();
+    /// assert_eq!(raw, ["alice", "alice", "bob"]);
+    /// assert_eq!(names[0], Spans::from("alice"));
+    /// ```
+    fn cmp(&self, other: &Spans<T>) -> Ordering {
+        self.content
+            .cmp(&other.content)
+            .then_with(|| self.spans.iter().cmp(other.spans.iter()))
+    }
+}
+
 impl<T> Spans<T> {
-    #[allow(clippy::type_complexity)]
-    fn segments(
-        &self,
-    ) -> Box<dyn Iterator<Item = ((&usize, Cow<'_, T>), Option<(&usize, Cow<'_, T>)>)> + '_>
+    /// Returns the style and text of each run of this object.
+    ///
+    /// Text before the first style has the default style. This walks the
+    /// style tree once, looking ahead one key for the end of each run.
+    fn runs(&self) -> impl Iterator<Item = (Cow<'_, T>, &str)>
     where
         T: Clone + Default,
     {
-        if self.spans.contains_key(0) {
-            Box::new(
+        let leading = if self.spans.contains_key(0) {
+            None
+        } else {
+            Some((0, Cow::Owned(Default::default())))
+        };
+        let mut keys = leading
+            .into_iter()
+            .chain(
                 self.spans
                     .iter()
-                    .map(|(key, val)| (key, Cow::Borrowed(val)))
-                    .zip(
-                        self.spans
-                            .iter()
-                            .map(|(key, val)| (key, Cow::Borrowed(val)))
-                            .map(Some)
-                            .skip(1)
-                            .chain(repeat(None)),
-                    ),
+                    .map(|(key, val)| (*key, Cow::Borrowed(val))),
             )
-        } else {
-            Box::new(
-                once((&0, Cow::Owned(Default::default())))
-                    .chain(
-                        self.spans
-                            .iter()
-                            .map(|(key, val)| (key, Cow::Borrowed(val))),
-                    )
-                    .zip(
-                        self.spans
-                            .iter()
-                            .map(|(key, val)| (key, Cow::Borrowed(val)))
-                            .map(Some)
-                            .chain(repeat(None)),
-                    ),
-            )
-        }
+            .peekable();
+        std::iter::from_fn(move || loop {
+            let (start, style) = keys.next()?;
+            let end = keys.peek().map_or(self.content.len(), |(end, _)| *end);
+            // A missing range represents an invalid state in the spans.
+            // One of the spans is actually out of the range of the length of the string.
+            if let Some(run) = self.content.get(start..end) {
+                return Some((style, run));
+            }
+        })
     }
     /// Returns the spans of text contained in this object.
     pub fn spans(&self) -> impl Iterator<Item = Span<'_, T>>
     where
         T: Clone + Default,
     {
-        self.segments()
-            .filter_map(move |((first_key, style), second)| {
-                let second_key = if let Some((second_key, _)) = second {
-                    *second_key
-                } else {
-                    self.content.len()
+        self.runs()
+            .map(|(style, s)| Span::new(style, Cow::Borrowed(s)))
+    }
+    /// Splits the text wherever its style changes, giving one object per
+    /// run of a single style. This is the inverse of pushing the runs back
+    /// together.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::spans;
+    /// use stylish_stringlike::text::{RawText, Tag};
+    /// let dir = Tag::new("<d>", "</d>");
+    /// let sep = Tag::new("<s>", "</s>");
+    /// let path = spans![dir => "usr", sep => "/", dir => "lib"];
+    /// let parts: Vec<String> = path.split_by_style().map(|part| part.raw()).collect();
+    /// assert_eq!(parts, ["usr", "/", "lib"]);
+    /// ```
+    pub fn split_by_style(&self) -> impl Iterator<Item = Spans<T>> + '_
+    where
+        T: Clone + Default,
+    {
+        self.runs()
+            .scan(0, |start, (_, s)| {
+                let range = *start..*start + s.len();
+                *start = range.end;
+                Some(range)
+            })
+            .filter(|range| !range.is_empty())
+            .filter_map(move |range| self.slice(range))
+    }
+    /// Splits the text into lines, like [`str::lines`]. Lines end with `\n`
+    /// or `\r\n`, which are not included, and a final line ending does not
+    /// start another line.
+    ///
+    /// Widths and truncation treat text as a single line, so multi-line text
+    /// should be split into lines before it is laid out, for example with
+    /// [`crate::widget::VBox::push_lines`].
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::spans;
+    /// use stylish_stringlike::text::Tag;
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let text = spans![bold => "foo\nbar", Tag::default() => "\r\nbaz\n"];
+    /// let lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+    /// assert_eq!(lines, ["<b>foo</b>", "<b>bar</b>", "baz"]);
+    /// assert_eq!(text.height(), 3);
+    /// ```
+    pub fn lines(&self) -> impl Iterator<Item = Spans<T>> + '_
+    where
+        T: Clone,
+    {
+        self.content
+            .split_inclusive('\n')
+            .scan(0, |start, line| {
+                let range = *start..*start + line.len();
+                *start = range.end;
+                Some((range.start, line))
+            })
+            .filter_map(move |(start, line)| {
+                let line = line.strip_suffix('\n').unwrap_or(line);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                self.slice(start..start + line.len())
+            })
+    }
+    /// Splits the text into consecutive pieces at most `width` columns wide,
+    /// for paginating text or wrapping it at any grapheme. Graphemes are
+    /// never split, so a grapheme wider than `width` is a piece on its own.
+    /// Line breaks are treated as any other text.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::spans;
+    /// use stylish_stringlike::text::Tag;
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let text = spans![bold => "abc🙈", Tag::default() => "de"];
+    /// let chunks: Vec<String> = text.chunks_width(2).map(|chunk| chunk.to_string()).collect();
+    /// assert_eq!(chunks, ["<b>ab</b>", "<b>c</b>", "<b>🙈</b>", "de"]);
+    /// ```
+    pub fn chunks_width(&self, width: usize) -> impl Iterator<Item = Spans<T>> + '_
+    where
+        T: Clone,
+    {
+        assert!(width > 0, "chunk width must be non-zero");
+        let policy = WidthPolicy::current();
+        let mut graphemes = self.content.grapheme_indices(true).peekable();
+        std::iter::from_fn(move || {
+            let (start, first) = graphemes.next()?;
+            let mut end = start + first.len();
+            let mut used = policy.grapheme_width(first);
+            while let Some(&(index, grapheme)) = graphemes.peek() {
+                let grapheme_width = policy.grapheme_width(grapheme);
+                if used + grapheme_width > width {
+                    break;
+                }
+                used += grapheme_width;
+                end = index + grapheme.len();
+                graphemes.next();
+            }
+            self.slice(start..end)
+        })
+    }
+    /// Returns the number of lines in the text, as given by [`Spans::lines`].
+    pub fn height(&self) -> usize {
+        self.content.lines().count()
+    }
+    /// Slices the text by extended grapheme clusters rather than bytes, so
+    /// that flags, emoji joined with zero-width joiners and letters with
+    /// combining marks are never split. Returns [`None`] if the range goes
+    /// past the last grapheme.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::spans;
+    /// use stylish_stringlike::text::Tag;
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let text = spans![bold => "e\u{301}👨‍👩‍👧", Tag::default() => "🇫🇷!"];
+    /// assert_eq!(text.slice_graphemes(1..3).unwrap().to_string(), "<b>👨‍👩‍👧</b>🇫🇷");
+    /// assert_eq!(text.slice_graphemes(..1).unwrap().to_string(), "<b>e\u{301}</b>");
+    /// assert!(text.slice_graphemes(5..).is_none());
+    /// ```
+    pub fn slice_graphemes<R>(&self, range: R) -> Option<Spans<T>>
+    where
+        R: RangeBounds<usize>,
+        T: Clone,
+    {
+        let (start, end) = bounds::normalize(&range);
+        let mut boundaries = self
+            .content
+            .grapheme_indices(true)
+            .map(|(byte, _)| byte)
+            .chain(once(self.content.len()));
+        let start_byte = boundaries.nth(start)?;
+        let end_byte = match end {
+            Some(end) if end < start => return None,
+            Some(end) if end == start => start_byte,
+            Some(end) => boundaries.nth(end - start - 1)?,
+            None => self.content.len(),
+        };
+        self.slice(start_byte..end_byte)
+    }
+    /// Returns the text of each run of this object with the key of its style,
+    /// for renderers that cache painted fragments across frames.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, StyleKey, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut spans: Spans<Tag> = Default::default();
+    /// spans.push(&Span::borrowed(&bold, "foo"));
+    /// let runs: Vec<_> = spans.keyed_runs().collect();
+    /// assert_eq!(runs, vec![(bold.style_key(), "foo")]);
+    /// ```
+    pub fn keyed_runs(&self) -> impl Iterator<Item = (u64, &str)>
+    where
+        T: StyleKey + Clone + Default,
+    {
+        self.runs().map(|(style, s)| (style.style_key(), s))
+    }
+    /// Creates a text object from formatting arguments, applying a single style.
+    /// The arguments are formatted directly into the text object's buffer.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let spans = Spans::from_fmt(bold, format_args!("{} + {}", 1, 2));
+    /// assert_eq!(format!("{}", spans), "<b>1 + 2</b>");
+    /// ```
+    pub fn from_fmt(style: T, args: fmt::Arguments<'_>) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut result: Spans<T> = Default::default();
+        result.push_fmt(style, args);
+        result
+    }
+    /// Appends formatting arguments with a single style.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let italic = Tag::new("<i>", "</i>");
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut spans = Spans::from_fmt(italic, format_args!("x = "));
+    /// spans.push_fmt(bold, format_args!("{:.2}", 0.5));
+    /// assert_eq!(format!("{}", spans), "<i>x = </i><b>0.50</b>");
+    /// ```
+    pub fn push_fmt(&mut self, style: T, args: fmt::Arguments<'_>)
+    where
+        T: PartialEq,
+    {
+        use std::fmt::Write;
+        self.spans.insert(self.content.len(), style);
+        self.content_mut()
+            .write_fmt(args)
+            .expect("a formatting trait implementation returned an error");
+        self.spans.dedup();
+        self.trim();
+    }
+    /// Creates an object holding text with a single style.
+    ///
+    /// Together with [`Spans::then`], this builds text with several styles
+    /// without creating [`Span`] values.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let italic = Tag::new("<i>", "</i>");
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let spans = Spans::styled(italic, "foo").then(bold, "bar");
+    /// assert_eq!(format!("{}", spans), "<i>foo</i><b>bar</b>");
+    /// ```
+    pub fn styled(style: T, text: &str) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut result: Spans<T> = Default::default();
+        result.push_styled(style, text);
+        result
+    }
+    /// Appends text with a single style, returning the result so calls can be
+    /// chained.
+    pub fn then(mut self, style: T, text: &str) -> Self
+    where
+        T: PartialEq,
+    {
+        self.push_styled(style, text);
+        self
+    }
+    /// Concatenates text objects with a separator between them.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let sep = Tag::new("<s>", "</s>");
+    /// let crumbs: Vec<Spans<Tag>> = vec![Spans::from("home"), Spans::from("docs")];
+    /// let breadcrumbs = Spans::join_all(&crumbs, &Spans::styled(sep, " > "));
+    /// assert_eq!(breadcrumbs.to_string(), "home<s> > </s>docs");
+    /// ```
+    pub fn join_all<I, U>(items: I, separator: &Spans<T>) -> Self
+    where
+        I: IntoIterator<Item = U>,
+        U: Borrow<Spans<T>>,
+        T: Clone + PartialEq,
+    {
+        items.into_iter().join_with(separator)
+    }
+    /// Appends text with a single style.
+    pub fn push_styled(&mut self, style: T, text: &str)
+    where
+        T: PartialEq,
+    {
+        self.spans.insert(self.content.len(), style);
+        self.content_mut().push_str(text);
+        self.spans.dedup();
+        self.trim();
+    }
+    /// Applies a style transformation to the text within a range of display
+    /// columns, splitting spans at the edges of the range as needed.
+    ///
+    /// Graphemes only partially covered by the range are not selected.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// let italic = Tag::new("<i>", "</i>");
+    /// let selected = Tag::new("<s>", "</s>");
+    /// let mut spans: Spans<Tag> = Default::default();
+    /// spans.push(&Span::borrowed(&italic, "foobar"));
+    /// let actual = spans.select(2..4, |_style| selected.clone());
+    /// assert_eq!(format!("{}", actual), "<i>fo</i><s>ob</s><i>ar</i>");
+    /// ```
+    pub fn select<R, F>(&self, range: R, transform: F) -> Spans<T>
+    where
+        R: RangeBounds<usize>,
+        F: Fn(&T) -> T,
+        T: Clone + Default + PartialEq,
+    {
+        let (start, end) = self.width_range_to_bytes(&range);
+        let mut result: Spans<T> = Default::default();
+        let mut offset = 0;
+        for span in self.spans() {
+            let text = span.raw_ref();
+            let next_offset = offset + text.len();
+            let first = start.clamp(offset, next_offset) - offset;
+            let last = end.clamp(offset, next_offset) - offset;
+            let selected = transform(span.style());
+            for (style, part) in [
+                (span.style().as_ref(), &text[..first]),
+                (&selected, &text[first..last]),
+                (span.style().as_ref(), &text[last..]),
+            ] {
+                if !part.is_empty() {
+                    result.push(&Span::borrowed(style, part));
+                }
+            }
+            offset = next_offset;
+        }
+        result
+    }
+    /// Annotates the text in a range of columns, for example to attach a
+    /// tooltip to a region of HTML output.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// let span = Tag::new("<span>", "</span>");
+    /// let mut spans: Spans<Tag> = Default::default();
+    /// spans.push(&Span::borrowed(&span, "foobar"));
+    /// let actual = spans.annotate_range(3.., "title", "bar");
+    /// assert_eq!(
+    ///     format!("{}", actual),
+    ///     "<span>foo</span><span title=\"bar\">bar</span>"
+    /// );
+    /// ```
+    pub fn annotate_range<R>(&self, range: R, key: &str, value: &str) -> Spans<T>
+    where
+        R: RangeBounds<usize>,
+        T: Annotatable + Clone + Default + PartialEq,
+    {
+        self.select(range, |style| style.annotate(key, value))
+    }
+    /// Restyles the graphemes for which `selected` returns true, given the
+    /// byte index and text of each grapheme, keeping the styles of the rest.
+    /// The new style is computed from the old one, so it can be layered over
+    /// the existing styles, as when highlighting search matches.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let hit = Tag::new("<m>", "</m>");
+    /// let text = Spans::styled(bold, "ab").then(Tag::default(), "cd");
+    /// let actual = text.overlay_graphemes(|_, g| g == "b" || g == "c", |_| hit.clone());
+    /// assert_eq!(actual.to_string(), "<b>a</b><m>bc</m>d");
+    /// ```
+    pub fn overlay_graphemes<P, F>(&self, mut selected: P, mut transform: F) -> Spans<T>
+    where
+        P: FnMut(usize, &str) -> bool,
+        F: FnMut(&T) -> T,
+        T: Clone + Default + PartialEq,
+    {
+        let mut ranges: Vec<(usize, usize)> = vec![];
+        for (start, grapheme) in self.content.grapheme_indices(true) {
+            if selected(start, grapheme) {
+                let end = start + grapheme.len();
+                match ranges.last_mut() {
+                    Some(last) if last.1 == start => last.1 = end,
+                    _ => ranges.push((start, end)),
+                }
+            }
+        }
+        let mut ranges = ranges.into_iter().peekable();
+        let mut spans = SearchTree::new();
+        let mut offset = 0;
+        for (style, run) in self.runs() {
+            let end = offset + run.len();
+            let mut position = offset;
+            while position < end {
+                while matches!(ranges.peek(), Some(&(_, stop)) if stop <= position) {
+                    ranges.next();
+                }
+                let (selected, next) = match ranges.peek() {
+                    Some(&(start, stop)) if start <= position => (true, stop.min(end)),
+                    Some(&(start, _)) => (false, start.min(end)),
+                    None => (false, end),
                 };
-                #[allow(clippy::manual_map)]
-                if let Some(ref s) = self.content.get(*first_key..second_key) {
-                    Some(Span::new(style, Cow::Borrowed(s)))
+                let new = if selected {
+                    transform(&style)
                 } else {
-                    // This represents an invalid state in the spans.
-                    // One of the spans is actually out of the range of the length of the string.
-                    None
+                    style.clone().into_owned()
+                };
+                spans.insert(position, new);
+                position = next;
+            }
+            offset = end;
+        }
+        spans.dedup();
+        Spans {
+            content: self.content.clone(),
+            spans,
+            width: self.width.clone(),
+        }
+    }
+    /// Finds the byte range of the graphemes entirely within a range of columns.
+    fn width_range_to_bytes<R>(&self, range: &R) -> (usize, usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (first_column, last_column) = bounds::normalize(range);
+        let mut start = None;
+        let mut end = self.content.len();
+        let mut column = 0;
+        let policy = WidthPolicy::current();
+        for (byte, grapheme) in self.content.grapheme_indices(true) {
+            let width = policy.grapheme_width(grapheme);
+            let in_range = bounds::contains(first_column, last_column, column, width);
+            match (in_range, start) {
+                (true, None) => start = Some(byte),
+                (false, Some(_)) => {
+                    end = byte;
+                    break;
                 }
+                _ => {}
+            }
+            column += width;
+        }
+        match start {
+            Some(start) => (start, end),
+            None => (0, 0),
+        }
+    }
+    /// Converts a byte index into the number of characters before it. Indices
+    /// past the end are clamped to the end, and indices inside a character
+    /// are rounded to a character boundary.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Rounding, Spans, Tag};
+    /// let text: Spans<Tag> = Spans::from("aéb");
+    /// assert_eq!(text.byte_to_char(3, Rounding::Floor), 2);
+    /// assert_eq!(text.byte_to_char(2, Rounding::Floor), 1);
+    /// assert_eq!(text.byte_to_char(2, Rounding::Ceil), 2);
+    /// ```
+    pub fn byte_to_char(&self, index: usize, rounding: Rounding) -> usize {
+        let mut index = index.min(self.content.len());
+        while !self.content.is_char_boundary(index) {
+            match rounding {
+                Rounding::Floor => index -= 1,
+                Rounding::Ceil => index += 1,
+            }
+        }
+        self.content[..index].chars().count()
+    }
+    /// Converts a number of characters into the byte index after them.
+    /// Counts past the end are clamped to the end.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let text: Spans<Tag> = Spans::from("aéb");
+    /// assert_eq!(text.char_to_byte(2), 3);
+    /// assert_eq!(text.char_to_byte(9), 4);
+    /// ```
+    pub fn char_to_byte(&self, index: usize) -> usize {
+        self.content
+            .char_indices()
+            .nth(index)
+            .map_or(self.content.len(), |(byte, _)| byte)
+    }
+    /// Converts a byte index into the number of columns before it, measured
+    /// with the current [`WidthPolicy`]. Indices past the end are clamped to
+    /// the end, and indices inside a grapheme count the grapheme if rounding
+    /// up.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Rounding, Spans, Tag};
+    /// let text: Spans<Tag> = Spans::from("a🙈b");
+    /// assert_eq!(text.byte_to_width(5, Rounding::Floor), 3);
+    /// assert_eq!(text.byte_to_width(2, Rounding::Floor), 1);
+    /// assert_eq!(text.byte_to_width(2, Rounding::Ceil), 3);
+    /// ```
+    pub fn byte_to_width(&self, index: usize, rounding: Rounding) -> usize {
+        let policy = WidthPolicy::current();
+        let mut column = 0;
+        for (start, grapheme) in self.content.grapheme_indices(true) {
+            if start >= index {
+                break;
+            }
+            let width = policy.grapheme_width(grapheme);
+            if start + grapheme.len() > index && rounding == Rounding::Floor {
+                break;
+            }
+            column += width;
+        }
+        column
+    }
+    /// Converts a column into the byte index of the grapheme that starts
+    /// there, measured with the current [`WidthPolicy`]. Columns past the end
+    /// are clamped to the end, and columns inside a wide grapheme are rounded
+    /// to the start or end of the grapheme. The result can be passed to
+    /// [`Sliceable::slice`].
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Rounding, Sliceable, Spans, Tag};
+    /// let text: Spans<Tag> = Spans::from("a🙈b");
+    /// assert_eq!(text.width_to_byte(3, Rounding::Floor), 5);
+    /// assert_eq!(text.width_to_byte(2, Rounding::Floor), 1);
+    /// assert_eq!(text.width_to_byte(2, Rounding::Ceil), 5);
+    /// let end = text.width_to_byte(2, Rounding::Floor);
+    /// assert_eq!(text.slice(..end).unwrap().to_string(), "a");
+    /// ```
+    pub fn width_to_byte(&self, column: usize, rounding: Rounding) -> usize {
+        let policy = WidthPolicy::current();
+        let mut start_column = 0;
+        for (start, grapheme) in self.content.grapheme_indices(true) {
+            if start_column >= column {
+                return start;
+            }
+            let width = policy.grapheme_width(grapheme);
+            if start_column + width > column {
+                return match rounding {
+                    Rounding::Floor => start,
+                    Rounding::Ceil => start + grapheme.len(),
+                };
+            }
+            start_column += width;
+        }
+        self.content.len()
+    }
+    /// Returns the length of the text in bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let spans: Spans<Tag> = Spans::from("🙈 foo");
+    /// assert_eq!(spans.len(), 8);
+    /// assert!(!spans.is_empty());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.content.len()
+    }
+    /// Returns whether there is no text.
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+    /// Returns the text without its styles. This is the same as
+    /// [`RawText::raw`].
+    pub fn unstyled(&self) -> String {
+        self.content.clone()
+    }
+    /// Returns the text with its styles removed, keeping the text object so
+    /// it can go through the same pipelines as styled text. Text with the unit
+    /// style is painted as the plain text.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag, WidthSliceable};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let spans: Spans<Tag> = Spans::from("foo ").then(bold, "bar");
+    /// assert_eq!(spans.unstyled(), "foo bar");
+    /// let plain = spans.strip();
+    /// assert_eq!(plain.slice_width(2..).unwrap().to_string(), "o bar");
+    /// ```
+    pub fn strip(&self) -> Spans<()> {
+        Spans::from(self.content.as_str())
+    }
+    /// Returns the number of runs of text with a single style, which is the
+    /// number of spans returned by [`Spans::spans`] for non-empty text.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut spans: Spans<Tag> = Spans::from("foo ");
+    /// spans.push(&Span::borrowed(&bold, "bar"));
+    /// assert_eq!(spans.span_count(), 2);
+    /// assert_eq!(Spans::<Tag>::default().span_count(), 0);
+    /// ```
+    pub fn span_count(&self) -> usize {
+        if self.content.is_empty() {
+            0
+        } else if self.spans.contains_key(0) {
+            self.spans.len()
+        } else {
+            self.spans.len() + 1
+        }
+    }
+    /// Returns the style of the text at a byte index, or `None` if the index
+    /// is past the end of the text or the text there has no style, as when
+    /// unstyled text was pushed onto an empty object.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut spans: Spans<Tag> = Spans::from("foo ");
+    /// spans.push(&Span::borrowed(&bold, "bar"));
+    /// assert_eq!(spans.style_at(0), Some(&Tag::default()));
+    /// assert_eq!(spans.style_at(4), Some(&bold));
+    /// assert_eq!(spans.style_at(7), None);
+    /// ```
+    pub fn style_at(&self, index: usize) -> Option<&T> {
+        if index < self.content.len() {
+            self.spans.search_left(index)
+        } else {
+            None
+        }
+    }
+    /// Creates an empty object with room for at least `capacity` bytes of text.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            content: String::with_capacity(capacity),
+            ..Default::default()
+        }
+    }
+    /// Returns the number of bytes of text this object can hold without
+    /// reallocating.
+    pub fn capacity(&self) -> usize {
+        self.content.capacity()
+    }
+    /// Reserves room for at least `additional` more bytes of text.
+    pub fn reserve(&mut self, additional: usize) {
+        self.content.reserve(additional);
+    }
+    /// Removes all text and styles, keeping the allocated capacity of the text.
+    ///
+    /// Rebuilding an object with [`Spans::clear`] followed by pushes, or
+    /// copying into it with [`Clone::clone_from`], only reallocates the text
+    /// when it outgrows its capacity, so a line rebuilt on every frame settles
+    /// at no text allocations. Styles are kept in a B-tree, which frees its
+    /// nodes when cleared; it holds one entry per change of style, so this
+    /// cost is small for typical lines.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut spans: Spans<Tag> = Spans::with_capacity(64);
+    /// for frame in 0..3 {
+    ///     spans.clear();
+    ///     spans.push(&Span::borrowed(&bold, "frame "));
+    ///     spans.push(&frame.to_string().as_str());
+    /// }
+    /// assert_eq!(format!("{}", spans), "<b>frame 2</b>");
+    /// assert!(spans.capacity() >= 64);
+    /// ```
+    pub fn clear(&mut self) {
+        self.content_mut().clear();
+        self.spans.clear();
+    }
+    /// Returns owned copies of the spans of text contained in this object.
+    ///
+    /// Unlike [`Spans::spans`], the returned spans do not borrow from `self`.
+    pub fn spans_owned(&self) -> impl Iterator<Item = Span<'static, T>> + '_
+    where
+        T: Clone + Default + 'static,
+    {
+        self.spans().map(Span::into_owned)
+    }
+    /// Converts the style of every span, so that text styled with semantic
+    /// styles can be given concrete styles when it is rendered. Adjacent spans
+    /// whose styles convert to the same style are merged.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// #[derive(Clone, Default, PartialEq)]
+    /// enum Token {
+    ///     #[default]
+    ///     Plain,
+    ///     Keyword,
+    ///     Name,
+    /// }
+    /// let code = Spans::styled(Token::Keyword, "fn")
+    ///     .then(Token::Plain, " ")
+    ///     .then(Token::Name, "main");
+    /// let html = code.map_style(|token| match token {
+    ///     Token::Keyword => Tag::new("<b>", "</b>"),
+    ///     _ => Tag::default(),
+    /// });
+    /// assert_eq!(html.to_string(), "<b>fn</b> main");
+    /// assert_eq!(html.span_count(), 2);
+    /// ```
+    pub fn map_style<U, F>(&self, mut f: F) -> Spans<U>
+    where
+        T: Default,
+        U: PartialEq,
+        F: FnMut(&T) -> U,
+    {
+        let mut spans = SearchTree::new();
+        // Text before the first span has the default style implicitly.
+        if !self.content.is_empty() && !self.spans.contains_key(0) {
+            spans.insert(0, f(&T::default()));
+        }
+        for (key, style) in self.spans.iter() {
+            spans.insert(*key, f(style));
+        }
+        spans.dedup();
+        Spans {
+            content: self.content.clone(),
+            spans,
+            width: self.width.clone(),
+        }
+    }
+    /// Returns a copy with the text of every span lowercased, keeping its
+    /// style. Byte offsets may change, since lowercasing can change the length
+    /// of some characters.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut spans: Spans<Tag> = Default::default();
+    /// spans.push(&Span::borrowed(&bold, "FOO"));
+    /// spans.push(&"Bar");
+    /// assert_eq!(format!("{}", spans.casefold()), "<b>foobar</b>");
+    /// ```
+    pub fn casefold(&self) -> Spans<T>
+    where
+        T: Clone + Default + PartialEq,
+    {
+        self.spans()
+            .map(|span| {
+                Span::new(
+                    span.style().clone(),
+                    Cow::Owned(span.raw_ref().to_lowercase()),
+                )
+            })
+            .collect()
+    }
+    /// Replaces each tab with spaces up to the next multiple of `tabstop`
+    /// columns, in the style of the tab. Columns restart after each line
+    /// break. A `tabstop` of zero removes tabs.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::spans;
+    /// use stylish_stringlike::text::{HasWidth, Tag, Width};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let text = spans![Tag::default() => "ab\t", bold => "c\td"];
+    /// let expanded = text.expand_tabs(4);
+    /// assert_eq!(expanded.to_string(), "ab  <b>c   d</b>");
+    /// assert_eq!(expanded.width(), Width::Bounded(9));
+    /// ```
+    pub fn expand_tabs(&self, tabstop: usize) -> Spans<T>
+    where
+        T: Clone + Default + PartialEq,
+    {
+        let mut column = 0;
+        let policy = WidthPolicy::current();
+        self.spans()
+            .map(|span| {
+                let mut expanded = String::with_capacity(span.raw_ref().len());
+                for grapheme in span.raw_ref().graphemes(true) {
+                    if grapheme == "\t" {
+                        let spaces = match tabstop {
+                            0 => 0,
+                            tabstop => tabstop - column % tabstop,
+                        };
+                        expanded.push_str(&" ".repeat(spaces));
+                        column += spaces;
+                    } else if grapheme.ends_with('\n') {
+                        expanded.push_str(grapheme);
+                        column = 0;
+                    } else {
+                        expanded.push_str(grapheme);
+                        column += policy.grapheme_width(grapheme);
+                    }
+                }
+                Span::new(span.style().clone(), Cow::Owned(expanded))
+            })
+            .collect()
+    }
+    /// Gives the segments of the text between matches of `pattern` styles
+    /// taken in turn from `styles`, as for alternating colors of path
+    /// components or table rows. The matches keep their styles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `styles` is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let even = Tag::new("<e>", "</e>");
+    /// let odd = Tag::new("<o>", "</o>");
+    /// let path: Spans<Tag> = Spans::from("usr/local/bin");
+    /// assert_eq!(
+    ///     path.stripe("/", &[even, odd]).to_string(),
+    ///     "<e>usr</e>/<o>local</o>/<e>bin</e>"
+    /// );
+    /// ```
+    pub fn stripe(&self, pattern: &str, styles: &[T]) -> Spans<T>
+    where
+        T: Clone + Default + PartialEq,
+    {
+        assert!(!styles.is_empty(), "stripe needs at least one style");
+        self.stripe_with(pattern, |segment, _| styles[segment % styles.len()].clone())
+    }
+    /// Restyles the segments of the text between matches of `pattern`.
+    /// `style` is called with the index of each segment, counting empty
+    /// segments, and the current style of its text, and returns the new
+    /// style. The matches keep their styles.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Color, Format, Spans};
+    /// let rows = Spans::styled(Color::Red.normal(), "a\nb\n").then(Color::Blue.normal(), "c");
+    /// let zebra = rows.stripe_with("\n", |row, style| match row % 2 {
+    ///     0 => *style,
+    ///     _ => style.bg(Color::White),
+    /// });
+    /// assert_eq!(zebra.style_at(2), Some(&Color::Red.on(Color::White)));
+    /// assert_eq!(zebra.style_at(4), Some(&Color::Blue.normal()));
+    /// ```
+    pub fn stripe_with<F>(&self, pattern: &str, mut style: F) -> Spans<T>
+    where
+        T: Clone + Default + PartialEq,
+        F: FnMut(usize, &T) -> T,
+    {
+        let mut matches = self
+            .content
+            .match_indices(pattern)
+            .map(|(start, found)| (start, start + found.len()))
+            .peekable();
+        let mut result: Spans<T> = Default::default();
+        let mut segment = 0;
+        let mut offset = 0;
+        for span in self.spans() {
+            let text = span.raw_ref();
+            let end = offset + text.len();
+            let mut at = offset;
+            while at < end {
+                let (stop, restyled) = match matches.peek() {
+                    Some(&(start, stop)) if start <= at => {
+                        if stop <= end {
+                            matches.next();
+                            segment += 1;
+                        }
+                        (stop.min(end), None)
+                    }
+                    Some(&(start, _)) => (start.min(end), Some(style(segment, span.style()))),
+                    None => (end, Some(style(segment, span.style()))),
+                };
+                let part = &text[at - offset..stop - offset];
+                if !part.is_empty() {
+                    let part_style = restyled.as_ref().unwrap_or_else(|| span.style());
+                    result.push(&Span::borrowed(part_style, part));
+                }
+                at = stop;
+            }
+            offset = end;
+        }
+        result
+    }
+    /// Restyles the text in pieces `step` columns wide, as for a color
+    /// gradient. `style` is called with the current style of each piece and
+    /// the position of its first column, from 0.0 at the first column to 1.0
+    /// at the last, and returns the new style. A piece covering text of
+    /// several styles is restyled separately for each. Graphemes are never
+    /// split, so a wide grapheme belongs to the piece it starts in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Color, Format, Spans};
+    /// let text: Spans<Format> = Spans::from("abcde");
+    /// let faded = text.gradient(2, |style, position| {
+    ///     let level = (255.0 * (1.0 - position)) as u8;
+    ///     style.clone().fg(Color::Rgb(level, level, level))
+    /// });
+    /// assert_eq!(faded.span_count(), 3);
+    /// assert_eq!(faded.style_at(2), Some(&Color::Rgb(127, 127, 127).normal()));
+    /// ```
+    pub fn gradient<F>(&self, step: usize, mut style: F) -> Spans<T>
+    where
+        T: Clone + Default + PartialEq,
+        F: FnMut(&T, f64) -> T,
+    {
+        assert!(step > 0, "gradient step must be positive");
+        let policy = WidthPolicy::current();
+        let last_column = self.bounded_width().saturating_sub(1).max(1) as f64;
+        let mut result = Spans {
+            content: String::with_capacity(self.content.len()),
+            spans: SearchTree::new(),
+            width: self.width.clone(),
+        };
+        let mut column = 0;
+        // The piece and run last restyled, and the new style.
+        let mut current: Option<(usize, usize, T)> = None;
+        for (run_index, (original, run)) in self.runs().enumerate() {
+            for grapheme in run.graphemes(true) {
+                let piece = column / step;
+                let restyle = match &current {
+                    Some((last_piece, last_run, _)) => {
+                        *last_piece != piece || *last_run != run_index
+                    }
+                    None => true,
+                };
+                if restyle {
+                    let position = (piece * step) as f64 / last_column;
+                    let new = style(&original, position.min(1.0));
+                    if !matches!(&current, Some((_, _, last)) if *last == new) {
+                        result.spans.insert(result.content.len(), new.clone());
+                    }
+                    current = Some((piece, run_index, new));
+                }
+                result.content.push_str(grapheme);
+                column += policy.grapheme_width(grapheme);
+            }
+        }
+        result
+    }
+    /// Reverses the order of the graphemes of the text, keeping the style of
+    /// each grapheme.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let italic = Tag::new("<i>", "</i>");
+    /// let text = Spans::styled(bold, "ab🇫🇷").then(italic, "ce\u{301}");
+    /// assert_eq!(text.reverse().to_string(), "<i>e\u{301}c</i><b>🇫🇷ba</b>");
+    /// ```
+    pub fn reverse(&self) -> Spans<T>
+    where
+        T: Clone + Default + PartialEq,
+    {
+        let spans: Vec<Span<T>> = self.spans().collect();
+        spans
+            .iter()
+            .rev()
+            .filter(|span| !span.raw_ref().is_empty())
+            .map(|span| {
+                let reversed: String = span.raw_ref().graphemes(true).rev().collect();
+                Span::new(span.style().clone(), Cow::Owned(reversed))
             })
+            .collect()
+    }
+    /// Replaces each run of whitespace with a single space, in the style of
+    /// the first whitespace character of the run, for showing multi-line or
+    /// indented text on one line.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let text = Spans::styled(bold, "a \n").then(Tag::default(), "\t b  ");
+    /// assert_eq!(text.squeeze_whitespace().to_string(), "<b>a </b>b ");
+    /// ```
+    pub fn squeeze_whitespace(&self) -> Spans<T>
+    where
+        T: Clone + Default + PartialEq,
+    {
+        self.collapse(char::is_whitespace, " ")
+    }
+    /// Replaces each run of characters matching `pattern` with `replacement`,
+    /// in the style of the first character of the run. Runs continue across
+    /// span boundaries.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let text = Spans::styled(bold, "a--").then(Tag::default(), "-b-c");
+    /// assert_eq!(text.collapse(|c| c == '-', "–").to_string(), "<b>a–</b>b–c");
+    /// ```
+    pub fn collapse<P>(&self, mut pattern: P, replacement: &str) -> Spans<T>
+    where
+        T: Clone + Default + PartialEq,
+        P: FnMut(char) -> bool,
+    {
+        let mut in_run = false;
+        self.spans()
+            .map(|span| {
+                let mut collapsed = String::with_capacity(span.raw_ref().len());
+                for c in span.raw_ref().chars() {
+                    if !pattern(c) {
+                        collapsed.push(c);
+                        in_run = false;
+                    } else if !in_run {
+                        collapsed.push_str(replacement);
+                        in_run = true;
+                    }
+                }
+                Span::new(span.style().clone(), Cow::Owned(collapsed))
+            })
+            .collect()
+    }
+    /// Compares the text of this object with other text, ignoring case and
+    /// style.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut spans: Spans<Tag> = Default::default();
+    /// spans.push(&Span::borrowed(&bold, "Straße"));
+    /// assert!(spans.eq_ignore_case(&String::from("STRAßE")));
+    /// assert!(spans.eq_ignore_case(&Spans::<Tag>::from("straße")));
+    /// assert!(!spans.eq_ignore_case(&String::from("strasse")));
+    /// ```
+    pub fn eq_ignore_case<S: RawText>(&self, other: &S) -> bool {
+        let theirs = other.raw_ref().chars().flat_map(char::to_lowercase);
+        self.content.chars().flat_map(char::to_lowercase).eq(theirs)
+    }
+    /// Inserts a styled span at a byte index, shifting the text after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is larger than the length of the text or does not
+    /// lie on a [`char`] boundary.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// let italic = Tag::new("<i>", "</i>");
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut spans: Spans<Tag> = Default::default();
+    /// spans.push(&Span::borrowed(&italic, "foobar"));
+    /// spans.insert(3, &Span::borrowed(&bold, "baz"));
+    /// assert_eq!(format!("{}", spans), "<i>foo</i><b>baz</b><i>bar</i>");
+    /// ```
+    pub fn insert(&mut self, index: usize, span: &Span<'_, T>)
+    where
+        T: Clone + Default + PartialEq,
+    {
+        let mut spans = SearchTree::new();
+        spans.insert(0, span.style().clone().into_owned());
+        self.splice(index, span.raw_ref(), &spans);
+    }
+    /// Inserts styled text at a byte index, shifting the text after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is larger than the length of the text or does not
+    /// lie on a [`char`] boundary.
+    pub fn insert_spans(&mut self, index: usize, other: &Spans<T>)
+    where
+        T: Clone + Default + PartialEq,
+    {
+        self.splice(index, &other.content, &other.spans);
+    }
+    /// Inserts unstyled text at a byte index. The new text takes the style
+    /// of the text preceding it, or of the text following it if inserted
+    /// at the start.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is larger than the length of the text or does not
+    /// lie on a [`char`] boundary.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Joinable, Span, Spans, Tag};
+    /// let italic = Tag::new("<i>", "</i>");
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut spans: Spans<Tag> = Span::borrowed(&italic, "foo")
+    ///     .join(&Span::borrowed(&bold, "bar"));
+    /// spans.insert_str(3, "d");
+    /// assert_eq!(format!("{}", spans), "<i>food</i><b>bar</b>");
+    /// ```
+    pub fn insert_str(&mut self, index: usize, string: &str) {
+        assert!(
+            self.content.is_char_boundary(index),
+            "insertion index is not a char boundary"
+        );
+        self.spans.shift_right(index.max(1), string.len());
+        self.content_mut().insert_str(index, string);
+    }
+    /// Removes the text in a byte range, shifting the text after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or does not lie on [`char`]
+    /// boundaries.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Joinable, Span, Spans, Tag};
+    /// let italic = Tag::new("<i>", "</i>");
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut spans: Spans<Tag> = Span::borrowed(&italic, "foo")
+    ///     .join(&Span::borrowed(&bold, "bar"));
+    /// spans.remove(2..4);
+    /// assert_eq!(format!("{}", spans), "<i>fo</i><b>ar</b>");
+    /// ```
+    pub fn remove<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+        T: Clone + PartialEq,
+    {
+        let (start, end) = bounds::normalize_within(&range, self.content.len());
+        assert!(
+            start <= end
+                && self.content.is_char_boundary(start)
+                && self.content.is_char_boundary(end),
+            "removal range is out of bounds or not on char boundaries"
+        );
+        if start == end {
+            return;
+        }
+        if end < self.content.len() && !self.spans.contains_key(end) {
+            if let Some(style) = self.spans.search_left_shared(end).cloned() {
+                self.spans.insert_shared(end, style);
+            }
+        }
+        self.spans.shift_left(end, end - start);
+        self.content_mut().replace_range(start..end, "");
+        self.spans.dedup();
+        self.trim();
+    }
+    fn splice(&mut self, index: usize, text: &str, spans: &SearchTree<T>)
+    where
+        T: Clone + Default + PartialEq,
+    {
+        assert!(
+            self.content.is_char_boundary(index),
+            "insertion index is not a char boundary"
+        );
+        if text.is_empty() {
+            return;
+        }
+        let following = if index < self.content.len() && !self.spans.contains_key(index) {
+            Some(
+                self.spans
+                    .search_left_shared(index)
+                    .cloned()
+                    .unwrap_or_default(),
+            )
+        } else {
+            None
+        };
+        self.spans.shift_right(index, text.len());
+        if !spans.contains_key(0) {
+            self.spans.insert(index, Default::default());
+        }
+        // The following style goes in first, so that copying the inserted
+        // styles does not merge them with the styles after it.
+        if let Some(following) = following {
+            self.spans.insert_shared(index + text.len(), following);
+        }
+        // copy_with_shift always succeeds because the shift is positive
+        self.spans.copy_with_shift(spans, .., index).unwrap();
+        self.content_mut().insert_str(index, text);
+        self.spans.dedup();
+        self.trim();
+    }
+    /// Gives mutable access to the content, invalidating the cached width.
+    fn content_mut(&mut self) -> &mut String {
+        self.width.take();
+        &mut self.content
     }
     fn trim(&mut self) {
         self.spans.trim(self.content.len().saturating_sub(1));
@@ -113,40 +1331,76 @@ impl<T: Clone + PartialEq> Pushable<Spans<T>> for Spans<T> {
         self.spans
             .copy_with_shift(&other.spans, .., self.content.len())
             .unwrap();
-        self.content.push_str(&other.content);
+        self.content_mut().push_str(&other.content);
         self.trim();
     }
+    /// Copies the range of the other text, sharing its styles, without
+    /// building the slice first.
+    fn push_slice<R>(&mut self, other: &Spans<T>, range: R) -> bool
+    where
+        R: RangeBounds<usize> + Clone,
+    {
+        let (start, end) = bounds::normalize(&range);
+        let text = match end {
+            Some(end) => other.content.get(start..end),
+            None => other.content.get(start..),
+        };
+        let text = match text {
+            Some(text) => text,
+            None => return false,
+        };
+        let at = self.content.len();
+        self.spans.copy_slice(&other.spans, start, end, at);
+        self.content_mut().push_str(text);
+        self.trim();
+        true
+    }
 }
 
 impl<'a, T: Clone + PartialEq> Pushable<Span<'a, T>> for Spans<T> {
     fn push(&mut self, other: &Span<'a, T>) {
-        self.spans
-            .insert(self.content.len(), other.style().clone().into_owned());
-        self.content.push_str(other.raw_ref());
-        self.spans.dedup();
+        let index = self.content.len();
+        self.spans.insert(index, other.style().clone().into_owned());
+        self.content_mut().push_str(other.raw_ref());
+        self.spans.dedup_from(index);
         self.trim();
     }
 }
 
 impl<T> Pushable<&str> for Spans<T> {
     fn push(&mut self, other: &&str) {
-        self.content.push_str(other);
+        self.content_mut().push_str(other);
     }
 }
 
 impl<T> Pushable<str> for Spans<T> {
     fn push(&mut self, other: &str) {
-        self.content.push_str(other);
+        self.content_mut().push_str(other);
     }
 }
 
+impl<T> Pushable<Spans<T>> for String {
+    /// Pushes the raw content of the text, dropping its styles.
+    fn push(&mut self, other: &Spans<T>) {
+        self.push_str(&other.content);
+    }
+}
+
+impl<T: Clone> Pushable<Span<'_, T>> for String {
+    /// Pushes the raw content of the span, dropping its style.
+    fn push(&mut self, other: &Span<'_, T>) {
+        self.push_str(other.raw_ref());
+    }
+}
+
+#[cfg(feature = "regex")]
 impl<T: Default + Clone + PartialEq> Expandable for Spans<T> {
     fn expand(&self, capture: &Captures) -> Self {
         let mut result: Spans<T> = Default::default();
         for span in self.spans() {
             let mut dst = String::new();
             capture.expand(span.raw_ref(), &mut dst);
-            let new_span = Span::<T>::borrowed(&span.style(), &dst);
+            let new_span = Span::<T>::borrowed(span.style(), &dst);
             result.push(&new_span);
         }
         result
@@ -160,6 +1414,7 @@ impl<'a, T: Clone + PartialEq> Replaceable<&'a str> for Spans<T> {
         let mut result = Spans {
             content: String::new(),
             spans: SearchTree::new(),
+            width: Default::default(),
         };
 
         let mut last_end = 0;
@@ -167,7 +1422,7 @@ impl<'a, T: Clone + PartialEq> Replaceable<&'a str> for Spans<T> {
             if let Some(spans) = self.slice(last_end..start) {
                 result.push(&spans);
                 if let Some(mut r) = self.slice(start..start + part.len()) {
-                    r.content = String::from(replacer);
+                    *r.content_mut() = String::from(replacer);
                     result.push(&r);
                 }
             }
@@ -179,11 +1434,16 @@ impl<'a, T: Clone + PartialEq> Replaceable<&'a str> for Spans<T> {
         result.trim();
         result
     }
+}
+
+#[cfg(feature = "regex")]
+impl<'a, T: Clone + PartialEq> RegexReplaceable<&'a str> for Spans<T> {
     fn replace_regex(&self, searcher: &Regex, replacer: &'a str) -> Self {
         let mut last_end = 0;
         let mut result = Spans {
             content: String::new(),
             spans: SearchTree::new(),
+            width: Default::default(),
         };
         let captures = searcher.captures_iter(&self.content);
         for capture in captures {
@@ -195,7 +1455,7 @@ impl<'a, T: Clone + PartialEq> Replaceable<&'a str> for Spans<T> {
                 if let Some(mut r) = self.slice(mat.start()..mat.end()) {
                     let mut new = String::new();
                     String::from(replacer).replace_append(&capture, &mut new);
-                    r.content = new;
+                    *r.content_mut() = new;
                     result.push(&r);
                 }
                 last_end = mat.end();
@@ -209,7 +1469,7 @@ impl<'a, T: Clone + PartialEq> Replaceable<&'a str> for Spans<T> {
     }
 }
 
-impl<'a, T: Clone> Sliceable for Spans<T> {
+impl<T: Clone> Sliceable for Spans<T> {
     fn slice<R>(&self, range: R) -> Option<Self>
     where
         R: RangeBounds<usize> + Clone,
@@ -220,6 +1480,7 @@ impl<'a, T: Clone> Sliceable for Spans<T> {
                 return Some(Spans {
                     content: string.to_string(),
                     spans: SearchTree::new(),
+                    width: Default::default(),
                 });
             }
         }
@@ -228,6 +1489,7 @@ impl<'a, T: Clone> Sliceable for Spans<T> {
             Some(Spans {
                 content: string.to_string(),
                 spans,
+                width: Default::default(),
             })
         } else {
             None
@@ -235,10 +1497,10 @@ impl<'a, T: Clone> Sliceable for Spans<T> {
     }
 }
 
-impl<'a, T, U> FromIterator<U> for Spans<T>
+impl<T, U> FromIterator<U> for Spans<T>
 where
-    T: Clone + PartialEq + 'a,
-    U: Borrow<Spans<T>> + 'a,
+    T: Clone + PartialEq,
+    U: Borrow<Spans<T>>,
 {
     fn from_iter<I>(iter: I) -> Spans<T>
     where
@@ -270,6 +1532,26 @@ where
     }
 }
 
+impl<T: Clone + Default + 'static> IntoIterator for Spans<T> {
+    type Item = Span<'static, T>;
+    type IntoIter = std::vec::IntoIter<Span<'static, T>>;
+    /// Consumes the text, returning its spans.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Joinable, RawText, Span, Spans, Tag};
+    /// let italic = Tag::new("<i>", "</i>");
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let spans: Spans<Tag> = Span::borrowed(&italic, "foo").join(&Span::borrowed(&bold, "bar"));
+    /// let parts: Vec<Span<'static, Tag>> = spans.into_iter().collect();
+    /// assert_eq!(parts[1].raw(), "bar");
+    /// assert_eq!(parts[1].style().as_ref(), &bold);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.spans_owned().collect::<Vec<_>>().into_iter()
+    }
+}
+
 impl<T> RawText for Spans<T> {
     fn raw(&self) -> String {
         self.content.clone()
@@ -289,19 +1571,105 @@ where
         Spans {
             content: String::from(other),
             spans,
+            width: Default::default(),
+        }
+    }
+}
+
+impl<T> From<String> for Spans<T>
+where
+    T: Clone + Default + PartialEq,
+{
+    /// Creates unstyled text, reusing the allocation of the string.
+    fn from(other: String) -> Spans<T> {
+        let mut spans: SearchTree<_> = Default::default();
+        spans.insert(0, Default::default());
+        Spans {
+            content: other,
+            spans,
+            width: Default::default(),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> From<Vec<(T, String)>> for Spans<T> {
+    /// Creates text from pairs of styles and the text they apply to.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let pairs = vec![
+    ///     (bold.clone(), String::from("fn")),
+    ///     (Tag::default(), String::from(" ")),
+    ///     (bold.clone(), String::from("main")),
+    /// ];
+    /// let text = Spans::from(pairs.clone());
+    /// assert_eq!(text.to_string(), "<b>fn</b> <b>main</b>");
+    /// assert_eq!(Vec::from(text), pairs);
+    /// ```
+    fn from(pairs: Vec<(T, String)>) -> Spans<T> {
+        let mut result: Spans<T> = Default::default();
+        for (style, text) in pairs {
+            result.push_styled(style, &text);
         }
+        result
+    }
+}
+
+impl<T: Clone + Default + PartialEq> From<&Spans<T>> for Vec<(T, String)> {
+    /// Returns pairs of styles and the text they apply to, merging adjacent
+    /// text with the same style and leaving out empty text.
+    fn from(spans: &Spans<T>) -> Vec<(T, String)> {
+        let mut pairs: Vec<(T, String)> = vec![];
+        for (style, text) in spans.runs().filter(|(_, text)| !text.is_empty()) {
+            match pairs.last_mut() {
+                Some((last, last_text)) if *last == *style => last_text.push_str(text),
+                _ => pairs.push((style.into_owned(), text.to_string())),
+            }
+        }
+        pairs
+    }
+}
+
+impl<T: Clone + Default + PartialEq> From<Spans<T>> for Vec<(T, String)> {
+    fn from(spans: Spans<T>) -> Vec<(T, String)> {
+        Vec::from(&spans)
     }
 }
 
-impl<'a, T: Paintable + Clone + Default> fmt::Display for Spans<T> {
+impl<T: Paintable + Clone + Default> fmt::Display for Spans<T> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        T::paint_many(self.spans().map(|span| (span.style().clone(), span.raw()))).fmt(fmt)
+        let groups = self.spans().map(Span::into_parts);
+        if fmt.width().is_some() || fmt.precision().is_some() {
+            // Padding applies to the painted output as a whole.
+            T::paint_many(groups).fmt(fmt)
+        } else {
+            T::paint_many_fmt(groups, fmt)
+        }
     }
 }
 
 impl<T> BoundedWidth for Spans<T> {
+    /// Returns the width of the content. The width under the default
+    /// [`WidthPolicy`] is computed once and cached until the content changes.
     fn bounded_width(&self) -> usize {
-        self.content.bounded_width()
+        if WidthPolicy::current().is_default() {
+            *self.width.get_or_init(|| self.content.bounded_width())
+        } else {
+            self.content.bounded_width()
+        }
+    }
+}
+
+impl<T: Paintable + Clone + Default> PaintedWidth for Spans<T> {
+    fn painted_width(&self) -> usize {
+        self.bounded_width()
+            + self
+                .spans()
+                .filter(|span| !span.raw_ref().is_empty())
+                .map(|span| span.style().markup_width())
+                .sum::<usize>()
     }
 }
 
@@ -311,6 +1679,42 @@ impl<T> HasWidth for Spans<T> {
     }
 }
 
+impl<T> BoundedWidth for &Spans<T> {
+    fn bounded_width(&self) -> usize {
+        (**self).bounded_width()
+    }
+}
+
+impl<T> HasWidth for &Spans<T> {
+    fn width(&self) -> Width {
+        (**self).width()
+    }
+}
+
+impl<T: Clone> WidthSliceable for &Spans<T> {
+    type Output = Spans<T>;
+    fn slice_width<R>(&self, range: R) -> Option<Self::Output>
+    where
+        R: RangeBounds<usize>,
+    {
+        (**self).slice_width(range)
+    }
+    fn push_width<R>(&self, range: R, output: &mut Self::Output) -> Option<usize>
+    where
+        R: RangeBounds<usize>,
+        Self::Output: Pushable<Self::Output> + BoundedWidth,
+    {
+        (**self).push_width(range, output)
+    }
+    fn width_within<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<usize>,
+        Self::Output: BoundedWidth,
+    {
+        (**self).width_within(range)
+    }
+}
+
 impl<T: PartialEq + Clone> Joinable<Spans<T>> for Spans<T> {
     type Output = Spans<T>;
     fn join(&self, other: &Spans<T>) -> Self::Output {
@@ -332,10 +1736,149 @@ impl<T: PartialEq + Clone> Joinable<Span<'_, T>> for Spans<T> {
     }
 }
 
+impl<T> Joinable<&str> for Spans<T>
+where
+    T: Clone,
+{
+    type Output = Spans<T>;
+    /// Joins text in the style of the end of this text, as pushing it does.
+    fn join(&self, other: &&str) -> Self::Output {
+        let mut result = self.clone();
+        result.push(other);
+        result
+    }
+}
+
+impl<T: Clone + PartialEq> Add<&Spans<T>> for Spans<T> {
+    type Output = Spans<T>;
+    /// Appends text, like adding to a `String`.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Span, Spans, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let prefix: Spans<Tag> = Spans::from("[");
+    /// let mut body: Spans<Tag> = Default::default();
+    /// body += &Span::borrowed(&bold, "ok");
+    /// let line = prefix + &body + "]";
+    /// // Like pushing, adding a `&str` continues the last style.
+    /// assert_eq!(line.to_string(), "[<b>ok]</b>");
+    /// ```
+    fn add(mut self, other: &Spans<T>) -> Self::Output {
+        self.push(other);
+        self
+    }
+}
+
+impl<T: Clone + PartialEq> Add<&Span<'_, T>> for Spans<T> {
+    type Output = Spans<T>;
+    fn add(mut self, other: &Span<'_, T>) -> Self::Output {
+        self.push(other);
+        self
+    }
+}
+
+impl<T> Add<&str> for Spans<T> {
+    type Output = Spans<T>;
+    fn add(mut self, other: &str) -> Self::Output {
+        self.push(other);
+        self
+    }
+}
+
+impl<T: Clone + PartialEq> AddAssign<&Spans<T>> for Spans<T> {
+    fn add_assign(&mut self, other: &Spans<T>) {
+        self.push(other);
+    }
+}
+
+impl<T: Clone + PartialEq> AddAssign<&Span<'_, T>> for Spans<T> {
+    fn add_assign(&mut self, other: &Span<'_, T>) {
+        self.push(other);
+    }
+}
+
+impl<T> AddAssign<&str> for Spans<T> {
+    fn add_assign(&mut self, other: &str) {
+        self.push(other);
+    }
+}
+
+impl<T: Clone + PartialEq> Sum for Spans<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Default::default(), |total, spans| total + &spans)
+    }
+}
+
+impl<'a, T: Clone + PartialEq + 'a> Sum<&'a Spans<T>> for Spans<T> {
+    fn sum<I: Iterator<Item = &'a Spans<T>>>(iter: I) -> Self {
+        iter.fold(Default::default(), |total, spans| total + spans)
+    }
+}
+
+impl<'a, T: Clone + PartialEq> Sum<Span<'a, T>> for Spans<T> {
+    fn sum<I: Iterator<Item = Span<'a, T>>>(iter: I) -> Self {
+        iter.fold(Default::default(), |total, span| total + &span)
+    }
+}
+
+#[cfg(feature = "ratatui")]
+impl<T: Clone + Default> Spans<T> {
+    /// Converts the text into a ratatui line, mapping each style to a ratatui
+    /// style.
+    ///
+    /// # Example
+    /// ```
+    /// use ratatui::style::{Color as RColor, Style};
+    /// use stylish_stringlike::text::{Color, Format, Pushable, Span, Spans};
+    /// let red = Color::Red.normal();
+    /// let mut spans: Spans<Format> = Default::default();
+    /// spans.push(&Span::borrowed(&red, "foo"));
+    /// let line = spans.to_line(|format| match format.fg {
+    ///     Some(Color::Red) => Style::new().fg(RColor::Red),
+    ///     _ => Style::new(),
+    /// });
+    /// assert_eq!(line.spans[0].content, "foo");
+    /// assert_eq!(line.spans[0].style, Style::new().fg(RColor::Red));
+    /// ```
+    pub fn to_line<F>(&self, mut mapper: F) -> ratatui::text::Line<'_>
+    where
+        F: FnMut(&T) -> ratatui::style::Style,
+    {
+        self.runs()
+            .filter(|(_, s)| !s.is_empty())
+            .map(|(style, s)| ratatui::text::Span::styled(s, mapper(&style)))
+            .collect()
+    }
+}
+
+#[cfg(feature = "ratatui")]
+impl<'a> From<&'a Spans<ratatui::style::Style>> for ratatui::text::Line<'a> {
+    fn from(spans: &'a Spans<ratatui::style::Style>) -> Self {
+        spans.to_line(|style| *style)
+    }
+}
+
+#[cfg(feature = "ratatui")]
+impl From<&ratatui::text::Line<'_>> for Spans<ratatui::style::Style> {
+    /// Converts a ratatui line, patching the line's style with the style of
+    /// each of its spans.
+    fn from(line: &ratatui::text::Line<'_>) -> Self {
+        let mut result: Spans<_> = Default::default();
+        for span in &line.spans {
+            result.push(&Span::new(
+                Cow::Owned(line.style.patch(span.style)),
+                Cow::Borrowed(span.content.as_ref()),
+            ));
+        }
+        result
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::text::{Sliceable, Split, Splitable, WidthSliceable};
+    use crate::text::{Sliceable, Split, Splitable, Tag, WidthSliceable};
     use ansi_term::{ANSIString, ANSIStrings, Color, Style};
     fn strings_to_spans(strings: &[ANSIString<'_>]) -> Spans<Style> {
         strings.iter().map(Span::<Style>::from).collect()
@@ -438,6 +1981,7 @@ mod test {
         ]);
         assert_eq!(new_text, target_text);
     }
+    #[cfg(feature = "regex")]
     #[test]
     fn simple_regex_replace() {
         let text = strings_to_spans(&[Color::Red.paint("foooo")]);
@@ -446,6 +1990,7 @@ mod test {
 
         assert_eq!(new_text, target_text);
     }
+    #[cfg(feature = "regex")]
     #[test]
     fn replace_regex_across_span_simple_trival() {
         let text = strings_to_spans(&[Color::Red.paint("Here lies "), Color::Blue.paint("Beavis")]);
@@ -459,6 +2004,7 @@ mod test {
         ]);
         assert_eq!(new_text, target_text);
     }
+    #[cfg(feature = "regex")]
     #[test]
     fn replace_regex_across_span_simple_backref() {
         let text = strings_to_spans(&[Color::Red.paint("Here lies "), Color::Blue.paint("Beavis")]);
@@ -470,6 +2016,7 @@ mod test {
         ]);
         assert_eq!(new_text, target_text);
     }
+    #[cfg(feature = "regex")]
     #[test]
     fn replace_regex_across_span_simple_2_backref() {
         let text = strings_to_spans(&[
@@ -487,6 +2034,7 @@ mod test {
         println!("actual:   {}", new_text);
         assert_eq!(new_text, target_text);
     }
+    #[cfg(feature = "regex")]
     #[test]
     fn replace_regex_across_span_simple_2_trivial() {
         let text = strings_to_spans(&[
@@ -504,6 +2052,7 @@ mod test {
         println!("actual:   {}", new_text);
         assert_eq!(new_text, target_text);
     }
+    #[cfg(feature = "regex")]
     #[test]
     fn replace_regex_empty() {
         let text = strings_to_spans(&[
@@ -514,6 +2063,7 @@ mod test {
         let new_text = text.replace_regex(&Regex::new("quux").unwrap(), "bar");
         assert_eq!(new_text, text);
     }
+    #[cfg(feature = "regex")]
     #[test]
     fn replace_regex_empty_fancy() {
         let text = strings_to_spans(&[
@@ -524,6 +2074,7 @@ mod test {
         let new_text = text.replace_regex(&Regex::new("([zyx])").unwrap(), "missing $1 letters");
         assert_eq!(new_text, text);
     }
+    #[cfg(feature = "regex")]
     #[test]
     fn replace_regex_styled_easy() {
         let text = strings_to_spans(&[
@@ -540,6 +2091,7 @@ mod test {
         ]);
         assert_eq!(expected, actual);
     }
+    #[cfg(feature = "regex")]
     #[test]
     fn replace_regex_styled_complex() {
         let text = strings_to_spans(&[
@@ -588,6 +2140,110 @@ mod test {
         assert_eq!(expected, actual);
     }
     #[test]
+    fn into_iter() {
+        let texts = [Color::Red.paint("foo"), Color::Blue.paint("bar")];
+        let owned: Vec<Span<'static, Style>> = strings_to_spans(&texts).into_iter().collect();
+        let expected: Vec<Span<Style>> = texts.iter().map(Span::<Style>::from).collect();
+        assert_eq!(expected, owned);
+    }
+    #[test]
+    fn spans_owned() {
+        let texts = [Color::Red.paint("foo"), Color::Blue.paint("bar")];
+        let spans = strings_to_spans(&texts);
+        let owned: Vec<Span<'static, Style>> = spans.spans_owned().collect();
+        drop(spans);
+        let actual: Spans<Style> = owned.into_iter().collect();
+        assert_eq!(strings_to_spans(&texts), actual);
+    }
+    #[test]
+    fn from_fmt() {
+        let actual = Spans::from_fmt(Color::Red.normal(), format_args!("{}-{}", 1, "a"));
+        let expected = strings_to_spans(&[Color::Red.paint("1-a")]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn push_fmt_empty() {
+        let mut actual = strings_to_spans(&[Color::Red.paint("foo")]);
+        actual.push_fmt(Color::Blue.normal(), format_args!("{}", ""));
+        actual.push(&Span::new(
+            Cow::Owned(Color::Red.normal()),
+            Cow::Borrowed("bar"),
+        ));
+        let expected = strings_to_spans(&[Color::Red.paint("foobar")]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn select_across_spans() {
+        let text = strings_to_spans(&[Color::Red.paint("012"), Color::Blue.paint("345")]);
+        let actual = text.select(1..4, |style| style.reverse());
+        let expected = strings_to_spans(&[
+            Color::Red.paint("0"),
+            Color::Red.reverse().paint("12"),
+            Color::Blue.reverse().paint("3"),
+            Color::Blue.paint("45"),
+        ]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn select_wide_graphemes() {
+        let text = strings_to_spans(&[Color::Red.paint("😼🙋👩")]);
+        let actual = text.select(1..5, |style| style.reverse());
+        let expected = strings_to_spans(&[
+            Color::Red.paint("😼"),
+            Color::Red.reverse().paint("🙋"),
+            Color::Red.paint("👩"),
+        ]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn clear_keeps_capacity() {
+        let mut text = strings_to_spans(&[Color::Red.paint("012"), Color::Blue.paint("345")]);
+        let capacity = text.capacity();
+        assert_eq!(6, text.bounded_width());
+        text.clear();
+        assert_eq!(capacity, text.capacity());
+        assert_eq!(0, text.bounded_width());
+        assert_eq!(Spans::<Style>::default(), text);
+        text.push(&strings_to_spans(&[Color::Green.paint("6")]));
+        assert_eq!(strings_to_spans(&[Color::Green.paint("6")]), text);
+    }
+    #[test]
+    fn clone_from_reuses_text() {
+        let source = strings_to_spans(&[Color::Red.paint("012"), Color::Blue.paint("345")]);
+        let mut text: Spans<Style> = Spans::with_capacity(64);
+        text.clone_from(&source);
+        assert_eq!(source, text);
+        assert!(text.capacity() >= 64);
+    }
+    #[test]
+    fn casefold_keeps_styles() {
+        let text = strings_to_spans(&[Color::Red.paint("FOO"), Color::Blue.paint("İX")]);
+        let actual = text.casefold();
+        let expected = strings_to_spans(&[Color::Red.paint("foo"), Color::Blue.paint("i\u{307}x")]);
+        assert_eq!(expected, actual);
+        assert!(text.eq_ignore_case(&actual));
+        assert!(!text.eq_ignore_case(&String::from("foo")));
+    }
+    #[test]
+    fn select_nothing() {
+        let text = strings_to_spans(&[Color::Red.paint("012")]);
+        let actual = text.select(5.., |style| style.reverse());
+        assert_eq!(text, actual);
+    }
+    #[test]
+    fn cached_width_invalidated() {
+        let mut text = strings_to_spans(&[Color::Red.paint("012")]);
+        assert_eq!(3, text.bounded_width());
+        text.push(&strings_to_spans(&[Color::Blue.paint("👱")]));
+        assert_eq!(5, text.bounded_width());
+        text.insert_str(0, "ab");
+        assert_eq!(7, text.bounded_width());
+        text.remove(..4);
+        assert_eq!(3, text.bounded_width());
+        let replaced = text.replace("👱", "a");
+        assert_eq!(2, replaced.bounded_width());
+    }
+    #[test]
     fn raw() {
         let text = strings_to_spans(&[
             Color::Red.paint("Here is some f"),
@@ -656,6 +2312,111 @@ mod test {
         assert_eq!(expected, actual);
     }
     #[test]
+    fn insert_middle() {
+        let mut actual = strings_to_spans(&[Color::Red.paint("0123"), Color::Blue.paint("4567")]);
+        actual.insert(
+            2,
+            &Span::new(Cow::Owned(Color::Green.normal()), Cow::Borrowed("ab")),
+        );
+        let expected = strings_to_spans(&[
+            Color::Red.paint("01"),
+            Color::Green.paint("ab"),
+            Color::Red.paint("23"),
+            Color::Blue.paint("4567"),
+        ]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn insert_at_boundary() {
+        let mut actual = strings_to_spans(&[Color::Red.paint("0123"), Color::Blue.paint("4567")]);
+        actual.insert(
+            4,
+            &Span::new(Cow::Owned(Color::Green.normal()), Cow::Borrowed("ab")),
+        );
+        let expected = strings_to_spans(&[
+            Color::Red.paint("0123"),
+            Color::Green.paint("ab"),
+            Color::Blue.paint("4567"),
+        ]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn insert_end() {
+        let mut actual = strings_to_spans(&[Color::Red.paint("0123")]);
+        actual.insert(
+            4,
+            &Span::new(Cow::Owned(Color::Green.normal()), Cow::Borrowed("ab")),
+        );
+        let expected = strings_to_spans(&[Color::Red.paint("0123"), Color::Green.paint("ab")]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn insert_style_of_later_run() {
+        let mut actual = strings_to_spans(&[Color::Red.paint("01"), Color::Blue.paint("23")]);
+        actual.insert(
+            1,
+            &Span::new(Cow::Owned(Color::Blue.normal()), Cow::Borrowed("ab")),
+        );
+        let expected = strings_to_spans(&[
+            Color::Red.paint("0"),
+            Color::Blue.paint("ab"),
+            Color::Red.paint("1"),
+            Color::Blue.paint("23"),
+        ]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn insert_spans_middle() {
+        let mut actual = strings_to_spans(&[Color::Red.paint("0123")]);
+        let other = strings_to_spans(&[Color::Green.paint("ab"), Color::Blue.paint("cd")]);
+        actual.insert_spans(1, &other);
+        let expected = strings_to_spans(&[
+            Color::Red.paint("0"),
+            Color::Green.paint("ab"),
+            Color::Blue.paint("cd"),
+            Color::Red.paint("123"),
+        ]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn insert_str_extends_previous() {
+        let mut actual = strings_to_spans(&[Color::Red.paint("0123"), Color::Blue.paint("4567")]);
+        actual.insert_str(4, "ab");
+        actual.insert_str(0, "cd");
+        let expected = strings_to_spans(&[Color::Red.paint("cd0123ab"), Color::Blue.paint("4567")]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    #[should_panic]
+    fn insert_str_not_char_boundary() {
+        let mut spans = strings_to_spans(&[Color::Red.paint("👱")]);
+        spans.insert_str(1, "a");
+    }
+    #[test]
+    fn remove_across_spans() {
+        let mut actual = strings_to_spans(&[
+            Color::Red.paint("012"),
+            Color::Blue.paint("345"),
+            Color::Green.paint("678"),
+        ]);
+        actual.remove(2..7);
+        let expected = strings_to_spans(&[Color::Red.paint("01"), Color::Green.paint("78")]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn remove_inside_span() {
+        let mut actual = strings_to_spans(&[Color::Red.paint("012"), Color::Blue.paint("345")]);
+        actual.remove(4..=4);
+        let expected = strings_to_spans(&[Color::Red.paint("012"), Color::Blue.paint("35")]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn remove_all() {
+        let mut actual = strings_to_spans(&[Color::Red.paint("012"), Color::Blue.paint("345")]);
+        actual.remove(..);
+        assert_eq!("", actual.raw());
+    }
+    #[test]
     fn split_outer() {
         let texts = vec![
             Color::Black.paint("::"),
@@ -715,4 +2476,345 @@ mod test {
         ];
         assert_eq!(expected, actual);
     }
+    #[cfg(feature = "ratatui")]
+    #[test]
+    fn convert_ratatui() {
+        use ratatui::style::{Color, Modifier, Style};
+        use ratatui::text::Line;
+        let red = Style::new().fg(Color::Red);
+        let bold = Style::new().add_modifier(Modifier::BOLD);
+        let line = Line::from(vec![
+            ratatui::text::Span::styled("foo", red),
+            ratatui::text::Span::raw("bar"),
+        ])
+        .style(bold);
+        let spans = Spans::from(&line);
+        assert_eq!(spans.raw(), "foobar");
+        let sliced = spans.slice_width(..4).unwrap();
+        let actual = Line::from(&sliced);
+        let expected = Line::from(vec![
+            ratatui::text::Span::styled("foo", bold.patch(red)),
+            ratatui::text::Span::styled("b", bold),
+        ]);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn add_and_sum() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans += &Span::borrowed(&fmt_1, "a");
+        spans += "b";
+        let other = Spans::default() + &Span::borrowed(&fmt_2, "c");
+        let joined = spans.clone() + &other;
+        assert_eq!("<1>ab</1><2>c</2>", joined.to_string());
+        let summed: Spans<Tag> = [spans, other].iter().sum();
+        assert_eq!(joined, summed);
+        let summed: Spans<Tag> = vec![Span::borrowed(&fmt_1, "x"), Span::borrowed(&fmt_1, "y")]
+            .into_iter()
+            .sum();
+        assert_eq!("<1>xy</1>", summed.to_string());
+    }
+    #[test]
+    // The cached width is the only interior mutability, and it takes no part
+    // in hashing or ordering.
+    #[allow(clippy::mutable_key_type)]
+    fn hash_and_order() {
+        use std::collections::{BTreeSet, HashSet};
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let a1 = Spans::default() + &Span::borrowed(&fmt_1, "a");
+        let a2 = Spans::default() + &Span::borrowed(&fmt_2, "a");
+        let b1 = Spans::default() + &Span::borrowed(&fmt_1, "b");
+        let set: HashSet<_> = vec![a1.clone(), a2, b1.clone(), a1.clone()]
+            .into_iter()
+            .collect();
+        assert_eq!(3, set.len());
+        let strings = [String::from("<1>"), String::from("<2>")];
+        let ordered: BTreeSet<Spans<String>> = [("b", 0), ("a", 1), ("a", 0)]
+            .iter()
+            .map(|(s, i)| Spans::default() + &Span::borrowed(&strings[*i], s))
+            .collect();
+        let raw: Vec<_> = ordered.iter().map(|s| s.raw()).collect();
+        assert_eq!(vec!["a", "a", "b"], raw);
+        assert!(ordered
+            .iter()
+            .next()
+            .unwrap()
+            .spans()
+            .any(|s| s.style().as_str() == "<1>"));
+        assert_eq!(Some(Ordering::Less), a1.partial_cmp(&b1),);
+    }
+    #[test]
+    fn span_count_matches_spans() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans.push(&"plain");
+        assert_eq!(None, spans.style_at(0));
+        spans.push(&Span::borrowed(&fmt_1, "a"));
+        spans.push(&Span::borrowed(&fmt_2, "b"));
+        spans.push(&Span::borrowed(&fmt_2, "c"));
+        assert_eq!(3, spans.span_count());
+        assert_eq!(spans.spans().count(), spans.span_count());
+        assert_eq!(Some(&fmt_2), spans.style_at(7));
+        let sliced = spans.slice(5..6).unwrap();
+        assert_eq!(1, sliced.span_count());
+        assert_eq!(sliced.spans().count(), sliced.span_count());
+        assert_eq!(Some(&fmt_1), sliced.style_at(0));
+    }
+    #[test]
+    fn build_fluently() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let built = Spans::styled(fmt_1.clone(), "a")
+            .then(fmt_1.clone(), "b")
+            .then(fmt_2.clone(), "")
+            .then(fmt_2.clone(), "c");
+        let mut pushed: Spans<Tag> = Default::default();
+        pushed.push(&Span::borrowed(&fmt_1, "ab"));
+        pushed.push(&Span::borrowed(&fmt_2, "c"));
+        assert_eq!(pushed, built);
+        assert_eq!(2, built.span_count());
+    }
+    #[test]
+    fn split_by_style_round_trip() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans.push(&"plain");
+        spans.push(&Span::borrowed(&fmt_1, "a"));
+        spans.push(&Span::borrowed(&fmt_2, "bc"));
+        let parts: Vec<Spans<Tag>> = spans.split_by_style().collect();
+        assert_eq!(3, parts.len());
+        assert!(parts.iter().all(|part| part.span_count() == 1));
+        assert_eq!("<2>bc</2>", parts[2].to_string());
+        let joined: Spans<Tag> = parts.into_iter().sum();
+        assert_eq!(spans, joined);
+        assert_eq!(0, Spans::<Tag>::default().split_by_style().count());
+    }
+    #[test]
+    fn lines_keep_styles() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = Spans::styled(fmt_1, "a\n\nb").then(fmt_2, "c\n");
+        let lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+        assert_eq!(vec!["<1>a</1>", "", "<1>b</1><2>c</2>"], lines);
+        assert_eq!(3, text.height());
+        assert_eq!(0, Spans::<Tag>::default().height());
+        assert_eq!(0, Spans::<Tag>::default().lines().count());
+    }
+    #[test]
+    fn expand_tabs_columns() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = Spans::styled(fmt_1, "🙈\tx\n\t").then(fmt_2, "\ty");
+        assert_eq!(
+            "<1>🙈  x\n    </1><2>    y</2>",
+            text.expand_tabs(4).to_string()
+        );
+        assert_eq!("<1>🙈x\n</1><2>y</2>", text.expand_tabs(0).to_string());
+    }
+    #[test]
+    fn reverse_twice() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = Spans::styled(fmt_1.clone(), "a🙈").then(fmt_2.clone(), "bc");
+        let reversed = text.reverse();
+        assert_eq!("<2>cb</2><1>🙈a</1>", reversed.to_string());
+        assert_eq!(Some(&fmt_2), reversed.style_at(1));
+        assert_eq!(text, reversed.reverse());
+        assert_eq!(Spans::<Tag>::default(), Spans::default().reverse());
+    }
+    #[test]
+    fn squeeze_across_spans() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = Spans::styled(fmt_1.clone(), "  a\t").then(fmt_2.clone(), " \nb");
+        let squeezed = text.squeeze_whitespace();
+        assert_eq!("<1> a </1><2>b</2>", squeezed.to_string());
+        assert_eq!(2, squeezed.span_count());
+        let spaced = Spans::styled(fmt_1, "a ").then(fmt_2, "  ");
+        assert_eq!("<1>a </1>", spaced.squeeze_whitespace().to_string());
+        assert_eq!("a", spaced.collapse(char::is_whitespace, "").raw());
+    }
+    #[test]
+    fn index_conversions_round_trip() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let text = Spans::styled(fmt_1, "é🙈").then(Tag::default(), "e\u{301}x");
+        for (column, byte) in [(0, 0), (1, 2), (3, 6), (4, 9), (5, 10)].iter().copied() {
+            assert_eq!(byte, text.width_to_byte(column, Rounding::Floor));
+            assert_eq!(column, text.byte_to_width(byte, Rounding::Ceil));
+        }
+        assert_eq!(2, text.width_to_byte(2, Rounding::Floor));
+        assert_eq!(6, text.width_to_byte(2, Rounding::Ceil));
+        assert_eq!(3, text.byte_to_width(7, Rounding::Floor));
+        assert_eq!(4, text.byte_to_width(7, Rounding::Ceil));
+        assert_eq!(10, text.width_to_byte(99, Rounding::Ceil));
+        assert_eq!(5, text.byte_to_char(99, Rounding::Floor));
+        assert_eq!(6, text.char_to_byte(2));
+        assert_eq!(2, text.byte_to_char(6, Rounding::Floor));
+    }
+    #[test]
+    fn gradient_restyles_pieces() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = Spans::styled(fmt_1, "a🙈b").then(fmt_2, "cd");
+        let mut positions = vec![];
+        let actual = text.gradient(2, |_, position| {
+            positions.push(position);
+            let level = (position * 10.0).round();
+            Tag::new(&format!("<{}>", level), &format!("</{}>", level))
+        });
+        assert_eq!(vec![0.0, 0.4, 0.8], positions);
+        assert_eq!("<0>a🙈</0><4>b</4><8>cd</8>", actual.to_string());
+        assert_eq!(text, text.gradient(1, |style, _| style.clone()));
+    }
+    #[test]
+    fn stripe_counts_empty_segments() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_d = Tag::new("<d>", "</d>");
+        let text = Spans::styled(fmt_1, "a,").then(fmt_d, ",b,c");
+        let actual = text.stripe_with(",", |segment, _| {
+            Tag::new(&format!("<{}>", segment), &format!("</{}>", segment))
+        });
+        assert_eq!(
+            "<0>a</0><1>,</1><d>,</d><2>b</2><d>,</d><3>c</3>",
+            actual.to_string()
+        );
+        let one = Tag::new("<x>", "</x>");
+        assert_eq!(
+            "<x>a</x><1>,</1><d>,</d><x>b</x><d>,</d><x>c</x>",
+            text.stripe(",", &[one]).to_string()
+        );
+    }
+    #[test]
+    fn join_all_separates_items() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let sep = Spans::styled(Tag::new("<s>", "</s>"), ",");
+        let none: [Spans<Tag>; 0] = [];
+        assert_eq!(Spans::default(), Spans::join_all(&none, &sep));
+        let one = [Spans::styled(fmt_1.clone(), "a")];
+        assert_eq!("<1>a</1>", Spans::join_all(&one, &sep).to_string());
+        let items = vec![Spans::styled(fmt_1, "a"), Spans::from(""), Spans::from("b")];
+        assert_eq!(
+            "<1>a</1><s>,,</s>b",
+            Spans::join_all(items, &sep).to_string()
+        );
+    }
+    #[test]
+    fn join_and_push_combinations() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let text = Spans::styled(fmt_1.clone(), "a");
+        assert_eq!("<1>ab</1>", text.join(&"b").to_string());
+        let span = Span::borrowed(&fmt_1, "x");
+        assert_eq!("<1>xa</1>", span.join(&text).to_string());
+        let plain: Span<Tag> = Span::from("c");
+        assert_eq!("<1>a</1>c", text.join(&plain).to_string());
+        let mut raw = String::from(">");
+        Pushable::push(&mut raw, &text);
+        Pushable::push(&mut raw, &span);
+        assert_eq!(">ax", raw);
+        let owned: Spans<Tag> = Spans::from(raw);
+        assert_eq!(Spans::from(">ax"), owned);
+    }
+    #[test]
+    fn pairs_round_trip() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let mut text = Spans::styled(fmt_1.clone(), "a").then(fmt_1.clone(), "");
+        text.push_styled(Tag::default(), "b");
+        text.insert_str(0, "🙈");
+        let pairs: Vec<(Tag, String)> = Vec::from(&text);
+        assert_eq!(
+            vec![
+                (fmt_1, String::from("🙈a")),
+                (Tag::default(), String::from("b"))
+            ],
+            pairs
+        );
+        assert_eq!(text, Spans::from(pairs));
+        assert!(Vec::<(Tag, String)>::from(Spans::<Tag>::default()).is_empty());
+    }
+    #[test]
+    fn strip_renders_plain_text() {
+        let bold = Tag::new("<b>", "</b>");
+        let text = Spans::styled(bold, "foo").then(Tag::default(), "\tbar");
+        let plain = text.strip().expand_tabs(4);
+        assert_eq!(plain.to_string(), "foo bar");
+        assert_eq!(plain.raw(), text.expand_tabs(4).unstyled());
+        assert_eq!(plain.span_count(), 1);
+    }
+    #[test]
+    fn map_style_merges_runs() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = Spans::styled(1, "a").then(2, "b").then(3, "c").then(1, "d");
+        let mapped = text.map_style(|n| {
+            if n % 2 == 1 {
+                fmt_1.clone()
+            } else {
+                fmt_2.clone()
+            }
+        });
+        assert_eq!(mapped.to_string(), "<1>a</1><2>b</2><1>cd</1>");
+        assert_eq!(mapped.span_count(), 3);
+        let span = Span::borrowed(&2, "x").map_style(|n| n * 10);
+        assert_eq!(**span.style(), 20);
+        assert_eq!(span.raw_ref(), "x");
+    }
+    #[test]
+    fn map_style_implicit_default() {
+        let bold = Tag::new("<b>", "</b>");
+        let mut text: Spans<Tag> = Default::default();
+        text.push(&"abc");
+        let mapped = text.map_style(|_| bold.clone());
+        assert_eq!(mapped.to_string(), "<b>abc</b>");
+        assert_eq!(Spans::<Tag>::default().map_style(|_| 1).span_count(), 0);
+    }
+    #[test]
+    fn split_at_width_keeps_wide_graphemes() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let text = Spans::styled(fmt_1, "a🙈").then(Tag::default(), "bc");
+        let (head, tail) = text.split_at_width(2);
+        assert_eq!(head.unwrap().to_string(), "<1>a</1>");
+        assert_eq!(tail.unwrap().to_string(), "<1>🙈</1>bc");
+        assert_eq!(text.take_width(3), text.slice_width(..3));
+        assert_eq!(text.skip_width(5), None);
+    }
+    #[test]
+    fn chunks_width_cover_text() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let text = Spans::styled(fmt_1, "a\u{301}bc").then(Tag::default(), "🙈d");
+        let chunks: Vec<Spans<Tag>> = text.chunks_width(3).collect();
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|chunk| chunk.bounded_width() <= 3));
+        assert_eq!(chunks.iter().sum::<Spans<Tag>>(), text);
+        assert_eq!(Spans::<Tag>::default().chunks_width(1).count(), 0);
+    }
+    #[test]
+    fn push_slice_matches_slice_and_push() {
+        let bold = Tag::new("<b>", "</b>");
+        let italic = Tag::new("<i>", "</i>");
+        let mut text: Spans<Tag> = Spans::from("ab");
+        text.push(&Span::borrowed(&bold, "cd"));
+        text.push(&Span::borrowed(&italic, "ef🙈"));
+        let mut start: Spans<Tag> = Default::default();
+        start.push(&Span::borrowed(&bold, "> "));
+        for range in [0..2, 1..4, 3..6, 4..10] {
+            let mut expected = start.clone();
+            expected.push(&text.slice(range.clone()).unwrap());
+            let mut actual = start.clone();
+            assert!(actual.push_slice(&text, range));
+            assert_eq!(actual, expected);
+        }
+        let mut actual = start.clone();
+        assert!(!actual.push_slice(&text, 7..9));
+        assert_eq!(actual, start);
+        let mut expected = start.clone();
+        expected.push(&text.slice_width(3..8).unwrap());
+        assert_eq!(text.push_width(3..8, &mut actual), Some(5));
+        assert_eq!(actual, expected);
+        assert_eq!(text.width_within(3..8), 5);
+        assert_eq!(text.width_within(7..), 0);
+    }
 }
@@ -1,28 +1,58 @@
 use super::Sliceable;
+use crate::text::bounds;
 /// Contains a data structure to allow fast lookup of the value to the left.
 use std::borrow::Borrow;
-use std::collections::{
-    btree_map::{Iter, Range},
-    BTreeMap,
-};
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 use std::ops::{Add, Bound, RangeBounds};
+use std::sync::Arc;
 /// Data structure to quickly look up the nearest value smaller than a given value.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// Values are kept behind an [`Arc`], so copying values between trees, as
+/// cloning, slicing and pushing styled text does, shares them instead of
+/// cloning them.
+#[derive(Debug)]
 pub struct SearchTree<V> {
-    tree: BTreeMap<usize, V>,
+    tree: BTreeMap<usize, Arc<V>>,
 }
 
 impl<V> Default for SearchTree<V> {
     fn default() -> Self {
         Self {
-            tree: BTreeMap::<usize, V>::new(),
+            tree: BTreeMap::<usize, Arc<V>>::new(),
         }
     }
 }
 
+impl<V> Clone for SearchTree<V> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree.clone(),
+        }
+    }
+}
+
+impl<V: PartialEq> PartialEq for SearchTree<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tree.len() == other.tree.len()
+            && self.tree.iter().zip(other.tree.iter()).all(
+                |((key, val), (other_key, other_val))| {
+                    key == other_key && same_value(val, other_val)
+                },
+            )
+    }
+}
+
+impl<V: Eq> Eq for SearchTree<V> {}
+
+/// Whether two shared values are equal, without comparing them if they are
+/// the same value.
+fn same_value<V: PartialEq>(first: &Arc<V>, second: &Arc<V>) -> bool {
+    Arc::ptr_eq(first, second) || first == second
+}
+
 impl<V> SearchTree<V> {
     pub fn new() -> SearchTree<V> {
         SearchTree {
@@ -32,33 +62,27 @@ impl<V> SearchTree<V> {
     pub fn contains_key(&self, key: usize) -> bool {
         self.tree.contains_key(&key)
     }
-    pub fn range<T, R>(&self, range: R) -> Range<'_, usize, V>
-    where
-        T: Ord + ?Sized,
-        R: RangeBounds<T>,
-        usize: Borrow<T> + Ord,
-    {
-        self.tree.range(range)
+    pub fn insert(&mut self, key: usize, value: V) -> Option<Arc<V>> {
+        self.insert_shared(key, Arc::new(value))
     }
-    pub fn insert(&mut self, key: usize, value: V) -> Option<V> {
+    /// Inserts a value shared with other trees.
+    pub fn insert_shared(&mut self, key: usize, value: Arc<V>) -> Option<Arc<V>> {
         self.tree.insert(key, value)
     }
-    pub fn iter(&self) -> Iter<usize, V> {
-        self.tree.iter()
+    pub fn clear(&mut self) {
+        self.tree.clear();
+    }
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&usize, &V)> + Clone {
+        self.tree.iter().map(|(key, val)| (key, val.as_ref()))
     }
     #[allow(dead_code)]
     pub fn keys(&self) -> Vec<usize> {
         self.tree.keys().cloned().collect()
     }
+    /// Drops keys greater than `max_key`.
     pub fn trim(&mut self, max_key: usize) {
-        let drop_keys: Vec<_> = self
-            .tree
-            .iter()
-            .filter_map(|(key, _val)| if key > &max_key { Some(key) } else { None })
-            .cloned()
-            .collect();
-        for key in drop_keys {
-            self.tree.remove(&key);
+        if let Some(first_dropped) = max_key.checked_add(1) {
+            self.tree.split_off(&first_dropped);
         }
     }
     /// Drops keys that have the same value as the previous keys
@@ -66,12 +90,25 @@ impl<V> SearchTree<V> {
     where
         V: PartialEq,
     {
+        self.dedup_from(0);
+    }
+    /// Drops keys from `from` on that have the same value as the previous
+    /// keys, leaving the keys before `from` unchecked.
+    pub fn dedup_from(&mut self, from: usize)
+    where
+        V: PartialEq,
+    {
+        let start = self
+            .tree
+            .range(..from)
+            .next_back()
+            .map_or(from, |(key, _)| *key);
         let drop_keys: Vec<_> = self
             .tree
-            .iter()
-            .zip(self.tree.iter().skip(1))
+            .range(start..)
+            .zip(self.tree.range(start..).skip(1))
             .filter_map(|((_first_key, first_val), (second_key, second_val))| {
-                if first_val == second_val {
+                if same_value(first_val, second_val) {
                     Some(second_key)
                 } else {
                     None
@@ -86,6 +123,52 @@ impl<V> SearchTree<V> {
     pub fn is_empty(&self) -> bool {
         self.tree.is_empty()
     }
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+    /// Returns the value at the largest key less than or equal to `key`.
+    pub fn search_left(&self, key: usize) -> Option<&V> {
+        self.search_left_shared(key).map(Arc::as_ref)
+    }
+    /// Returns the shared value at the largest key less than or equal to
+    /// `key`.
+    pub fn search_left_shared(&self, key: usize) -> Option<&Arc<V>> {
+        self.tree.range(..=key).next_back().map(|(_key, val)| val)
+    }
+    /// Moves every key greater than or equal to `from` right by `amount`.
+    pub fn shift_right(&mut self, from: usize, amount: usize) {
+        let shifted = self.tree.split_off(&from);
+        self.tree
+            .extend(shifted.into_iter().map(|(key, val)| (key + amount, val)));
+    }
+    /// Moves every key greater than or equal to `from` left by `amount`.
+    /// Keys in the overwritten range `from - amount..from` are dropped.
+    pub fn shift_left(&mut self, from: usize, amount: usize) {
+        let shifted = self.tree.split_off(&from);
+        let floor = from.saturating_sub(amount);
+        self.tree.split_off(&floor);
+        self.tree.extend(
+            shifted
+                .into_iter()
+                .map(|(key, val)| (key.saturating_sub(amount), val)),
+        );
+    }
+    /// Copies the values of another tree as [`Sliceable::slice`] would slice
+    /// it to a range of keys, placing the start of the range at a key of this
+    /// tree.
+    pub fn copy_slice(&mut self, from: &SearchTree<V>, start: usize, end: Option<usize>, at: usize)
+    where
+        V: PartialEq,
+    {
+        if let Some(val) = from.search_left_shared(start) {
+            self.insert_shared(at, Arc::clone(val));
+        }
+        let end = end.map_or(Bound::Unbounded, Bound::Excluded);
+        for (key, val) in from.tree.range((Bound::Excluded(start), end)) {
+            self.insert_shared(*key - start + at, Arc::clone(val));
+        }
+        self.dedup_from(at);
+    }
     /// Copy values in a range from another tree into this tree,
     /// shifting the keys by some amount.
     pub fn copy_with_shift<T, R, S>(
@@ -95,26 +178,27 @@ impl<V> SearchTree<V> {
         shift: S,
     ) -> Result<(), Box<dyn Error>>
     where
-        V: Clone + PartialEq,
+        V: PartialEq,
         T: Ord + ?Sized,
         R: RangeBounds<T>,
         usize: Borrow<T> + Ord + TryFrom<S> + Copy,
         S: Add<Output = S> + TryFrom<usize> + Copy,
     {
-        let contained_spans = from.range(range);
+        let contained_spans = from.tree.range(range);
+        let mut first_key = None;
         for (key, value) in contained_spans {
             // S is a (possibly signed) value
             if let Ok(add_key) = S::try_from(*key) {
-                if let Ok(new_key) = usize::try_from(add_key + shift) {
-                    self.insert(new_key, value.clone());
-                } else {
-                    self.insert(*key, value.clone());
-                }
+                let new_key = usize::try_from(add_key + shift).unwrap_or(*key);
+                first_key.get_or_insert(new_key);
+                self.insert_shared(new_key, Arc::clone(value));
             } else {
                 return Err(Box::new(ShiftError));
             }
         }
-        self.dedup();
+        if let Some(first_key) = first_key {
+            self.dedup_from(first_key);
+        }
         Ok(())
     }
 }
@@ -128,49 +212,26 @@ impl fmt::Display for ShiftError {
 }
 impl Error for ShiftError {}
 
-impl<V> Sliceable for SearchTree<V>
-where
-    V: Clone,
-{
+impl<V> Sliceable for SearchTree<V> {
     fn slice<R>(&self, range: R) -> Option<Self>
     where
         R: std::ops::RangeBounds<usize> + Clone,
     {
-        if let Some((zero_key, zero_val)) = self.tree.iter().next() {
-            let mut tree: BTreeMap<_, _> = Default::default();
-            let (new_zero_key, new_zero_val) = match range.start_bound() {
-                Bound::Excluded(x) => {
-                    if let Some((_k, v)) = self
-                        .tree
-                        .range((Bound::Unbounded, Bound::Included(x)))
-                        .last()
-                    {
-                        (*x, v)
-                    } else {
-                        (*zero_key, zero_val)
-                    }
-                }
-                Bound::Included(x) => {
-                    if let Some((_k, v)) = self
-                        .tree
-                        .range((Bound::Unbounded, Bound::Excluded(x)))
-                        .last()
-                    {
-                        (*x, v)
-                    } else {
-                        (*zero_key, zero_val)
-                    }
-                }
-                Bound::Unbounded => (*zero_key, zero_val),
-            };
-            tree.insert(*zero_key, new_zero_val.clone());
-            for (key, val) in self.tree.range(range) {
-                tree.insert(*key - new_zero_key, val.clone());
-            }
-            Some(SearchTree { tree })
-        } else {
-            None
+        if self.tree.is_empty() {
+            return None;
         }
+        let (start, end) = bounds::normalize(&range);
+        let mut tree: BTreeMap<_, _> = Default::default();
+        // If the range starts before the first key, the unstyled text before it
+        // stays unstyled and the first key moves left with the rest.
+        if let Some(val) = self.search_left_shared(start) {
+            tree.insert(0, Arc::clone(val));
+        }
+        let end = end.map_or(Bound::Unbounded, Bound::Excluded);
+        for (key, val) in self.tree.range((Bound::Included(start), end)) {
+            tree.insert(*key - start, Arc::clone(val));
+        }
+        Some(SearchTree { tree })
     }
 }
 
@@ -213,6 +274,43 @@ mod test {
         assert_eq!(expected, actual);
     }
     #[test]
+    fn search_left() {
+        let mut tree: SearchTree<usize> = Default::default();
+        tree.insert(2, 2);
+        tree.insert(4, 5);
+        assert_eq!(None, tree.search_left(1));
+        assert_eq!(Some(&2), tree.search_left(2));
+        assert_eq!(Some(&2), tree.search_left(3));
+        assert_eq!(Some(&5), tree.search_left(100));
+    }
+    #[test]
+    fn shift_right() {
+        let mut actual: SearchTree<usize> = Default::default();
+        actual.insert(0, 1);
+        actual.insert(2, 2);
+        actual.insert(4, 5);
+        actual.shift_right(2, 3);
+        let mut expected: SearchTree<usize> = Default::default();
+        expected.insert(0, 1);
+        expected.insert(5, 2);
+        expected.insert(7, 5);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn shift_left() {
+        let mut actual: SearchTree<usize> = Default::default();
+        actual.insert(0, 1);
+        actual.insert(2, 2);
+        actual.insert(4, 5);
+        actual.insert(6, 7);
+        actual.shift_left(4, 3);
+        let mut expected: SearchTree<usize> = Default::default();
+        expected.insert(0, 1);
+        expected.insert(1, 5);
+        expected.insert(3, 7);
+        assert_eq!(expected, actual);
+    }
+    #[test]
     fn slice_to_zero() {
         let mut tree: SearchTree<usize> = Default::default();
         tree.insert(2, 2);
@@ -249,6 +347,42 @@ mod test {
         assert_eq!(expected, actual);
     }
     #[test]
+    fn slice_before_first_key() {
+        let mut tree: SearchTree<usize> = Default::default();
+        tree.insert(2, 2);
+        tree.insert(4, 5);
+        let actual = tree.slice(1..).unwrap();
+        let mut expected: SearchTree<usize> = Default::default();
+        expected.insert(1, 2);
+        expected.insert(3, 5);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn slice_after_first_key() {
+        let mut tree: SearchTree<usize> = Default::default();
+        tree.insert(2, 2);
+        tree.insert(4, 5);
+        let actual = tree.slice(3..).unwrap();
+        let mut expected: SearchTree<usize> = Default::default();
+        expected.insert(0, 2);
+        expected.insert(1, 5);
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn slice_excluded_start() {
+        let mut tree: SearchTree<usize> = Default::default();
+        tree.insert(0, 1);
+        tree.insert(2, 2);
+        tree.insert(4, 5);
+        let actual = tree
+            .slice((Bound::Excluded(1), Bound::Included(usize::MAX)))
+            .unwrap();
+        let mut expected: SearchTree<usize> = Default::default();
+        expected.insert(0, 2);
+        expected.insert(2, 5);
+        assert_eq!(expected, actual);
+    }
+    #[test]
     fn slice_unbounded_start() {
         let mut tree: SearchTree<usize> = Default::default();
         tree.insert(0, 1);
@@ -271,4 +405,19 @@ mod test {
         expected.insert(2, 2);
         assert_eq!(expected, actual);
     }
+    #[test]
+    fn slices_share_values() {
+        let mut tree: SearchTree<String> = Default::default();
+        tree.insert(0, String::from("a"));
+        tree.insert(2, String::from("b"));
+        let slice = tree.slice(1..).unwrap();
+        let mut copy: SearchTree<String> = Default::default();
+        copy.copy_with_shift(&slice, .., 3).unwrap();
+        for (key, from) in [(0, 3), (2, 4)].iter() {
+            assert!(Arc::ptr_eq(
+                tree.search_left_shared(*key).unwrap(),
+                copy.search_left_shared(*from).unwrap()
+            ));
+        }
+    }
 }
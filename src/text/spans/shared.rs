@@ -0,0 +1,266 @@
+use super::{Span, Spans};
+use crate::text::{BoundedWidth, HasWidth, Paintable, Pushable, RawText, Sliceable, Width};
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::{Range, RangeBounds};
+use std::sync::Arc;
+
+/// Styled text that shares its storage between clones and slices.
+///
+/// Cloning or slicing a [`Spans`] copies its text. A `SharedSpans` keeps
+/// its text behind an [`Arc`] along with the byte range it shows, so cloning
+/// it and slicing it are O(1) and every slice shares the buffer of the
+/// document it came from. It can be sent between threads when its style can.
+///
+/// The text is copied only when it is changed while shared, through
+/// [`SharedSpans::make_mut`].
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{Pushable, RawText, SharedSpans, Sliceable, Spans, Tag};
+/// let bold = Tag::new("<b>", "</b>");
+/// let document = SharedSpans::new(Spans::styled(bold, "hello").then(Tag::default(), " world"));
+/// let word = document.slice(3..8).unwrap();
+/// assert_eq!(word.to_string(), "<b>lo</b> wo");
+/// // The slice points into the same buffer as the document.
+/// assert_eq!(word.raw_ref().as_ptr(), document.raw_ref()[3..].as_ptr());
+/// let mut changed = word.clone();
+/// changed.make_mut().push(&"!");
+/// assert_eq!(changed.to_string(), "<b>lo</b> wo!");
+/// assert_eq!(word.to_string(), "<b>lo</b> wo");
+/// ```
+#[derive(Debug)]
+pub struct SharedSpans<T> {
+    inner: Arc<Spans<T>>,
+    /// Byte range of the shared text that this object shows, or [`None`] if
+    /// it shows all of it
+    range: Option<Range<usize>>,
+}
+
+impl<T> SharedSpans<T> {
+    /// Moves text into shared storage.
+    pub fn new(spans: Spans<T>) -> Self {
+        SharedSpans {
+            inner: Arc::new(spans),
+            range: None,
+        }
+    }
+    /// Whether this object shows all of its shared text.
+    fn is_whole(&self) -> bool {
+        self.range.is_none()
+    }
+    /// Returns the byte range of the shared text that this object shows.
+    fn range(&self) -> Range<usize> {
+        self.range.clone().unwrap_or(0..self.inner.content.len())
+    }
+    /// Returns the style and text of each run within the range of this
+    /// object.
+    fn runs(&self) -> impl Iterator<Item = (Cow<'_, T>, &str)>
+    where
+        T: Clone + Default,
+    {
+        let Range { start, end } = self.range();
+        self.inner
+            .runs()
+            .scan(0, |offset, (style, run)| {
+                let run_start = *offset;
+                *offset += run.len();
+                Some((run_start, style, run))
+            })
+            .filter_map(move |(run_start, style, run)| {
+                let from = start.max(run_start) - run_start;
+                let to = end.min(run_start + run.len()).checked_sub(run_start)?;
+                run.get(from..to)
+                    .filter(|run| !run.is_empty())
+                    .map(|run| (style, run))
+            })
+    }
+    /// Returns the spans of text shown by this object.
+    pub fn spans(&self) -> impl Iterator<Item = Span<'_, T>>
+    where
+        T: Clone + Default,
+    {
+        self.runs()
+            .map(|(style, s)| Span::new(style, Cow::Borrowed(s)))
+    }
+    /// Copies the text shown by this object out of shared storage.
+    pub fn to_spans(&self) -> Spans<T>
+    where
+        T: Clone,
+    {
+        if self.is_whole() {
+            self.inner.as_ref().clone()
+        } else {
+            self.inner.slice(self.range()).unwrap_or_default()
+        }
+    }
+    /// Takes the text shown by this object out of shared storage, copying it
+    /// only if it is shared or only partly shown.
+    pub fn into_spans(self) -> Spans<T>
+    where
+        T: Clone,
+    {
+        if self.is_whole() {
+            Arc::try_unwrap(self.inner).unwrap_or_else(|inner| inner.as_ref().clone())
+        } else {
+            self.to_spans()
+        }
+    }
+    /// Gives mutable access to the text, first copying it into storage of
+    /// its own if it is shared with other objects or only partly shown.
+    pub fn make_mut(&mut self) -> &mut Spans<T>
+    where
+        T: Clone,
+    {
+        if !self.is_whole() {
+            self.inner = Arc::new(self.to_spans());
+            self.range = None;
+        }
+        Arc::make_mut(&mut self.inner)
+    }
+}
+
+impl<T> Clone for SharedSpans<T> {
+    fn clone(&self) -> Self {
+        SharedSpans {
+            inner: Arc::clone(&self.inner),
+            range: self.range.clone(),
+        }
+    }
+}
+
+impl<T> Default for SharedSpans<T> {
+    fn default() -> Self {
+        SharedSpans::new(Default::default())
+    }
+}
+
+impl<T> From<Spans<T>> for SharedSpans<T> {
+    fn from(spans: Spans<T>) -> Self {
+        SharedSpans::new(spans)
+    }
+}
+
+impl<T: Clone> From<SharedSpans<T>> for Spans<T> {
+    fn from(shared: SharedSpans<T>) -> Self {
+        shared.into_spans()
+    }
+}
+
+impl<T: Clone + Default + PartialEq> PartialEq for SharedSpans<T> {
+    /// Compares the text shown by each object and its styles.
+    fn eq(&self, other: &SharedSpans<T>) -> bool {
+        self.raw_ref() == other.raw_ref() && self.runs().eq(other.runs())
+    }
+}
+
+impl<T> RawText for SharedSpans<T> {
+    fn raw(&self) -> String {
+        self.raw_ref().to_string()
+    }
+    fn raw_ref(&self) -> &str {
+        &self.inner.content[self.range()]
+    }
+}
+
+impl<T> Sliceable for SharedSpans<T> {
+    /// Slices the text without copying it. The range is relative to the
+    /// text shown by this object.
+    fn slice<R>(&self, range: R) -> Option<Self>
+    where
+        R: RangeBounds<usize> + Clone,
+    {
+        let (start, end) = crate::text::bounds::normalize(&range);
+        let shown = self.range();
+        let end = end.unwrap_or(shown.len());
+        self.raw_ref().get(start..end)?;
+        Some(SharedSpans {
+            inner: Arc::clone(&self.inner),
+            range: Some(shown.start + start..shown.start + end),
+        })
+    }
+}
+
+impl<T> BoundedWidth for SharedSpans<T> {
+    /// Returns the width of the text, which is cached for the whole shared
+    /// text under the default [`crate::text::WidthPolicy`].
+    fn bounded_width(&self) -> usize {
+        if self.is_whole() {
+            self.inner.bounded_width()
+        } else {
+            self.raw_ref().bounded_width()
+        }
+    }
+}
+
+impl<T> HasWidth for SharedSpans<T> {
+    fn width(&self) -> Width {
+        Width::Bounded(self.bounded_width())
+    }
+}
+
+impl<T: Paintable + Clone + Default> fmt::Display for SharedSpans<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let groups = self.spans().map(Span::into_parts);
+        if fmt.width().is_some() || fmt.precision().is_some() {
+            // Padding applies to the painted output as a whole.
+            T::paint_many(groups).fmt(fmt)
+        } else {
+            T::paint_many_fmt(groups, fmt)
+        }
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Pushable<SharedSpans<T>> for Spans<T> {
+    fn push(&mut self, other: &SharedSpans<T>) {
+        for span in other.spans() {
+            self.push(&span);
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> Pushable<Spans<T>> for SharedSpans<T> {
+    fn push(&mut self, other: &Spans<T>) {
+        self.make_mut().push(other);
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Pushable<SharedSpans<T>> for SharedSpans<T> {
+    fn push(&mut self, other: &SharedSpans<T>) {
+        self.make_mut().push(other);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Tag, WidthSliceable};
+    #[test]
+    fn shared_slices_match_slices() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = Spans::styled(fmt_1, "a🙈b").then(fmt_2, "🙉cd");
+        let shared = SharedSpans::new(text.clone());
+        for start in 0..9 {
+            for end in start..10 {
+                let sliced = text.slice_width(start..end).unwrap_or_default();
+                let shared_slice = shared.slice_width(start..end).unwrap_or_default();
+                assert_eq!(sliced.to_string(), shared_slice.to_string());
+                assert_eq!(sliced, shared_slice.to_spans());
+                assert_eq!(sliced.bounded_width(), shared_slice.bounded_width());
+            }
+        }
+    }
+    #[test]
+    fn make_mut_copies_on_write() {
+        let text: Spans<Tag> = Spans::from("abc");
+        let mut shared = SharedSpans::new(text);
+        shared.make_mut().push(&"d");
+        let copy = shared.clone();
+        assert_eq!(copy.raw_ref().as_ptr(), shared.raw_ref().as_ptr());
+        shared.make_mut().push(&"e");
+        assert_eq!(copy.raw_ref(), "abcd");
+        assert_eq!(shared.raw_ref(), "abcde");
+        assert_eq!(shared.into_spans(), Spans::from("abcde"));
+    }
+}
@@ -1,15 +1,19 @@
+#[cfg(feature = "regex")]
+use super::Expandable;
 use super::{
-    BoundedWidth, Expandable, HasWidth, Joinable, Paintable, Pushable, RawText, Sliceable, Spans,
-    Width,
+    BoundedWidth, HasWidth, Joinable, Paintable, Pushable, RawText, Sliceable, Spans, Width,
+    WidthPolicy, WidthSliceable,
 };
-#[cfg(test)]
+#[cfg(any(test, feature = "ansi"))]
 use ansi_term::{ANSIString, Style};
+#[cfg(feature = "regex")]
 use regex::Captures;
 use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::fmt;
-use std::ops::Deref;
+use std::hash::{Hash, Hasher};
 use std::ops::RangeBounds;
-use unicode_width::UnicodeWidthStr;
+use std::ops::{Add, Deref};
 
 /// A span of text having a single style.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -31,26 +35,83 @@ impl<'a, T: Clone> Span<'a, T> {
             content: Cow::Borrowed(content),
         }
     }
+    pub(crate) fn into_parts(self) -> (Cow<'a, T>, Cow<'a, str>) {
+        (self.style, self.content)
+    }
+    /// Converts into a span that owns its style and content.
+    pub fn into_owned(self) -> Span<'static, T>
+    where
+        T: 'static,
+    {
+        Span {
+            style: Cow::Owned(self.style.into_owned()),
+            content: Cow::Owned(self.content.into_owned()),
+        }
+    }
+    /// Converts the style of the span, keeping its content.
+    pub fn map_style<U, F>(&self, f: F) -> Span<'a, U>
+    where
+        U: Clone,
+        F: FnOnce(&T) -> U,
+    {
+        Span {
+            style: Cow::Owned(f(&self.style)),
+            content: self.content.clone(),
+        }
+    }
+}
+impl<T: Clone + Eq> Eq for Span<'_, T> {}
+
+impl<T: Clone + Hash> Hash for Span<'_, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.content.hash(state);
+        self.style.hash(state);
+    }
+}
+
+impl<T: Clone + PartialOrd> PartialOrd for Span<'_, T> {
+    /// Orders by raw content, then by style.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.content.cmp(&other.content) {
+            Ordering::Equal => self.style.partial_cmp(&other.style),
+            ordering => Some(ordering),
+        }
+    }
 }
+
+impl<T: Clone + Ord> Ord for Span<'_, T> {
+    /// Orders by raw content, then by style.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.content
+            .cmp(&other.content)
+            .then_with(|| self.style.cmp(&other.style))
+    }
+}
+
 impl<'a, T: Paintable + Clone> fmt::Display for Span<'a, T> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        self.style.paint(self.content.as_ref()).fmt(fmt)
+        if fmt.width().is_some() || fmt.precision().is_some() {
+            // Padding applies to the painted output as a whole.
+            self.style.paint(self.content.as_ref()).fmt(fmt)
+        } else {
+            self.style.paint_fmt(self.content.as_ref(), fmt)
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(any(test, feature = "ansi"))]
 impl<'a> From<&Span<'a, Style>> for ANSIString<'a> {
     fn from(span: &Span<'a, Style>) -> ANSIString<'a> {
         span.style.paint(span.content.clone())
     }
 }
-#[cfg(test)]
+#[cfg(any(test, feature = "ansi"))]
 impl<'a> From<Span<'a, Style>> for ANSIString<'a> {
     fn from(span: Span<'a, Style>) -> ANSIString<'a> {
         span.style.paint(span.content)
     }
 }
-#[cfg(test)]
+#[cfg(any(test, feature = "ansi"))]
 impl<'a> From<&'a ANSIString<'a>> for Span<'a, Style> {
     fn from(string: &'a ANSIString<'a>) -> Self {
         let style = Cow::Borrowed(string.style_ref());
@@ -58,7 +119,7 @@ impl<'a> From<&'a ANSIString<'a>> for Span<'a, Style> {
         Span::new(style, content)
     }
 }
-#[cfg(test)]
+#[cfg(any(test, feature = "ansi"))]
 impl<'a> From<ANSIString<'_>> for Span<'a, Style> {
     fn from(string: ANSIString<'_>) -> Self {
         let style = Cow::Owned(*string.style_ref());
@@ -66,6 +127,53 @@ impl<'a> From<ANSIString<'_>> for Span<'a, Style> {
         Span::new(style, content)
     }
 }
+#[cfg(feature = "owo-colors")]
+impl<'a, 'b> From<&'b Span<'a, owo_colors::Style>> for owo_colors::Styled<&'b str> {
+    fn from(span: &'b Span<'a, owo_colors::Style>) -> Self {
+        span.style.style(span.content.as_ref())
+    }
+}
+#[cfg(feature = "owo-colors")]
+impl<'a> From<owo_colors::Styled<&'a str>> for Span<'a, owo_colors::Style> {
+    fn from(styled: owo_colors::Styled<&'a str>) -> Self {
+        Span::new(Cow::Owned(styled.style), Cow::Borrowed(*styled.inner()))
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl<'a, D: fmt::Display> From<crossterm::style::StyledContent<D>>
+    for Span<'a, crossterm::style::ContentStyle>
+{
+    fn from(styled: crossterm::style::StyledContent<D>) -> Self {
+        Span::new(
+            Cow::Owned(*styled.style()),
+            Cow::Owned(styled.content().to_string()),
+        )
+    }
+}
+#[cfg(feature = "crossterm")]
+impl<D: fmt::Display> From<crossterm::style::StyledContent<D>>
+    for Spans<crossterm::style::ContentStyle>
+{
+    fn from(styled: crossterm::style::StyledContent<D>) -> Self {
+        let mut spans: Spans<_> = Default::default();
+        spans.push(&Span::from(styled));
+        spans
+    }
+}
+
+#[cfg(feature = "ratatui")]
+impl<'a> From<&'a Span<'_, ratatui::style::Style>> for ratatui::text::Span<'a> {
+    fn from(span: &'a Span<'_, ratatui::style::Style>) -> Self {
+        ratatui::text::Span::styled(span.raw_ref(), **span.style())
+    }
+}
+#[cfg(feature = "ratatui")]
+impl<'a> From<ratatui::text::Span<'a>> for Span<'a, ratatui::style::Style> {
+    fn from(span: ratatui::text::Span<'a>) -> Self {
+        Span::new(Cow::Owned(span.style), span.content)
+    }
+}
 
 impl<'a, T: Clone + Default + PartialEq> Joinable<Span<'a, T>> for Span<'a, T> {
     type Output = Spans<T>;
@@ -76,6 +184,46 @@ impl<'a, T: Clone + Default + PartialEq> Joinable<Span<'a, T>> for Span<'a, T> {
         res
     }
 }
+impl<'a, T: Clone + Default + PartialEq> Add<&Span<'_, T>> for Span<'a, T> {
+    type Output = Spans<T>;
+    /// Joins two spans into text.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Span, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let italic = Tag::new("<i>", "</i>");
+    /// let line = Span::borrowed(&bold, "foo") + &Span::borrowed(&italic, "bar");
+    /// assert_eq!(line.to_string(), "<b>foo</b><i>bar</i>");
+    /// ```
+    fn add(self, other: &Span<'_, T>) -> Self::Output {
+        let mut res: Spans<T> = Default::default();
+        res.push(&self);
+        res.push(other);
+        res
+    }
+}
+impl<'a, T: Clone + Default + PartialEq> Joinable<Spans<T>> for Span<'a, T> {
+    type Output = Spans<T>;
+    fn join(&self, other: &Spans<T>) -> Self::Output {
+        let mut res: Spans<T> = Default::default();
+        res.push(self);
+        res.push(other);
+        res
+    }
+}
+impl<'a, T: Clone + Default> From<&'a str> for Span<'a, T> {
+    /// Creates a span of text in the default style.
+    fn from(content: &'a str) -> Self {
+        Span::new(Cow::Owned(T::default()), Cow::Borrowed(content))
+    }
+}
+impl<T: Clone + Default> From<String> for Span<'static, T> {
+    /// Creates a span of text in the default style.
+    fn from(content: String) -> Self {
+        Span::new(Cow::Owned(T::default()), Cow::Owned(content))
+    }
+}
 impl<'a, T: Clone> Pushable<str> for Span<'a, T> {
     fn push(&mut self, other: &str) {
         self.content.to_mut().push_str(other);
@@ -102,7 +250,7 @@ impl<'a, T: Clone> RawText for Span<'a, T> {
 }
 impl<'a, T: Clone> BoundedWidth for Span<'a, T> {
     fn bounded_width(&self) -> usize {
-        self.content.width()
+        WidthPolicy::current().width(&self.content)
     }
 }
 impl<'a, T: Clone> HasWidth for Span<'a, T> {
@@ -110,6 +258,26 @@ impl<'a, T: Clone> HasWidth for Span<'a, T> {
         Width::Bounded(self.bounded_width())
     }
 }
+impl<'a, T: Clone> BoundedWidth for &Span<'a, T> {
+    fn bounded_width(&self) -> usize {
+        (**self).bounded_width()
+    }
+}
+impl<'a, T: Clone> HasWidth for &Span<'a, T> {
+    fn width(&self) -> Width {
+        (**self).width()
+    }
+}
+impl<'a, T: Clone> WidthSliceable for &Span<'a, T> {
+    type Output = Span<'a, T>;
+    fn slice_width<R>(&self, range: R) -> Option<Self::Output>
+    where
+        R: RangeBounds<usize>,
+    {
+        (**self).slice_width(range)
+    }
+}
+#[cfg(feature = "regex")]
 impl<'a, T: Clone> Expandable for Span<'a, T> {
     fn expand(&self, capture: &Captures) -> Span<'a, T> {
         let new_content = self.raw().expand(capture);
@@ -133,6 +301,47 @@ mod test {
         let expected = Style::new().paint("foo");
         assert_eq!(expected, actual);
     }
+    #[cfg(feature = "owo-colors")]
+    #[test]
+    fn convert_owo_colors() {
+        use owo_colors::OwoColorize;
+        let style = owo_colors::Style::new().green().bold();
+        let styled = "foo".style(style);
+        let span: Span<owo_colors::Style> = style.style("foo").into();
+        assert_eq!(span, Span::borrowed(&style, "foo"));
+        let actual: owo_colors::Styled<&str> = (&span).into();
+        assert_eq!(styled.to_string(), actual.to_string());
+        let mut spans: Spans<owo_colors::Style> = Default::default();
+        spans.push(&span);
+        spans.push(&Span::borrowed(&style, "bar"));
+        assert_eq!(spans.to_string(), "foobar".style(style).to_string());
+    }
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn convert_crossterm() {
+        use crate::widget::{HBox, TextWidget, TruncationStyle};
+        use crossterm::style::{ContentStyle, Stylize};
+        let red = ContentStyle::new().red();
+        let span: Span<ContentStyle> = red.apply(42).into();
+        assert_eq!(span, Span::borrowed(&red, "42"));
+        let spans: Spans<ContentStyle> = "hello world".bold().into();
+        let truncation = TruncationStyle::Left("…");
+        let widget = TextWidget::new(Cow::Borrowed(&spans), Cow::Borrowed(&truncation));
+        let mut hbox = HBox::new();
+        hbox.push(Box::new(widget));
+        let expected = "hello…".bold().to_string();
+        assert_eq!(hbox.truncate(6).to_string(), expected);
+    }
+    #[cfg(feature = "anstyle")]
+    #[test]
+    fn display_anstyle() {
+        let red = anstyle::Style::new().fg_color(Some(anstyle::AnsiColor::Red.into()));
+        let plain = anstyle::Style::new();
+        let mut spans: Spans<anstyle::Style> = Default::default();
+        spans.push(&Span::borrowed(&red, "foo"));
+        spans.push(&Span::borrowed(&plain, "bar"));
+        assert_eq!(spans.to_string(), "\x1b[31mfoo\x1b[0mbar");
+    }
     #[test]
     fn fmt() {
         let style = Style::new();
@@ -0,0 +1,141 @@
+use super::Spans;
+use crate::text::{bounds, BoundedWidth, Pushable, RawText, WidthPolicy};
+use std::borrow::Cow;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A single grapheme of text with its style, borrowed from the text it came
+/// from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StyledGrapheme<'a, T: Clone> {
+    style: Cow<'a, T>,
+    grapheme: &'a str,
+}
+
+impl<'a, T: Clone> StyledGrapheme<'a, T> {
+    pub fn new(style: Cow<'a, T>, grapheme: &'a str) -> Self {
+        StyledGrapheme { style, grapheme }
+    }
+    /// Returns the style of the grapheme.
+    pub fn style(&self) -> &T {
+        &self.style
+    }
+    /// Returns the grapheme.
+    pub fn grapheme(&self) -> &'a str {
+        self.grapheme
+    }
+}
+
+impl<T: Clone> BoundedWidth for StyledGrapheme<'_, T> {
+    fn bounded_width(&self) -> usize {
+        WidthPolicy::current().grapheme_width(self.grapheme)
+    }
+}
+
+impl<T: Clone> RawText for StyledGrapheme<'_, T> {
+    fn raw(&self) -> String {
+        self.grapheme.to_string()
+    }
+    fn raw_ref(&self) -> &str {
+        self.grapheme
+    }
+}
+
+impl<T: Clone + Default> Spans<T> {
+    /// Iterates over the graphemes of the text with their styles, without
+    /// copying the text.
+    pub fn styled_graphemes(&self) -> impl Iterator<Item = StyledGrapheme<'_, T>> {
+        self.runs().flat_map(|(style, run)| {
+            run.graphemes(true)
+                .map(move |grapheme| StyledGrapheme::new(style.clone(), grapheme))
+        })
+    }
+    /// Iterates over the graphemes entirely within a range of columns, which
+    /// are the graphemes [`crate::text::WidthSliceable::slice_width`] would
+    /// keep. Pushing them onto other text streams the slice into it without
+    /// building the slice first.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag, WidthSliceable};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let text = Spans::styled(bold, "🙈a").then(Tag::default(), "bc");
+    /// let mut streamed: Spans<Tag> = Spans::from("> ");
+    /// streamed.extend(text.graphemes_within(2..4));
+    /// assert_eq!(streamed.to_string(), "> <b>a</b>b");
+    /// let sliced: Spans<Tag> = text.graphemes_within(..3).collect();
+    /// assert_eq!(Some(sliced), text.slice_width(..3));
+    /// ```
+    pub fn graphemes_within<R>(&self, range: R) -> impl Iterator<Item = StyledGrapheme<'_, T>>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = bounds::normalize(&range);
+        let policy = WidthPolicy::current();
+        self.styled_graphemes()
+            .scan(0, move |column, grapheme| {
+                let width = policy.grapheme_width(grapheme.grapheme);
+                let in_range = bounds::contains(start, end, *column, width);
+                *column += width;
+                Some((in_range, grapheme))
+            })
+            .skip_while(|(in_range, _)| !in_range)
+            .take_while(|(in_range, _)| *in_range)
+            .map(|(_, grapheme)| grapheme)
+    }
+}
+
+impl<T: Clone + PartialEq> Pushable<StyledGrapheme<'_, T>> for Spans<T> {
+    fn push(&mut self, other: &StyledGrapheme<'_, T>) {
+        let last = self
+            .len()
+            .checked_sub(1)
+            .and_then(|last| self.style_at(last));
+        if last != Some(other.style()) {
+            self.spans.insert(self.content.len(), other.style().clone());
+        }
+        self.content_mut().push_str(other.grapheme);
+    }
+}
+
+impl<'a, T: Clone + PartialEq> Extend<StyledGrapheme<'a, T>> for Spans<T> {
+    fn extend<I: IntoIterator<Item = StyledGrapheme<'a, T>>>(&mut self, iter: I) {
+        for grapheme in iter {
+            self.push(&grapheme);
+        }
+    }
+}
+
+impl<'a, T: Clone + PartialEq> FromIterator<StyledGrapheme<'a, T>> for Spans<T> {
+    fn from_iter<I: IntoIterator<Item = StyledGrapheme<'a, T>>>(iter: I) -> Self {
+        let mut result: Spans<T> = Default::default();
+        result.extend(iter);
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Tag, WidthSliceable};
+    #[test]
+    fn streamed_graphemes_match_slices() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = Spans::styled(fmt_1, "a🙈b").then(fmt_2, "🙉cd");
+        for start in 0..9 {
+            for end in start..10 {
+                let streamed: Spans<Tag> = text.graphemes_within(start..end).collect();
+                let sliced = text.slice_width(start..end).unwrap_or_default();
+                assert_eq!(
+                    sliced.to_string(),
+                    streamed.to_string(),
+                    "{}..{}",
+                    start,
+                    end
+                );
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::{RawText, Sliceable};
+use super::{Pushable, RawText, Sliceable};
 use std::iter::once;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -10,6 +10,85 @@ pub struct Split<T, U> {
     pub segment: Option<U>,
 }
 
+impl<T, U> Split<T, U> {
+    /// Transforms the segment, keeping the delimiter.
+    pub fn map_segment<V, F>(self, f: F) -> Split<T, V>
+    where
+        F: FnOnce(U) -> V,
+    {
+        Split {
+            delim: self.delim,
+            segment: self.segment.map(f),
+        }
+    }
+    /// Transforms the delimiter, keeping the segment.
+    pub fn map_delim<V, F>(self, f: F) -> Split<V, U>
+    where
+        F: FnOnce(T) -> V,
+    {
+        Split {
+            delim: self.delim.map(f),
+            segment: self.segment,
+        }
+    }
+}
+
+/// Reassembles text from the segments and delimiters produced by
+/// [`Splitable::split`]. This is implemented for every iterator of
+/// [`Split`]s.
+pub trait Unsplit<T>: Iterator<Item = Split<T, T>> + Sized {
+    /// Joins the segments and delimiters back together.
+    ///
+    /// # Example
+    /// ```rust
+    /// use stylish_stringlike::text::{Split, Splitable, Unsplit};
+    /// let path = String::from("/usr/local/bin");
+    /// let rebuilt: String = Splitable::<&str>::split(&path, "/")
+    ///     .map(|split| split.map_segment(|segment| segment.to_uppercase()))
+    ///     .unsplit();
+    /// assert_eq!(rebuilt, "/USR/LOCAL/BIN");
+    /// ```
+    fn unsplit(self) -> T
+    where
+        T: Default + Pushable<T>,
+    {
+        self.unsplit_with(|segment| segment, |delim| delim)
+    }
+    /// Joins the segments and delimiters back together after passing them
+    /// through separate functions.
+    ///
+    /// # Example
+    /// ```rust
+    /// use stylish_stringlike::text::{RawText, Spans, Splitable, Tag, Unsplit};
+    /// let dim = Tag::new("<d>", "</d>");
+    /// let path: Spans<Tag> = Spans::from("/usr/local/bin");
+    /// let short: Spans<Tag> = path.split("/").unsplit_with(
+    ///     |segment| segment.slice_graphemes(..1).unwrap_or(segment),
+    ///     |delim| Spans::styled(dim.clone(), delim.raw_ref()),
+    /// );
+    /// assert_eq!(short.to_string(), "<d>/</d>u<d>/</d>l<d>/</d>b");
+    /// ```
+    fn unsplit_with<O, F, G>(self, mut segment: F, mut delim: G) -> O
+    where
+        F: FnMut(T) -> O,
+        G: FnMut(T) -> O,
+        O: Default + Pushable<O>,
+    {
+        let mut result = O::default();
+        for split in self {
+            if let Some(text) = split.segment {
+                result.push(&segment(text));
+            }
+            if let Some(text) = split.delim {
+                result.push(&delim(text));
+            }
+        }
+        result
+    }
+}
+
+impl<T, I: Iterator<Item = Split<T, T>>> Unsplit<T> for I {}
+
 /// Text objects that can be split on a delimiter or pattern
 pub trait Splitable<'a, T> {
     /// Split a text object on the given pattern.
@@ -119,4 +198,14 @@ mod test {
             split.next()
         );
     }
+    #[test]
+    fn unsplit_round_trips() {
+        let path = String::from("//a//b/");
+        let rebuilt: String = Splitable::<&str>::split(&path, "/").unsplit();
+        assert_eq!(rebuilt, path);
+        let counted: String = Splitable::<&str>::split(&path, "/")
+            .map(|split| split.map_delim(|_| String::from("|")))
+            .unsplit_with(|segment| format!("{}", segment.len()), |delim| delim);
+        assert_eq!(counted, "|0|1|0|1|");
+    }
 }
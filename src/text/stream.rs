@@ -0,0 +1,272 @@
+//! Line-by-line processing of styled text, such as the output of a program
+//! that colors its logs with ANSI escape sequences.
+//!
+//! Lines are read, parsed into [`Spans<Format>`], passed through a pipeline
+//! and written out one at a time, so files of any size can be processed
+//! without loading them whole.
+use crate::text::{Color, Format, Pushable, Span, Spans};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::io::{self, BufRead, Write};
+
+/// Parses text containing ANSI escape sequences into styled text.
+///
+/// Select Graphic Rendition (SGR) sequences set the format of the text that
+/// follows. The format carries over from one call to the next, as a terminal
+/// keeps it from one line to the next. Colors from the 256 color palette and
+/// 24-bit colors are kept as [`Color::Fixed`] and [`Color::Rgb`]. Other
+/// escape sequences are dropped.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{stream::AnsiParser, Color, RawText};
+/// let mut parser = AnsiParser::new();
+/// let first = parser.parse("plain \x1b[1;31merror");
+/// let second = parser.parse("still red\x1b[0m plain");
+/// assert_eq!(first.raw(), "plain error");
+/// assert_eq!(first.to_string(), "plain \x1b[1;31merror\x1b[0m");
+/// assert_eq!(second.style_at(0), Some(&Color::Red.bold()));
+/// assert_eq!(second.to_string(), "\x1b[1;31mstill red\x1b[0m plain");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AnsiParser {
+    format: Format,
+}
+
+impl AnsiParser {
+    /// Creates a parser starting with the plain format.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Returns the format the next text parsed starts with.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+    /// Parses text, updating the current format as escape sequences are
+    /// found.
+    pub fn parse(&mut self, text: &str) -> Spans<Format> {
+        let mut result: Spans<Format> = Default::default();
+        let mut rest = text;
+        while let Some(escape) = rest.find('\x1b') {
+            self.push_text(&mut result, &rest[..escape]);
+            rest = self.consume_escape(&rest[escape..]);
+        }
+        self.push_text(&mut result, rest);
+        result
+    }
+    /// Pushes text in the current format.
+    fn push_text(&self, result: &mut Spans<Format>, text: &str) {
+        if !text.is_empty() {
+            result.push(&Span::new(Cow::Borrowed(&self.format), Cow::Borrowed(text)));
+        }
+    }
+    /// Applies the escape sequence at the start of `text`, returning the text
+    /// after it.
+    fn consume_escape<'a>(&mut self, text: &'a str) -> &'a str {
+        let body = &text[1..];
+        if let Some(params) = body.strip_prefix('[') {
+            // A control sequence ends with a byte in `@` to `~`.
+            match params.find(|c| ('@'..='~').contains(&c)) {
+                Some(end) => {
+                    if params[end..].starts_with('m') {
+                        self.apply_sgr(&params[..end]);
+                    }
+                    &params[end + 1..]
+                }
+                None => "",
+            }
+        } else if let Some(command) = body.strip_prefix(']') {
+            // An operating system command, such as setting the title, ends
+            // with a bell or a string terminator.
+            match command.find(['\x07', '\x1b']) {
+                Some(end) if command[end..].starts_with('\x07') => &command[end + 1..],
+                Some(end) => command[end + 1..]
+                    .strip_prefix('\\')
+                    .unwrap_or(&command[end..]),
+                None => "",
+            }
+        } else {
+            let mut chars = body.chars();
+            chars.next();
+            chars.as_str()
+        }
+    }
+    /// Updates the current format from the parameters of an SGR sequence.
+    fn apply_sgr(&mut self, params: &str) {
+        let mut codes = params.split([';', ':']).map(|code| {
+            if code.is_empty() {
+                Some(0)
+            } else {
+                code.parse::<u16>().ok()
+            }
+        });
+        while let Some(code) = codes.next() {
+            let format = &mut self.format;
+            match code {
+                Some(0) => *format = Format::default(),
+                Some(1) => format.bold = true,
+                Some(3) => format.italic = true,
+                Some(4) => format.underline = true,
+                Some(22) => format.bold = false,
+                Some(23) => format.italic = false,
+                Some(24) => format.underline = false,
+                Some(code @ 30..=37) => format.fg = color(code - 30),
+                Some(code @ 90..=97) => format.fg = color(code - 90 + 8),
+                Some(39) => format.fg = None,
+                Some(code @ 40..=47) => format.bg = color(code - 40),
+                Some(code @ 100..=107) => format.bg = color(code - 100 + 8),
+                Some(49) => format.bg = None,
+                Some(code @ 38) | Some(code @ 48) => {
+                    let kind = codes.next();
+                    let mut byte = || codes.next().flatten().and_then(|c| u8::try_from(c).ok());
+                    let extended = match kind {
+                        Some(Some(5)) => byte().map(Color::Fixed),
+                        Some(Some(2)) => match (byte(), byte(), byte()) {
+                            (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    if let Some(extended) = extended {
+                        if code == 38 {
+                            format.fg = Some(extended);
+                        } else {
+                            format.bg = Some(extended);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Returns the color at an index of the sixteen color palette.
+fn color(index: u16) -> Option<Color> {
+    use Color::*;
+    let palette = [
+        Black,
+        Red,
+        Green,
+        Yellow,
+        Blue,
+        Magenta,
+        Cyan,
+        White,
+        BrightBlack,
+        BrightRed,
+        BrightGreen,
+        BrightYellow,
+        BrightBlue,
+        BrightMagenta,
+        BrightCyan,
+        BrightWhite,
+    ];
+    palette.get(usize::from(index)).copied()
+}
+
+/// An iterator over the lines of a reader, parsed into styled text.
+///
+/// Line endings are not included in the lines. The format set by escape
+/// sequences carries over from one line to the next.
+#[derive(Debug)]
+pub struct AnsiLines<R> {
+    reader: R,
+    parser: AnsiParser,
+    buffer: String,
+}
+
+impl<R: BufRead> AnsiLines<R> {
+    /// Creates an iterator over the lines of a reader.
+    pub fn new(reader: R) -> Self {
+        AnsiLines {
+            reader,
+            parser: Default::default(),
+            buffer: String::new(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for AnsiLines<R> {
+    type Item = io::Result<Spans<Format>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.clear();
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => None,
+            Ok(_) => {
+                let line = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                Some(Ok(self.parser.parse(line)))
+            }
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+/// Reads lines of ANSI-styled text, passes each through a pipeline and
+/// writes the results as ANSI-styled lines. Lines for which the pipeline
+/// returns [`None`] are left out.
+///
+/// Only one line is held in memory at a time.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{stream, Color, Format, Replaceable, Spans};
+/// let log = "\x1b[32mINFO\x1b[0m started\n\x1b[31mERROR\x1b[0m disk full\ndebug noise\n";
+/// let disk = Spans::styled(Color::Yellow.bold(), "DISK");
+/// let mut output = vec![];
+/// stream::process(log.as_bytes(), &mut output, |line| {
+///     // Drop lines that do not start with a colored level.
+///     if line.style_at(0).map_or(true, Format::is_plain) {
+///         return None;
+///     }
+///     Some(line.replace("disk", &disk))
+/// })
+/// .unwrap();
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "\x1b[32mINFO\x1b[0m started\n\x1b[31mERROR\x1b[0m \x1b[1;33mDISK\x1b[0m full\n"
+/// );
+/// ```
+pub fn process<R, W, F>(reader: R, mut writer: W, mut pipeline: F) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(Spans<Format>) -> Option<Spans<Format>>,
+{
+    for line in AnsiLines::new(reader) {
+        if let Some(line) = pipeline(line?) {
+            writeln!(writer, "{}", line)?;
+        }
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::RawText;
+    #[test]
+    fn parse_escape_sequences() {
+        let mut parser = AnsiParser::new();
+        let text = parser.parse(
+            "\x1b]0;title\x07a\x1b[38;5;196;4mb\x1b[24;92;101mc\x1b[2Kd\x1b]8;;url\x1b\\e\x1b[",
+        );
+        assert_eq!(text.raw(), "abcde");
+        let underline = Format::new().fg(Color::Fixed(196)).underline();
+        let green = Format::new().fg(Color::BrightGreen).bg(Color::BrightRed);
+        let styles: Vec<_> = text.spans().map(|span| **span.style()).collect();
+        assert_eq!(styles, [Format::new(), underline, green]);
+        assert_eq!(parser.format(), green);
+    }
+    #[test]
+    fn lines_strip_line_endings() {
+        let input = "\x1b[1ma\r\nb\n\nc";
+        let lines: Vec<Spans<Format>> = AnsiLines::new(input.as_bytes())
+            .collect::<io::Result<_>>()
+            .unwrap();
+        let raw: Vec<String> = lines.iter().map(RawText::raw).collect();
+        assert_eq!(raw, ["a", "b", "", "c"]);
+        assert_eq!(lines[1].style_at(0), Some(&Format::new().bold()));
+    }
+}
@@ -0,0 +1,79 @@
+/// Trait for styles with a stable identifier, so that renderers can cache
+/// painted fragments keyed by style and text across frames.
+pub trait StyleKey {
+    /// Returns an identifier for the style. Styles that paint text the same
+    /// way have the same key, and keys do not change between runs or builds.
+    ///
+    /// # Example
+    /// ```rust
+    /// use stylish_stringlike::text::{StyleKey, Tag};
+    /// let bold = Tag::new("<b>", "</b>");
+    /// assert_eq!(bold.style_key(), Tag::new("<b>", "</b>").style_key());
+    /// assert_ne!(bold.style_key(), Tag::new("<i>", "</i>").style_key());
+    /// ```
+    fn style_key(&self) -> u64;
+}
+
+/// Hashes byte strings with 64-bit FNV-1a, which unlike the standard library's
+/// hashers is specified and therefore stable. Each part is followed by its
+/// length so that moving bytes between parts changes the hash.
+pub(crate) fn stable_hash(parts: &[&[u8]]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let mut hash = OFFSET;
+    for part in parts {
+        for byte in part.iter().chain(&(part.len() as u64).to_le_bytes()) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    hash
+}
+
+impl StyleKey for () {
+    fn style_key(&self) -> u64 {
+        stable_hash(&[])
+    }
+}
+
+#[cfg(any(test, feature = "ansi"))]
+impl StyleKey for ansi_term::Style {
+    fn style_key(&self) -> u64 {
+        stable_hash(&[self.prefix().to_string().as_bytes()])
+    }
+}
+
+#[cfg(feature = "anstyle")]
+impl StyleKey for anstyle::Style {
+    fn style_key(&self) -> u64 {
+        stable_hash(&[self.render().to_string().as_bytes()])
+    }
+}
+
+#[cfg(feature = "owo-colors")]
+impl StyleKey for owo_colors::Style {
+    fn style_key(&self) -> u64 {
+        stable_hash(&[self.prefix_formatter().to_string().as_bytes()])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn stable_hash_values() {
+        assert_eq!(stable_hash(&[]), 0xcbf2_9ce4_8422_2325);
+        assert_ne!(stable_hash(&[b"ab", b"c"]), stable_hash(&[b"a", b"bc"]));
+        assert_eq!(stable_hash(&[b"abc"]), stable_hash(&[b"abc"]));
+    }
+    #[test]
+    fn ansi_style_keys() {
+        use ansi_term::{Color, Style};
+        assert_eq!(Style::new().style_key(), stable_hash(&[b""]));
+        assert_eq!(Color::Red.normal().style_key(), stable_hash(&[b"\x1b[31m"]));
+        assert_ne!(
+            Color::Red.normal().style_key(),
+            Color::Red.bold().style_key()
+        );
+    }
+}
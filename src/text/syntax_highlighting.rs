@@ -0,0 +1,178 @@
+//! Integration with [`syntect`] highlighting. Requires the `syntect` feature.
+use super::style_key::stable_hash;
+use crate::text::{Paintable, Pushable, Span, Spans, StyleKey};
+use std::borrow::{Borrow, Cow};
+use std::fmt;
+use syntect::highlighting::{FontStyle, Style};
+
+/// Writes the escape sequence that switches a terminal to a style, using
+/// 24-bit color for the foreground. The background is left to the terminal,
+/// since highlighting themes give every span the background of the theme.
+fn write_prefix<W: fmt::Write>(style: &Style, w: &mut W) -> fmt::Result {
+    w.write_str("\x1b[")?;
+    if style.font_style.contains(FontStyle::BOLD) {
+        w.write_str("1;")?;
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        w.write_str("3;")?;
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        w.write_str("4;")?;
+    }
+    let color = style.foreground;
+    write!(w, "38;2;{};{};{}m", color.r, color.g, color.b)
+}
+
+/// Writes groups of text, switching style only where it changes and skipping
+/// empty text. Text in the default style is written without escape sequences.
+fn write_many<'a, T, U, V, W>(groups: T, w: &mut W) -> fmt::Result
+where
+    T: IntoIterator<Item = (U, V)> + 'a,
+    U: Borrow<Style> + 'a,
+    V: Borrow<str> + 'a,
+    W: fmt::Write,
+{
+    let mut previous: Option<Style> = None;
+    // Whether an escape sequence has been written that needs a reset.
+    let mut open = false;
+    for (style, text) in groups {
+        let (style, text) = (*style.borrow(), text.borrow());
+        if text.is_empty() {
+            continue;
+        }
+        if previous != Some(style) {
+            if open {
+                w.write_str("\x1b[0m")?;
+            }
+            open = style != Style::default();
+            if open {
+                write_prefix(&style, w)?;
+            }
+            previous = Some(style);
+        }
+        w.write_str(text)?;
+    }
+    if open {
+        w.write_str("\x1b[0m")?;
+    }
+    Ok(())
+}
+
+/// Paints with [`syntect`] highlighting styles as 24-bit color escape
+/// sequences. Requires the `syntect` feature.
+///
+/// Text in [`Style::default`], which is how unhighlighted text is usually
+/// styled, is painted without escape sequences, keeping the colors of the
+/// terminal rather than painting it black.
+///
+/// # Example
+/// ```
+/// use syntect::highlighting::{Color, FontStyle, Style};
+/// use stylish_stringlike::text::Paintable;
+/// let keyword = Style {
+///     foreground: Color { r: 255, g: 0, b: 128, a: 255 },
+///     background: Color::WHITE,
+///     font_style: FontStyle::BOLD,
+/// };
+/// assert_eq!(keyword.paint("fn"), "\x1b[1;38;2;255;0;128mfn\x1b[0m");
+/// assert_eq!(Style::default().paint("main"), "main");
+/// ```
+impl Paintable for Style {
+    fn paint(&self, target: &str) -> String {
+        Style::paint_many(std::iter::once((self, target)))
+    }
+    fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_many(std::iter::once((self, target)), f)
+    }
+    fn paint_many<'a, T, U, V>(groups: T) -> String
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        let mut result = String::new();
+        // Writing into a `String` cannot fail.
+        let _ = write_many(groups, &mut result);
+        result
+    }
+    fn paint_many_fmt<'a, T, U, V>(groups: T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        write_many(groups, f)
+    }
+}
+
+impl StyleKey for Style {
+    fn style_key(&self) -> u64 {
+        let mut prefix = String::new();
+        if *self != Style::default() {
+            // Writing into a `String` cannot fail.
+            let _ = write_prefix(self, &mut prefix);
+        }
+        stable_hash(&[prefix.as_bytes()])
+    }
+}
+
+impl From<&[(Style, &str)]> for Spans<Style> {
+    /// Collects the output of a syntect highlighter, such as
+    /// `HighlightLines::highlight_line`, into text.
+    ///
+    /// # Example
+    /// ```
+    /// use syntect::highlighting::{Color, Style};
+    /// use stylish_stringlike::text::{RawText, Spans, WidthSliceable};
+    /// let keyword = Style { foreground: Color::BLACK, ..Style::default() };
+    /// let regions = vec![(keyword, "fn"), (Style::default(), " main() {}")];
+    /// let line = Spans::from(regions);
+    /// assert_eq!(line.raw(), "fn main() {}");
+    /// assert_eq!(line.slice_width(..4).unwrap().raw(), "fn m");
+    /// ```
+    fn from(regions: &[(Style, &str)]) -> Self {
+        let mut result: Spans<Style> = Default::default();
+        for (style, text) in regions {
+            result.push(&Span::new(Cow::Borrowed(style), Cow::Borrowed(*text)));
+        }
+        result
+    }
+}
+
+impl From<Vec<(Style, &str)>> for Spans<Style> {
+    fn from(regions: Vec<(Style, &str)>) -> Self {
+        Spans::from(regions.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::widget::{HBox, TextWidget, TruncationStyle};
+    use syntect::highlighting::Color;
+    #[test]
+    fn highlighted_regions_truncate() {
+        let red = Style {
+            foreground: Color {
+                r: 255,
+                g: 0,
+                b: 0,
+                a: 255,
+            },
+            ..Style::default()
+        };
+        let plain = Style::default();
+        let line = Spans::from(vec![(red, "let"), (plain, " x"), (plain, " = 1;")]);
+        let truncation = TruncationStyle::Left("…");
+        let mut hbox: HBox<Spans<Style>> = HBox::new();
+        hbox.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&line),
+            Cow::Borrowed(&truncation),
+        )));
+        assert_eq!(
+            hbox.truncate(6).to_string(),
+            "\x1b[38;2;255;0;0mlet\x1b[0m x…"
+        );
+        assert_ne!(red.style_key(), plain.style_key());
+    }
+}
@@ -1,11 +1,16 @@
-use crate::text::Paintable;
-use std::borrow::Borrow;
+use super::style_key::stable_hash;
+use crate::text::{Annotatable, BoundedWidth, Paintable, StyleKey};
+use std::borrow::{Borrow, Cow};
+use std::collections::BTreeMap;
+use std::fmt;
 
 /// A simple format for surrounding text in tags
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Tag {
     opening: String,
     closing: String,
+    attributes: BTreeMap<String, String>,
+    escape_html: bool,
 }
 
 impl Tag {
@@ -13,16 +18,112 @@ impl Tag {
         Self {
             opening: opening.to_string(),
             closing: closing.to_string(),
+            attributes: BTreeMap::new(),
+            escape_html: false,
+        }
+    }
+    /// Sets an attribute rendered into the opening tag, such as a `title`
+    /// shown as a tooltip in HTML. Attributes are only rendered when the
+    /// opening tag ends with `>`. Values are escaped for use in HTML.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Paintable, Tag};
+    /// let mut abbr = Tag::new("<abbr>", "</abbr>");
+    /// abbr.set_attribute("title", "Rust \"crate\"");
+    /// assert_eq!(
+    ///     abbr.paint("crate"),
+    ///     "<abbr title=\"Rust &quot;crate&quot;\">crate</abbr>"
+    /// );
+    /// ```
+    pub fn set_attribute(&mut self, key: &str, value: &str) {
+        self.attributes.insert(key.to_string(), value.to_string());
+    }
+    /// Sets whether text painted in this tag is escaped for HTML, as it is in
+    /// tags read by [`crate::text::MarkupParser::html`]. Defaults to false.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Paintable, Tag};
+    /// let mut code = Tag::new("<code>", "</code>");
+    /// code.set_escape_html(true);
+    /// assert_eq!(code.paint("a < b"), "<code>a &lt; b</code>");
+    /// ```
+    pub fn set_escape_html(&mut self, escape_html: bool) {
+        self.escape_html = escape_html;
+    }
+    /// Returns the value of an attribute, if it is set.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.get(key).map(String::as_str)
+    }
+    /// The opening tag with any attributes rendered into it.
+    fn opening(&self) -> Cow<'_, str> {
+        match self.opening.strip_suffix('>') {
+            Some(head) if !self.attributes.is_empty() => {
+                let mut opening = String::from(head);
+                for (key, value) in &self.attributes {
+                    opening.push(' ');
+                    opening.push_str(key);
+                    opening.push_str("=\"");
+                    escape_into(&mut opening, value);
+                    opening.push('"');
+                }
+                opening.push('>');
+                Cow::Owned(opening)
+            }
+            _ => Cow::Borrowed(&self.opening),
+        }
+    }
+    /// Text as it is painted in this tag, escaped if needed.
+    fn text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.escape_html && text.contains(['&', '<', '>', '"']) {
+            let mut escaped = String::with_capacity(text.len());
+            escape_into(&mut escaped, text);
+            Cow::Owned(escaped)
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+}
+
+/// Escapes text for use in HTML content or an attribute value.
+pub(crate) fn escape_into(target: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => target.push_str("&amp;"),
+            '<' => target.push_str("&lt;"),
+            '>' => target.push_str("&gt;"),
+            '"' => target.push_str("&quot;"),
+            c => target.push(c),
+        }
+    }
+}
+
+impl Annotatable for Tag {
+    fn annotate(&self, key: &str, value: &str) -> Self {
+        let mut tag = self.clone();
+        tag.set_attribute(key, value);
+        tag
+    }
+}
+
+impl StyleKey for Tag {
+    fn style_key(&self) -> u64 {
+        let opening = self.opening();
+        if self.escape_html {
+            stable_hash(&[opening.as_bytes(), self.closing.as_bytes(), b"escape_html"])
+        } else {
+            stable_hash(&[opening.as_bytes(), self.closing.as_bytes()])
         }
     }
 }
 
 impl Paintable for Tag {
     fn paint(&self, target: &str) -> String {
-        [self.opening.as_str(), target, self.closing.as_str()]
-            .iter()
-            .copied()
-            .collect()
+        let mut result = self.opening().into_owned();
+        result.push_str(&self.text(target));
+        result.push_str(&self.closing);
+        result
     }
     fn paint_many<'a, T, U, V>(groups: T) -> String
     where
@@ -56,6 +157,51 @@ impl Paintable for Tag {
         }
         result
     }
+    fn markup_width(&self) -> usize {
+        self.opening().as_ref().bounded_width() + self.closing.bounded_width()
+    }
+    fn paint_fmt(&self, target: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.opening())?;
+        f.write_str(&self.text(target))?;
+        f.write_str(&self.closing)
+    }
+    fn paint_many_fmt<'a, T, U, V>(groups: T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        T: IntoIterator<Item = (U, V)> + 'a,
+        U: Borrow<Self> + 'a,
+        V: Borrow<str> + 'a,
+    {
+        // Opening tags are written lazily so that output matches `paint_many`,
+        // which drops a trailing run of empty text.
+        let mut previous_tag: Option<U> = None;
+        let mut opened = false;
+        for (painter, s) in groups {
+            let same = matches!(previous_tag, Some(ref p) if p.borrow() == painter.borrow());
+            if !same {
+                if let Some(p) = previous_tag.take() {
+                    if !opened {
+                        f.write_str(&p.borrow().opening())?;
+                    }
+                    f.write_str(&p.borrow().closing)?;
+                }
+                previous_tag = Some(painter);
+                opened = false;
+            }
+            let s = s.borrow();
+            if let (false, Some(p)) = (s.is_empty(), &previous_tag) {
+                let p = p.borrow();
+                if !opened {
+                    f.write_str(&p.opening())?;
+                    opened = true;
+                }
+                f.write_str(&p.text(s))?;
+            }
+        }
+        if let (Some(p), true) = (previous_tag, opened) {
+            f.write_str(&p.borrow().closing)?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -71,6 +217,47 @@ mod test {
             String::from("<1>foo</1><2>barbaz</2>")
         );
     }
+    struct Streamed<'a>(Vec<(&'a Tag, &'a str)>);
+    impl fmt::Display for Streamed<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Tag::paint_many_fmt(self.0.iter().copied(), f)
+        }
+    }
+    #[test]
+    fn tag_text_streamed() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let groups = vec![
+            vec![(&fmt_1, "foo"), (&fmt_2, "bar"), (&fmt_2, "baz")],
+            vec![(&fmt_1, ""), (&fmt_2, "bar"), (&fmt_1, "")],
+            vec![(&fmt_1, "foo"), (&fmt_2, ""), (&fmt_2, "")],
+            vec![],
+        ];
+        for group in groups {
+            let expected = Tag::paint_many(group.clone());
+            let actual = Streamed(group).to_string();
+            assert_eq!(expected, actual);
+        }
+    }
+    #[test]
+    fn tag_attributes() {
+        let mut fmt_1 = Tag::new("<span>", "</span>");
+        fmt_1.set_attribute("title", "a < b");
+        fmt_1.set_attribute("class", "x");
+        assert_eq!(Some("x"), fmt_1.attribute("class"));
+        assert_eq!(
+            fmt_1.paint("foo"),
+            String::from("<span class=\"x\" title=\"a &lt; b\">foo</span>")
+        );
+        let texts = vec![(&fmt_1, "foo")];
+        assert_eq!(Streamed(texts.clone()).to_string(), Tag::paint_many(texts));
+    }
+    #[test]
+    fn tag_attributes_not_html() {
+        let mut fmt_1 = Tag::new("**", "**");
+        fmt_1.set_attribute("title", "bold");
+        assert_eq!(fmt_1.paint("foo"), String::from("**foo**"));
+    }
     #[test]
     fn tag_empty() {
         let texts: Vec<(&Tag, &str)> = vec![];
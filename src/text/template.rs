@@ -0,0 +1,225 @@
+use crate::text::{Pushable, RawText, Sliceable, Span, Spans};
+use std::borrow::{Borrow, Cow};
+use std::error::Error;
+use std::fmt;
+
+/// An error in a [`Template`] or in the arguments formatted into it.
+/// Positions are byte offsets into the template.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TemplateError {
+    /// A brace is neither part of a placeholder nor doubled to escape it.
+    UnmatchedBrace { position: usize },
+    /// A placeholder contains something other than an argument index.
+    InvalidPlaceholder { position: usize },
+    /// A placeholder refers to an argument that was not given.
+    MissingArgument { index: usize },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::UnmatchedBrace { position } => {
+                write!(f, "unmatched brace at byte {}", position)
+            }
+            TemplateError::InvalidPlaceholder { position } => {
+                write!(f, "invalid placeholder at byte {}", position)
+            }
+            TemplateError::MissingArgument { index } => {
+                write!(f, "missing argument {}", index)
+            }
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+#[derive(Clone, Debug)]
+enum Piece<T> {
+    Text(Spans<T>),
+    Argument { index: usize, style: T },
+}
+
+/// Styled text with placeholders that arguments are substituted into, in the
+/// manner of [`format!`].
+///
+/// Placeholders are written `{0}`, `{1}` and so on, or `{}` for the next
+/// argument in order. As in [`format!`], `{}` placeholders are counted on
+/// their own, so the first is always argument 0 whatever explicit indices
+/// come before it. Braces are escaped by doubling them, as `{{` and
+/// `}}`.
+///
+/// Each placeholder has the style of its opening brace in the template,
+/// which can be changed with [`Template::set_style`]. Text in an argument
+/// with the default style takes the style of the placeholder, while text
+/// with any other style keeps it.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{Spans, Tag, Template};
+/// let bold = Tag::new("<b>", "</b>");
+/// let italic = Tag::new("<i>", "</i>");
+/// let template: Spans<Tag> = Spans::from("{} pushed to ").then(bold, "{}");
+/// let template = Template::parse(&template).unwrap();
+/// let commit = Spans::from("abc123");
+/// let branch = Spans::styled(italic, "main").then(Tag::default(), "!");
+/// let message = template.format(&[commit, branch]).unwrap();
+/// assert_eq!(message.to_string(), "abc123 pushed to <i>main</i><b>!</b>");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Template<T> {
+    pieces: Vec<Piece<T>>,
+}
+
+impl<T: Clone + Default + PartialEq> Template<T> {
+    /// Parses a styled template.
+    pub fn parse(template: &Spans<T>) -> Result<Self, TemplateError> {
+        let raw = template.raw_ref();
+        let mut pieces = vec![];
+        let mut text: Spans<T> = Default::default();
+        // Index of the next `{}` placeholder.
+        let mut next_index = 0;
+        // Start of the template text not yet added to `text`.
+        let mut last = 0;
+        let mut position = 0;
+        while let Some(offset) = raw[position..].find(['{', '}']) {
+            let start = position + offset;
+            let brace = &raw[start..start + 1];
+            if raw[start + 1..].starts_with(brace) {
+                // Keep one of the two braces.
+                text.push(&template.slice(last..start + 1));
+                last = start + 2;
+                position = last;
+                continue;
+            }
+            if brace == "}" {
+                return Err(TemplateError::UnmatchedBrace { position: start });
+            }
+            let end = raw[start..]
+                .find('}')
+                .map(|end| start + end)
+                .ok_or(TemplateError::UnmatchedBrace { position: start })?;
+            let inner = &raw[start + 1..end];
+            let index = if inner.is_empty() {
+                next_index += 1;
+                next_index - 1
+            } else if inner.bytes().all(|b| b.is_ascii_digit()) {
+                inner
+                    .parse()
+                    .map_err(|_| TemplateError::InvalidPlaceholder { position: start })?
+            } else {
+                return Err(TemplateError::InvalidPlaceholder { position: start });
+            };
+            text.push(&template.slice(last..start));
+            if !text.raw_ref().is_empty() {
+                pieces.push(Piece::Text(std::mem::take(&mut text)));
+            }
+            pieces.push(Piece::Argument {
+                index,
+                style: template.style_at(start).cloned().unwrap_or_default(),
+            });
+            last = end + 1;
+            position = last;
+        }
+        text.push(&template.slice(last..));
+        if !text.raw_ref().is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+        Ok(Template { pieces })
+    }
+    /// Sets the style of every placeholder for an argument.
+    pub fn set_style(&mut self, index: usize, style: T) {
+        for piece in &mut self.pieces {
+            if let Piece::Argument {
+                index: piece_index,
+                style: piece_style,
+            } = piece
+            {
+                if *piece_index == index {
+                    *piece_style = style.clone();
+                }
+            }
+        }
+    }
+    /// Returns how many arguments the template needs, which is one more than
+    /// the largest index of its placeholders.
+    pub fn arguments(&self) -> usize {
+        self.pieces
+            .iter()
+            .filter_map(|piece| match piece {
+                Piece::Argument { index, .. } => Some(index + 1),
+                Piece::Text(_) => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+    /// Substitutes arguments into the template.
+    pub fn format<S>(&self, arguments: &[S]) -> Result<Spans<T>, TemplateError>
+    where
+        S: Borrow<Spans<T>>,
+    {
+        let mut result: Spans<T> = Default::default();
+        let default = T::default();
+        for piece in &self.pieces {
+            match piece {
+                Piece::Text(text) => result.push(text),
+                Piece::Argument { index, style } => {
+                    let argument = arguments
+                        .get(*index)
+                        .ok_or(TemplateError::MissingArgument { index: *index })?;
+                    for span in argument.borrow().spans() {
+                        let span_style = if *span.style().as_ref() == default {
+                            Cow::Borrowed(style)
+                        } else {
+                            span.style().clone()
+                        };
+                        result.push(&Span::new(span_style, Cow::Borrowed(span.raw_ref())));
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::Tag;
+    #[test]
+    fn template_placeholders() {
+        let bold = Tag::new("<b>", "</b>");
+        let template: Spans<Tag> = Spans::from("{{{1}}} ").then(bold.clone(), "{}/{}");
+        let mut template = Template::parse(&template).unwrap();
+        assert_eq!(template.arguments(), 2);
+        let arguments = [Spans::from("a"), Spans::from("b")];
+        let result = template.format(&arguments).unwrap();
+        assert_eq!(result.to_string(), "{b} <b>a/b</b>");
+        template.set_style(1, Tag::new("<u>", "</u>"));
+        let result = template.format(&arguments).unwrap();
+        assert_eq!(result.to_string(), "{<u>b</u>} <b>a/</b><u>b</u>");
+        assert_eq!(
+            template.format(&arguments[..1]).unwrap_err(),
+            TemplateError::MissingArgument { index: 1 }
+        );
+    }
+    #[test]
+    fn implicit_placeholders_count_separately() {
+        let template: Spans<Tag> = Spans::from("{1}{}{0}{}");
+        let template = Template::parse(&template).unwrap();
+        let arguments = [Spans::from("a"), Spans::from("b")];
+        let result = template.format(&arguments).unwrap();
+        assert_eq!(result.to_string(), format!("{1}{}{0}{}", "a", "b"));
+    }
+    #[test]
+    fn template_errors() {
+        let cases = [
+            ("a {", TemplateError::UnmatchedBrace { position: 2 }),
+            ("a } b", TemplateError::UnmatchedBrace { position: 2 }),
+            ("{x}", TemplateError::InvalidPlaceholder { position: 0 }),
+        ];
+        for (template, error) in cases.iter() {
+            let template: Spans<Tag> = Spans::from(*template);
+            assert_eq!(Template::parse(&template).unwrap_err(), *error);
+        }
+    }
+}
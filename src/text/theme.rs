@@ -0,0 +1,125 @@
+use crate::text::{Paintable, Spans};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Maps semantic styles, such as the kinds of tokens in highlighted code, to
+/// the styles they are painted with, so that an application can swap color
+/// schemes without changing how its text is built.
+///
+/// Keys without a style of their own are painted with the fallback style.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{Spans, Tag, Theme};
+/// #[derive(Clone, Default, Eq, Hash, PartialEq)]
+/// enum Token {
+///     #[default]
+///     Plain,
+///     Keyword,
+///     Comment,
+/// }
+/// let code = Spans::styled(Token::Keyword, "let")
+///     .then(Token::Plain, " x; ")
+///     .then(Token::Comment, "// x");
+/// let light = Theme::new(Tag::default())
+///     .with(Token::Keyword, Tag::new("<blue>", "</blue>"))
+///     .with(Token::Comment, Tag::new("<gray>", "</gray>"));
+/// let dark = Theme::new(Tag::default()).with(Token::Keyword, Tag::new("<cyan>", "</cyan>"));
+/// assert_eq!(light.paint(&code), "<blue>let</blue> x; <gray>// x</gray>");
+/// assert_eq!(dark.paint(&code), "<cyan>let</cyan> x; // x");
+/// assert_eq!(dark.apply(&code).span_count(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Theme<K, P> {
+    styles: HashMap<K, P>,
+    fallback: P,
+}
+
+impl<K: Eq + Hash, P> Theme<K, P> {
+    /// Creates a theme that paints every key with a fallback style.
+    pub fn new(fallback: P) -> Self {
+        Theme {
+            styles: HashMap::new(),
+            fallback,
+        }
+    }
+    /// Sets the style of a key, returning the theme so calls can be chained.
+    pub fn with(mut self, key: K, style: P) -> Self {
+        self.styles.insert(key, style);
+        self
+    }
+    /// Sets the style of a key, returning the style it had before.
+    pub fn insert(&mut self, key: K, style: P) -> Option<P> {
+        self.styles.insert(key, style)
+    }
+    /// Removes the style of a key, so that it is painted with the fallback
+    /// style, returning the style it had.
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        self.styles.remove(key)
+    }
+    /// Returns the style of a key, or the fallback style.
+    pub fn get(&self, key: &K) -> &P {
+        self.styles.get(key).unwrap_or(&self.fallback)
+    }
+    /// Returns the style of keys without a style of their own.
+    pub fn fallback(&self) -> &P {
+        &self.fallback
+    }
+    /// Sets the style of keys without a style of their own.
+    pub fn set_fallback(&mut self, fallback: P) {
+        self.fallback = fallback;
+    }
+    /// Converts text with semantic styles into text with the styles of this
+    /// theme.
+    pub fn apply(&self, text: &Spans<K>) -> Spans<P>
+    where
+        K: Default,
+        P: Clone + PartialEq,
+    {
+        text.map_style(|key| self.get(key).clone())
+    }
+    /// Paints text with semantic styles, looking up the style of each span
+    /// without converting the text.
+    pub fn paint(&self, text: &Spans<K>) -> String
+    where
+        K: Clone + Default,
+        P: Paintable,
+    {
+        P::paint_many(text.spans().map(|span| {
+            let (key, content) = span.into_parts();
+            (self.get(&key), content)
+        }))
+    }
+}
+
+impl<K: Eq + Hash, P: Default> Default for Theme<K, P> {
+    fn default() -> Self {
+        Theme::new(P::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Color, Format, Pushable, Tag};
+    #[test]
+    fn theme_falls_back() {
+        let mut theme: Theme<&str, Format> = Theme::default();
+        theme.insert("error", Color::Red.bold());
+        let text = Spans::styled("error", "E1").then("note", " see");
+        assert_eq!(theme.paint(&text), "\x1b[1;31mE1\x1b[0m see");
+        assert_eq!(theme.paint(&text), theme.apply(&text).to_string());
+        theme.set_fallback(Color::Blue.normal());
+        assert_eq!(theme.remove(&"error"), Some(Color::Red.bold()));
+        assert_eq!(theme.paint(&text), "\x1b[34mE1 see\x1b[0m");
+    }
+    #[test]
+    fn apply_matches_paint_on_implicit_style() {
+        let mut theme: Theme<&str, Tag> = Theme::default();
+        theme.set_fallback(Tag::new("<f>", "</f>"));
+        let mut text: Spans<&str> = Default::default();
+        text.push(&"abc");
+        assert_eq!(theme.paint(&text), "<f>abc</f>");
+        assert_eq!(theme.paint(&text), theme.apply(&text).to_string());
+    }
+}
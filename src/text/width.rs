@@ -1,3 +1,4 @@
+use super::WidthPolicy;
 use std::iter::Sum;
 use std::ops::{Add, AddAssign};
 
@@ -77,7 +78,7 @@ pub trait BoundedWidth {
 
 impl BoundedWidth for String {
     fn bounded_width(&self) -> usize {
-        unicode_width::UnicodeWidthStr::width(self.as_str())
+        WidthPolicy::current().width(self)
     }
 }
 
@@ -87,9 +88,21 @@ impl HasWidth for String {
     }
 }
 
+impl BoundedWidth for &String {
+    fn bounded_width(&self) -> usize {
+        (**self).bounded_width()
+    }
+}
+
+impl HasWidth for &String {
+    fn width(&self) -> Width {
+        (**self).width()
+    }
+}
+
 impl BoundedWidth for &str {
     fn bounded_width(&self) -> usize {
-        unicode_width::UnicodeWidthStr::width(*self)
+        WidthPolicy::current().width(self)
     }
 }
 
@@ -99,6 +112,28 @@ impl HasWidth for &str {
     }
 }
 
+/// Support for returning the width of a text object including any markup that
+/// is visible when it is painted, such as tags displayed literally.
+pub trait PaintedWidth {
+    /// Return the unicode width of an object and its visible markup
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{PaintedWidth, Pushable, Span, Spans, Tag};
+    /// let bold = Tag::new("**", "**");
+    /// let mut spans: Spans<Tag> = Default::default();
+    /// spans.push(&Span::borrowed(&bold, "foo"));
+    /// assert_eq!(spans.painted_width(), 7);
+    /// ```
+    fn painted_width(&self) -> usize;
+}
+
+impl PaintedWidth for String {
+    fn painted_width(&self) -> usize {
+        self.bounded_width()
+    }
+}
+
 impl<T> BoundedWidth for Option<T>
 where
     T: BoundedWidth,
@@ -140,7 +175,7 @@ mod test {
     }
     #[test]
     fn sum() {
-        let v = vec![Width::Bounded(5), Width::Bounded(6), Width::Bounded(7)];
+        let v = [Width::Bounded(5), Width::Bounded(6), Width::Bounded(7)];
         let actual: Width = v.iter().cloned().sum();
         let expected = Width::Bounded(18);
         assert_eq!(expected, actual);
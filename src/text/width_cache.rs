@@ -0,0 +1,210 @@
+use crate::text::{bounds, HasWidth, RawText, Sliceable, Width, WidthPolicy, WidthSliceable};
+use std::collections::HashMap;
+use std::ops::RangeBounds;
+use std::sync::{Arc, Mutex, PoisonError};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The start byte and width of each grapheme of a string.
+type Graphemes = Arc<[(usize, usize)]>;
+
+/// A shared handle to a cache of measured strings, so that text repeated
+/// across widgets, such as separators and labels, is segmented into graphemes
+/// and measured only once per frame.
+///
+/// Clones of a cache share the same entries. Text is measured through the
+/// cache by wrapping it with [`WidthCache::wrap`]; the cache grows with each
+/// distinct string, so it should be cleared once a frame is drawn. Strings are
+/// measured with the [`WidthPolicy`] current on the calling thread, and the
+/// measurements under each policy are cached separately.
+///
+/// # Example
+/// ```
+/// use std::borrow::Cow;
+/// use stylish_stringlike::text::{Cached, Spans, Tag, WidthCache};
+/// use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle};
+/// let cache = WidthCache::new();
+/// let separator: Spans<Tag> = Spans::from(" | ");
+/// let truncation = TruncationStyle::Left("…");
+/// let mut hbox: HBox<Spans<Tag>> = HBox::new();
+/// for _ in 0..3 {
+///     let text: Cow<Cached<_>> = Cow::Owned(cache.wrap(&separator));
+///     hbox.push(Box::new(TextWidget::new(text, Cow::Borrowed(&truncation))));
+/// }
+/// assert_eq!(hbox.truncate(9).to_string(), " |  |  | ");
+/// assert_eq!(cache.len(), 1);
+/// cache.clear();
+/// assert!(cache.is_empty());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct WidthCache {
+    entries: Arc<Mutex<HashMap<usize, HashMap<String, Graphemes>>>>,
+}
+
+impl WidthCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Returns the graphemes of a string, measuring it with the current
+    /// policy if it is not cached.
+    fn graphemes(&self, s: &str) -> Graphemes {
+        let policy = WidthPolicy::current();
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let entries = entries.entry(policy.cache_key()).or_default();
+        if let Some(graphemes) = entries.get(s) {
+            return graphemes.clone();
+        }
+        let graphemes: Graphemes = s
+            .grapheme_indices(true)
+            .map(|(byte, grapheme)| (byte, policy.grapheme_width(grapheme)))
+            .collect();
+        entries.insert(s.to_string(), graphemes.clone());
+        graphemes
+    }
+    /// Returns the width of a string.
+    pub fn width(&self, s: &str) -> usize {
+        self.graphemes(s).iter().map(|(_, width)| width).sum()
+    }
+    /// Slices a text object by width like [`WidthSliceable::slice_width`],
+    /// using the cached graphemes of its raw text.
+    pub fn slice_width<T, R>(&self, text: &T, range: R) -> Option<T>
+    where
+        T: RawText + Sliceable,
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = bounds::normalize(&range);
+        let mut start_byte = None;
+        let mut end_byte = None;
+        let mut current_width = 0;
+        for &(byte, grapheme_width) in self.graphemes(text.raw_ref()).iter() {
+            let in_range = bounds::contains(start, end, current_width, grapheme_width);
+            current_width += grapheme_width;
+            match (in_range, start_byte) {
+                (true, None) => start_byte = Some(byte),
+                (false, Some(_)) => {
+                    end_byte = Some(byte);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        match (start_byte, end_byte) {
+            (Some(s), Some(e)) => text.slice(s..e),
+            (Some(s), None) => text.slice(s..),
+            (None, Some(e)) => text.slice(..e),
+            (None, None) => None,
+        }
+    }
+    /// Wraps a text object so that it is measured and sliced through this
+    /// cache.
+    pub fn wrap<'a, T>(&self, text: &'a T) -> Cached<'a, T> {
+        Cached {
+            text,
+            cache: self.clone(),
+        }
+    }
+    /// Removes all measured strings.
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+    /// Returns the number of distinct strings measured, counting strings
+    /// measured under several policies once for each.
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .map(HashMap::len)
+            .sum()
+    }
+    /// Returns whether no strings have been measured since the cache was
+    /// created or cleared.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A text object measured and sliced through a [`WidthCache`], made with
+/// [`WidthCache::wrap`].
+#[derive(Debug)]
+pub struct Cached<'a, T> {
+    text: &'a T,
+    cache: WidthCache,
+}
+
+impl<T> Clone for Cached<'_, T> {
+    fn clone(&self) -> Self {
+        Cached {
+            text: self.text,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl<T: RawText> HasWidth for Cached<'_, T> {
+    fn width(&self) -> Width {
+        Width::Bounded(self.cache.width(self.text.raw_ref()))
+    }
+}
+
+impl<T: RawText + Sliceable> WidthSliceable for Cached<'_, T> {
+    type Output = T;
+    fn slice_width<R>(&self, range: R) -> Option<Self::Output>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.cache.slice_width(self.text, range)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Pushable, Span, Spans, Tag};
+    #[test]
+    fn cached_slices_match() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans.push(&Span::borrowed(&fmt_1, "a🙈b"));
+        spans.push(&Span::borrowed(&fmt_2, "🙉cd"));
+        let cache = WidthCache::new();
+        let cached = cache.wrap(&spans);
+        assert_eq!(spans.width(), cached.width());
+        for start in 0..8 {
+            for end in start..9 {
+                assert_eq!(
+                    spans.slice_width(start..end),
+                    cached.slice_width(start..end),
+                    "{}..{}",
+                    start,
+                    end
+                );
+            }
+            assert_eq!(spans.slice_width(start..), cached.slice_width(start..));
+        }
+        assert_eq!(cache.len(), 1);
+    }
+    #[test]
+    fn cache_shared_between_clones() {
+        let cache = WidthCache::new();
+        let other = cache.clone();
+        assert_eq!(cache.width("foo"), 3);
+        assert_eq!(other.width("🙈🙉"), 4);
+        assert_eq!(other.width("foo"), 3);
+        assert_eq!(cache.len(), 2);
+        other.clear();
+        assert!(cache.is_empty());
+    }
+    #[test]
+    fn cache_keyed_by_policy() {
+        let cache = WidthCache::new();
+        assert_eq!(cache.width("α→β"), 3);
+        assert_eq!(WidthPolicy::Wide.scope(|| cache.width("α→β")), 6);
+        assert_eq!(cache.width("α→β"), 3);
+        assert_eq!(cache.len(), 2);
+    }
+}
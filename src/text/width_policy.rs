@@ -0,0 +1,157 @@
+use std::cell::Cell;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+thread_local! {
+    static CURRENT: Cell<WidthPolicy> = const { Cell::new(WidthPolicy::Narrow) };
+}
+
+/// How many columns text takes up in a terminal.
+///
+/// Characters of ambiguous East Asian width, such as `α` or `○`, are one
+/// column wide in most terminals but two columns wide in terminals set up for
+/// Chinese, Japanese or Korean text. Every width computed by this crate, from
+/// [`crate::text::BoundedWidth`] and [`crate::text::WidthSliceable`] up to
+/// the layout of a [`crate::widget::HBox`], uses the policy that is current
+/// on the calling thread, which is [`WidthPolicy::Narrow`] unless changed with
+/// [`WidthPolicy::scope`]. [`crate::text::Spans`] caches its width only
+/// under the default policy and measures it again under any other, while
+/// [`crate::text::WidthCache`] keeps a separate cache for each policy, so
+/// text shared between threads with different policies is measured
+/// correctly on each. To measure text under a policy without making it
+/// current, pass it explicitly with [`WidthPolicy::width`].
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{BoundedWidth, WidthPolicy, WidthSliceable};
+/// let text = "α→β";
+/// assert_eq!(text.bounded_width(), 3);
+/// WidthPolicy::Wide.scope(|| {
+///     assert_eq!(text.bounded_width(), 6);
+///     assert_eq!(String::from(text).slice_width(..4), Some(String::from("α→")));
+/// });
+/// // Count every grapheme as one column.
+/// let policy = WidthPolicy::Custom(|_| 1);
+/// assert_eq!(policy.scope(|| "🙈🙉".bounded_width()), 2);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub enum WidthPolicy {
+    /// Ambiguous-width characters are one column wide.
+    #[default]
+    Narrow,
+    /// Ambiguous-width characters are two columns wide, as in CJK terminals.
+    Wide,
+    /// Each grapheme is as wide as a function of it says.
+    Custom(fn(&str) -> usize),
+}
+
+impl WidthPolicy {
+    /// Returns the policy current on this thread.
+    pub fn current() -> Self {
+        CURRENT.with(Cell::get)
+    }
+    /// Runs a function with this policy current on this thread, restoring the
+    /// previous policy afterwards.
+    pub fn scope<F, R>(self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        /// Restores the previous policy even if `f` panics.
+        struct Restore(WidthPolicy);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+        let _restore = Restore(CURRENT.with(|current| current.replace(self)));
+        f()
+    }
+    /// Returns the width of a string under this policy, which is the sum of
+    /// the widths of its graphemes.
+    pub fn width(&self, s: &str) -> usize {
+        match self {
+            WidthPolicy::Narrow if s.is_ascii() => s.width(),
+            _ => s.graphemes(true).map(|g| self.grapheme_width(g)).sum(),
+        }
+    }
+    /// Returns the width of a single grapheme under this policy. Emoji
+    /// sequences, such as flags, emoji joined with zero-width joiners and
+    /// emoji with presentation selectors, are drawn as one two-column glyph.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::WidthPolicy;
+    /// let policy = WidthPolicy::Narrow;
+    /// assert_eq!(policy.grapheme_width("👨‍👩‍👧"), 2);
+    /// assert_eq!(policy.grapheme_width("🇫🇷"), 2);
+    /// assert_eq!(policy.grapheme_width("e\u{301}"), 1);
+    /// ```
+    pub fn grapheme_width(&self, grapheme: &str) -> usize {
+        match self {
+            WidthPolicy::Custom(width) => width(grapheme),
+            _ if is_emoji_sequence(grapheme) => 2,
+            WidthPolicy::Narrow => grapheme.width(),
+            WidthPolicy::Wide => grapheme.width_cjk(),
+        }
+    }
+    /// Identifies the policy for caches of measured widths. Custom policies
+    /// are told apart by their function.
+    pub(crate) fn cache_key(&self) -> usize {
+        match self {
+            WidthPolicy::Narrow => 0,
+            WidthPolicy::Wide => 1,
+            WidthPolicy::Custom(width) => *width as usize,
+        }
+    }
+    /// Whether this is the default policy, whose widths may be cached.
+    pub(crate) fn is_default(&self) -> bool {
+        matches!(self, WidthPolicy::Narrow)
+    }
+}
+
+/// Whether a grapheme is a sequence of code points drawn as a single emoji.
+fn is_emoji_sequence(grapheme: &str) -> bool {
+    let mut chars = grapheme.chars();
+    let (first, second) = match (chars.next(), chars.next()) {
+        (Some(first), Some(second)) => (first, second),
+        _ => return false,
+    };
+    let regional_indicator = |c: char| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c);
+    (regional_indicator(first) && regional_indicator(second))
+        || grapheme.contains('\u{FE0F}')
+        || (grapheme.contains('\u{200D}') && first.width() == Some(2))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{BoundedWidth, RawText, Spans, Tag};
+    #[test]
+    fn scope_restores_policy() {
+        let spans: Spans<Tag> = Spans::from("±1");
+        assert_eq!(spans.bounded_width(), 2);
+        let wide = WidthPolicy::Wide.scope(|| {
+            let inner = WidthPolicy::Narrow.scope(|| spans.bounded_width());
+            (inner, spans.bounded_width())
+        });
+        assert_eq!(wide, (2, 3));
+        assert!(WidthPolicy::current().is_default());
+        let result = std::panic::catch_unwind(|| WidthPolicy::Wide.scope(|| panic!()));
+        assert!(result.is_err());
+        assert!(WidthPolicy::current().is_default());
+    }
+    #[test]
+    fn cached_width_per_thread_policy() {
+        let spans: Spans<Tag> = Spans::from("±1");
+        assert_eq!(spans.bounded_width(), 2);
+        let wide = std::thread::scope(|scope| {
+            scope
+                .spawn(|| WidthPolicy::Wide.scope(|| spans.bounded_width()))
+                .join()
+                .unwrap()
+        });
+        assert_eq!(wide, 3);
+        assert_eq!(WidthPolicy::Wide.width(spans.raw_ref()), 3);
+        assert_eq!(spans.bounded_width(), 2);
+    }
+}
@@ -1,7 +1,9 @@
-use crate::text::{RawText, Sliceable};
-use std::ops::RangeBounds;
+use crate::text::{
+    bounds, BoundedWidth, HasWidth, Pushable, RawText, Sliceable, Width, WidthPolicy,
+};
+use crate::Error;
+use std::ops::{Bound, RangeBounds};
 use unicode_segmentation::UnicodeSegmentation;
-use unicode_width::UnicodeWidthStr;
 
 /// Provides a function for slicing by grapheme width rather than bytes.
 ///
@@ -28,6 +30,124 @@ pub trait WidthSliceable {
     fn slice_width<R>(&self, range: R) -> Option<Self::Output>
     where
         R: RangeBounds<usize>;
+    /// Slice an object by width, returning why nothing could be sliced if
+    /// [`WidthSliceable::slice_width`] would return [`None`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stylish_stringlike::Error;
+    /// use stylish_stringlike::text::WidthSliceable;
+    /// let bar = String::from("🙈🙉🙊");
+    /// assert_eq!(Ok(String::from("🙈")), bar.try_slice_width(..2));
+    /// assert_eq!(Err(Error::WidthTooSmall { width: 2 }), bar.try_slice_width(1..3));
+    /// assert_eq!(Err(Error::OutOfBounds { index: 7, len: 6 }), bar.try_slice_width(7..));
+    /// ```
+    fn try_slice_width<R>(&self, range: R) -> Result<Self::Output, Error>
+    where
+        Self: HasWidth,
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = bounds::normalize(&range);
+        let len = match self.width() {
+            Width::Bounded(w) => Some(w),
+            Width::Unbounded => None,
+        };
+        match (end, len) {
+            (Some(end), _) if start > end => return Err(Error::InvertedRange { start, end }),
+            (_, Some(len)) if start > len => return Err(Error::OutOfBounds { index: start, len }),
+            (None, None) => return Err(Error::UnboundedRange),
+            _ => {}
+        }
+        self.slice_width(range).ok_or_else(|| Error::WidthTooSmall {
+            width: end.or(len).map_or(0, |end| end.saturating_sub(start)),
+        })
+    }
+    /// Pushes the graphemes within a range of columns onto text, as pushing
+    /// the output of [`WidthSliceable::slice_width`] would, and returns the
+    /// width pushed, or [`None`] if nothing could be sliced. Text that can
+    /// copy the columns straight from the object does so without building the
+    /// slice first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stylish_stringlike::text::WidthSliceable;
+    /// let monkeys = String::from("🙈🙉🙊");
+    /// let mut output = String::from("> ");
+    /// assert_eq!(monkeys.push_width(1..5, &mut output), Some(2));
+    /// assert_eq!(output, "> 🙉");
+    /// assert_eq!(monkeys.push_width(1..3, &mut output), None);
+    /// ```
+    fn push_width<R>(&self, range: R, output: &mut Self::Output) -> Option<usize>
+    where
+        R: RangeBounds<usize>,
+        Self::Output: Pushable<Self::Output> + BoundedWidth,
+    {
+        let slice = self.slice_width(range)?;
+        output.push(&slice);
+        Some(slice.bounded_width())
+    }
+    /// Returns the width of the graphemes within a range of columns, which is
+    /// the width of the output of [`WidthSliceable::slice_width`]. Text that
+    /// can measure the columns without slicing does so.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stylish_stringlike::text::WidthSliceable;
+    /// let monkeys = String::from("🙈🙉🙊");
+    /// assert_eq!(monkeys.width_within(1..), 4);
+    /// assert_eq!(monkeys.width_within(1..3), 0);
+    /// ```
+    fn width_within<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<usize>,
+        Self::Output: BoundedWidth,
+    {
+        self.slice_width(range)
+            .as_ref()
+            .map_or(0, BoundedWidth::bounded_width)
+    }
+    /// Returns the first `width` columns of an object. This is
+    /// `slice_width(..width)`.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::WidthSliceable;
+    /// let foo = String::from("foobar");
+    /// assert_eq!(foo.take_width(3), Some(String::from("foo")));
+    /// assert_eq!(foo.skip_width(3), Some(String::from("bar")));
+    /// ```
+    fn take_width(&self, width: usize) -> Option<Self::Output> {
+        self.slice_width(..width)
+    }
+    /// Returns an object without its first `width` columns. This is
+    /// `slice_width(width..)`.
+    fn skip_width(&self, width: usize) -> Option<Self::Output> {
+        self.slice_width(width..)
+    }
+    /// Splits an object into the graphemes that fit in the first `width`
+    /// columns and the rest. A wide grapheme that straddles the split goes in
+    /// the rest, so no text is lost.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::WidthSliceable;
+    /// let monkeys = String::from("🙈🙉🙊");
+    /// let (head, tail) = monkeys.split_at_width(3);
+    /// assert_eq!(head, Some(String::from("🙈")));
+    /// assert_eq!(tail, Some(String::from("🙉🙊")));
+    /// assert_eq!(monkeys.split_at_width(1), (None, Some(monkeys.clone())));
+    /// ```
+    fn split_at_width(&self, width: usize) -> (Option<Self::Output>, Option<Self::Output>)
+    where
+        Self::Output: BoundedWidth,
+    {
+        let head = self.take_width(width);
+        let head_width = head.as_ref().map_or(0, BoundedWidth::bounded_width);
+        (head, self.skip_width(head_width))
+    }
 }
 
 impl<T> WidthSliceable for T
@@ -40,39 +160,84 @@ where
         Self: Sized,
         R: RangeBounds<usize>,
     {
-        let mut start_byte = None;
-        let mut end_byte = None;
-        let mut current_width = 0;
-        let mut current_byte = 0;
-        for grapheme in self.raw().graphemes(true) {
-            let grapheme_width = grapheme.width();
-            let in_range = {
-                let mut in_range = true;
-                for w in current_width..current_width + grapheme_width {
-                    if !range.contains(&w) {
-                        in_range = false;
-                        break;
-                    }
-                }
-                in_range
-            };
-            current_width += grapheme_width;
-            match (in_range, start_byte) {
-                (true, None) => start_byte = Some(current_byte),
-                (false, Some(_)) => {
-                    end_byte = Some(current_byte);
-                    break;
-                }
-                _ => {}
+        let (bytes, _) = column_bytes(self.raw_ref(), &range)?;
+        self.slice(bytes)
+    }
+    /// Copies the bytes of the columns onto the output with
+    /// [`Pushable::push_slice`].
+    fn push_width<R>(&self, range: R, output: &mut Self::Output) -> Option<usize>
+    where
+        R: RangeBounds<usize>,
+        Self::Output: Pushable<Self::Output> + BoundedWidth,
+    {
+        let (bytes, width) = column_bytes(self.raw_ref(), &range)?;
+        output.push_slice(self, bytes).then_some(width)
+    }
+    fn width_within<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<usize>,
+    {
+        column_bytes(self.raw_ref(), &range).map_or(0, |(_, width)| width)
+    }
+}
+
+/// A range of bytes, as taken by [`Sliceable::slice`].
+type Bytes = (Bound<usize>, Bound<usize>);
+
+/// Finds the bytes of the graphemes of text that lie within a range of
+/// columns, along with the width of those graphemes. Returns [`None`] if no
+/// grapheme lies within the range.
+fn column_bytes<R>(text: &str, range: &R) -> Option<(Bytes, usize)>
+where
+    R: RangeBounds<usize> + ?Sized,
+{
+    let (start, end) = bounds::normalize(range);
+    let mut start_byte = None;
+    let mut end_byte = None;
+    let mut current_width = 0;
+    let mut current_byte = 0;
+    let mut width = 0;
+    let policy = WidthPolicy::current();
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = policy.grapheme_width(grapheme);
+        let in_range = bounds::contains(start, end, current_width, grapheme_width);
+        current_width += grapheme_width;
+        match (in_range, start_byte) {
+            (true, None) => start_byte = Some(current_byte),
+            (false, Some(_)) => {
+                end_byte = Some(current_byte);
+                break;
             }
-            current_byte += grapheme.len();
+            _ => {}
         }
-        match (start_byte, end_byte) {
-            (Some(s), Some(e)) => self.slice(s..e),
-            (Some(s), None) => self.slice(s..),
-            (None, Some(e)) => self.slice(..e),
-            (None, None) => None,
+        if in_range {
+            width += grapheme_width;
         }
+        current_byte += grapheme.len();
+    }
+    let end = end_byte.map_or(Bound::Unbounded, Bound::Excluded);
+    Some(((Bound::Included(start_byte?), end), width))
+}
+
+impl WidthSliceable for &String {
+    type Output = String;
+    fn slice_width<R>(&self, range: R) -> Option<Self::Output>
+    where
+        R: RangeBounds<usize>,
+    {
+        (**self).slice_width(range)
+    }
+    fn push_width<R>(&self, range: R, output: &mut Self::Output) -> Option<usize>
+    where
+        R: RangeBounds<usize>,
+    {
+        (**self).push_width(range, output)
+    }
+    fn width_within<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<usize>,
+    {
+        (**self).width_within(range)
     }
 }
 
@@ -90,4 +255,21 @@ where
             None => None,
         }
     }
+    fn push_width<R>(&self, range: R, output: &mut Self::Output) -> Option<usize>
+    where
+        R: RangeBounds<usize>,
+        Self::Output: Pushable<Self::Output> + BoundedWidth,
+    {
+        match self {
+            Some(t) => t.push_width(range, output),
+            None => None,
+        }
+    }
+    fn width_within<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<usize>,
+        Self::Output: BoundedWidth,
+    {
+        self.as_ref().map_or(0, |t| t.width_within(range))
+    }
 }
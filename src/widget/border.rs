@@ -0,0 +1,146 @@
+use crate::text::{BoundedWidth, Pushable, Span, Spans, WidthSliceable};
+use crate::widget::{Fitable, Repeat};
+use std::borrow::Cow;
+
+/// A frame drawn around another widget, such as
+/// ```text
+/// ┌─────┐
+/// │hello│
+/// └─────┘
+/// ```
+pub struct Border<'a, T: Clone> {
+    inner: Box<dyn Fitable<Spans<T>> + 'a>,
+    top_left: Spans<T>,
+    top_right: Spans<T>,
+    bottom_left: Spans<T>,
+    bottom_right: Spans<T>,
+    horizontal: Repeat<'a, Spans<T>>,
+    vertical: Spans<T>,
+}
+
+impl<'a, T: Clone + Default + PartialEq> Border<'a, T> {
+    /// Creates a border of light box-drawing characters in a given style.
+    pub fn new(inner: Box<dyn Fitable<Spans<T>> + 'a>, style: &T) -> Self {
+        let make_spans = |text: &str| {
+            let mut spans: Spans<T> = Default::default();
+            spans.push(&Span::borrowed(style, text));
+            spans
+        };
+        Border {
+            inner,
+            top_left: make_spans("┌"),
+            top_right: make_spans("┐"),
+            bottom_left: make_spans("└"),
+            bottom_right: make_spans("┘"),
+            horizontal: Repeat::new(make_spans("─")),
+            vertical: make_spans("│"),
+        }
+    }
+    /// Sets the corners, clockwise from the top left.
+    pub fn set_corners(&mut self, corners: [Spans<T>; 4]) {
+        let [top_left, top_right, bottom_right, bottom_left] = corners;
+        self.top_left = top_left;
+        self.top_right = top_right;
+        self.bottom_right = bottom_right;
+        self.bottom_left = bottom_left;
+    }
+    /// Sets the text repeated along the top and bottom edges.
+    pub fn set_horizontal(&mut self, horizontal: Spans<T>) {
+        self.horizontal = Repeat::new(horizontal);
+    }
+    /// Sets the text on the left and right edges.
+    pub fn set_vertical(&mut self, vertical: Spans<T>) {
+        self.vertical = vertical;
+    }
+    /// Draws the border and its contents in a given width, truncating the
+    /// contents with their own strategy. Returns the top edge, the contents
+    /// and the bottom edge.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{Border, TextWidget, TruncationStyle};
+    /// let plain = Tag::new("", "");
+    /// let text: Spans<Tag> = Spans::from("hello world");
+    /// let truncation = TruncationStyle::Left("…");
+    /// let inner = TextWidget::new(Cow::Borrowed(&text), Cow::Borrowed(&truncation));
+    /// let border = Border::new(Box::new(inner), &plain);
+    /// let rows: Vec<String> = border.truncate(8).iter().map(|row| row.to_string()).collect();
+    /// assert_eq!(rows, vec!["┌──────┐", "│hello…│", "└──────┘"]);
+    /// ```
+    pub fn truncate(&self, width: usize) -> Vec<Spans<T>> {
+        let edge = |left: &Spans<T>, right: &Spans<T>| {
+            let mut row = left.clone();
+            let inner_width = width.saturating_sub(left.bounded_width() + right.bounded_width());
+            row.push(&self.horizontal.slice_width(..inner_width));
+            row.push(right);
+            row
+        };
+        let mut middle = self.vertical.clone();
+        let inner_width = width.saturating_sub(2 * self.vertical.bounded_width());
+        let inner = self.inner.truncate(inner_width);
+        let padding = inner_width.saturating_sub(inner.bounded_width());
+        middle.push(&inner);
+        if padding > 0 {
+            middle.push(&Span::new(
+                Cow::Owned(T::default()),
+                Cow::Owned(" ".repeat(padding)),
+            ));
+        }
+        middle.push(&self.vertical);
+        vec![
+            edge(&self.top_left, &self.top_right),
+            middle,
+            edge(&self.bottom_left, &self.bottom_right),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    use crate::widget::fixtures::make_spans;
+    use crate::widget::{TextWidget, TruncationStyle};
+    #[test]
+    fn border_truncates_inner() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_b = Tag::new("<b>", "</b>");
+        let text = make_spans(&fmt_1, "0123456789");
+        let truncation = TruncationStyle::Left(make_spans(&fmt_1, "…"));
+        let inner = TextWidget::new(Cow::Borrowed(&text), Cow::Borrowed(&truncation));
+        let border = Border::new(Box::new(inner), &fmt_b);
+        let actual: Vec<String> = border.truncate(6).iter().map(ToString::to_string).collect();
+        let expected = vec![
+            String::from("<b>┌────┐</b>"),
+            String::from("<b>│</b><1>012…</1><b>│</b>"),
+            String::from("<b>└────┘</b>"),
+        ];
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn border_pads_inner() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_b = Tag::new("<b>", "</b>");
+        let text = make_spans(&fmt_1, "01");
+        let truncation = TruncationStyle::Left(make_spans(&fmt_1, "…"));
+        let inner = TextWidget::new(Cow::Borrowed(&text), Cow::Borrowed(&truncation));
+        let mut border = Border::new(Box::new(inner), &fmt_b);
+        border.set_corners([
+            make_spans(&fmt_b, "+"),
+            make_spans(&fmt_b, "+"),
+            make_spans(&fmt_b, "+"),
+            make_spans(&fmt_b, "+"),
+        ]);
+        border.set_horizontal(make_spans(&fmt_b, "-="));
+        border.set_vertical(make_spans(&fmt_b, "|"));
+        let actual: Vec<String> = border.truncate(7).iter().map(ToString::to_string).collect();
+        let expected = vec![
+            String::from("<b>+-=-=-+</b>"),
+            String::from("<b>|</b><1>01</1>   <b>|</b>"),
+            String::from("<b>+-=-=-+</b>"),
+        ];
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::text::{bounds, Rounding, Spans};
+use std::borrow::Cow;
+use std::ops::RangeBounds;
+
+/// Whether a label marks the main cause of a diagnostic or context for it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LabelKind {
+    /// The main cause, underlined with the primary marker
+    Primary,
+    /// Context, underlined with the secondary marker
+    Secondary,
+}
+
+/// A byte range of the source and the message explaining it.
+#[derive(Clone, Debug)]
+struct Label {
+    start: usize,
+    end: usize,
+    kind: LabelKind,
+    message: String,
+}
+
+/// Draws a line of source with labelled ranges underlined beneath it, in the
+/// way compilers report errors:
+/// ```text
+/// let x = add(1, "2");
+///         ---    ^^^ expected a number
+///         in this call
+/// ```
+/// Primary labels are drawn over secondary labels where they overlap. The
+/// message of the label that ends last follows its underline, and the others
+/// are placed under the start of their labels, from right to left.
+///
+/// Columns are measured with the current [`crate::text::WidthPolicy`], so
+/// tabs should be expanded first with [`Spans::expand_tabs`].
+pub struct Diagnostic<'a, T: Clone> {
+    source: Cow<'a, Spans<T>>,
+    labels: Vec<Label>,
+    primary: (char, T),
+    secondary: (char, T),
+}
+
+impl<'a, T: Clone + Default + PartialEq> Diagnostic<'a, T> {
+    /// Creates a diagnostic for a line of source, with primary labels drawn
+    /// with `^` and secondary labels with `-` in the given styles.
+    pub fn new(source: Cow<'a, Spans<T>>, primary: T, secondary: T) -> Self {
+        Diagnostic {
+            source,
+            labels: vec![],
+            primary: ('^', primary),
+            secondary: ('-', secondary),
+        }
+    }
+    /// Sets the single-column characters that underline primary and
+    /// secondary labels.
+    pub fn set_markers(&mut self, primary: char, secondary: char) {
+        self.primary.0 = primary;
+        self.secondary.0 = secondary;
+    }
+    /// Labels a byte range of the source. An empty range is marked with a
+    /// single column, as for a missing token. The message may be empty.
+    pub fn label<R>(&mut self, range: R, kind: LabelKind, message: &str)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = bounds::normalize_within(&range, self.source.len());
+        self.labels.push(Label {
+            start,
+            end: end.max(start),
+            kind,
+            message: message.to_string(),
+        });
+    }
+    fn marker(&self, kind: LabelKind) -> &(char, T) {
+        match kind {
+            LabelKind::Primary => &self.primary,
+            LabelKind::Secondary => &self.secondary,
+        }
+    }
+    /// Returns the columns a label underlines.
+    fn columns(&self, label: &Label) -> (usize, usize) {
+        let start = self.source.byte_to_width(label.start, Rounding::Floor);
+        let end = self.source.byte_to_width(label.end, Rounding::Ceil);
+        (start, end.max(start + 1))
+    }
+    /// Draws the source line, the underlines and the messages that do not
+    /// fit beside the underlines.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{Diagnostic, LabelKind};
+    /// let source: Spans<Tag> = Spans::from("let x = add(1, \"2\");");
+    /// let error = Tag::new("<e>", "</e>");
+    /// let note = Tag::new("<n>", "</n>");
+    /// let mut diagnostic = Diagnostic::new(Cow::Borrowed(&source), error, note);
+    /// diagnostic.label(15..18, LabelKind::Primary, "expected a number");
+    /// diagnostic.label(8..11, LabelKind::Secondary, "in this call");
+    /// let lines: Vec<String> = diagnostic.render().iter().map(|line| line.to_string()).collect();
+    /// assert_eq!(
+    ///     lines,
+    ///     vec![
+    ///         "let x = add(1, \"2\");",
+    ///         "        <n>---</n>    <e>^^^ expected a number</e>",
+    ///         "        <n>in this call</n>",
+    ///     ]
+    /// );
+    /// ```
+    pub fn render(&self) -> Vec<Spans<T>> {
+        let mut cells: Vec<Option<LabelKind>> = vec![];
+        let mut placed: Vec<(usize, usize, &Label)> = self
+            .labels
+            .iter()
+            .map(|label| {
+                let (start, end) = self.columns(label);
+                (start, end, label)
+            })
+            .collect();
+        for kind in [LabelKind::Secondary, LabelKind::Primary].iter() {
+            for (start, end, _) in placed.iter().filter(|(_, _, label)| label.kind == *kind) {
+                if cells.len() < *end {
+                    cells.resize(*end, None);
+                }
+                for cell in &mut cells[*start..*end] {
+                    *cell = Some(*kind);
+                }
+            }
+        }
+        let mut underline: Spans<T> = Spans::default();
+        for cell in &cells {
+            match cell {
+                Some(kind) => {
+                    let (marker, style) = self.marker(*kind);
+                    underline.push_styled(style.clone(), marker.encode_utf8(&mut [0; 4]));
+                }
+                None => underline.push_styled(T::default(), " "),
+            }
+        }
+        // Messages are placed from the label that ends last, leftwards.
+        placed.retain(|(_, _, label)| !label.message.is_empty());
+        placed.sort_by_key(|(start, end, _)| (*end, *start));
+        let mut lines = vec![self.source.as_ref().clone()];
+        let mut below = vec![];
+        if let Some(&(_, end, label)) = placed.last() {
+            if end == cells.len() {
+                let style = &self.marker(label.kind).1;
+                underline.push_styled(style.clone(), " ");
+                underline.push_styled(style.clone(), &label.message);
+                placed.pop();
+            }
+        }
+        placed.sort_by_key(|(start, _, _)| *start);
+        for (start, _, label) in placed.iter().rev() {
+            let mut line: Spans<T> = Spans::default();
+            line.push_styled(T::default(), &" ".repeat(*start));
+            line.push_styled(self.marker(label.kind).1.clone(), &label.message);
+            below.push(line);
+        }
+        lines.push(underline);
+        lines.extend(below);
+        lines
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::Tag;
+    #[test]
+    fn overlapping_labels() {
+        let source: Spans<Tag> = Spans::from("a🙈cd");
+        let primary = Tag::new("<p>", "</p>");
+        let secondary = Tag::new("<s>", "</s>");
+        let mut diagnostic = Diagnostic::new(Cow::Borrowed(&source), primary, secondary);
+        diagnostic.set_markers('~', '.');
+        diagnostic.label(0.., LabelKind::Secondary, "whole");
+        diagnostic.label(1..5, LabelKind::Primary, "wide");
+        diagnostic.label(7..7, LabelKind::Primary, "");
+        let lines: Vec<String> = diagnostic.render().iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "a🙈cd",
+                "<s>.</s><p>~~</p><s>..</s><p>~</p>",
+                " <p>wide</p>",
+                "<s>whole</s>",
+            ]
+        );
+    }
+}
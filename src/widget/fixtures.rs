@@ -0,0 +1,10 @@
+//! Fixtures shared by the widget tests.
+use crate::text::{Pushable, Span, Spans, Tag};
+use std::borrow::Cow;
+
+/// Makes text in a single style.
+pub(crate) fn make_spans(style: &Tag, text: &str) -> Spans<Tag> {
+    let mut spans: Spans<Tag> = Default::default();
+    spans.push(&Span::new(Cow::Borrowed(style), Cow::Borrowed(text)));
+    spans
+}
@@ -0,0 +1,181 @@
+use super::hbox::arrange;
+use crate::text::{HasWidth, Pushable, Width, WidthSliceable};
+use crate::widget::{Distribution, Fitable, Measure, Truncateable, TruncationStrategy};
+use std::borrow::Cow;
+
+/// Several widgets truncated together as a single unit.
+///
+/// An [`crate::widget::HBox`] shrinks each of its elements independently, so a
+/// label and its value may each lose their ends. A group lays out its elements
+/// at their full widths first, and then truncates the result as one piece of
+/// text, so the pair shrinks together:
+/// ```text
+/// HBox:  Sp… 1…
+/// Group: Spee…
+/// ```
+/// Elements of unbounded width share whatever width is left over by the
+/// others.
+pub struct Group<'a, T: Truncateable, U: Clone> {
+    elements: Vec<Box<dyn Fitable<T> + 'a>>,
+    truncation_strategy: Cow<'a, U>,
+}
+
+impl<'a, T: Truncateable, U: Clone> Group<'a, T, U> {
+    /// Creates an empty group, truncated together by a strategy.
+    pub fn new(truncation_strategy: Cow<'a, U>) -> Self {
+        Group {
+            elements: vec![],
+            truncation_strategy,
+        }
+    }
+    /// Adds an element.
+    pub fn push(&mut self, element: Box<dyn Fitable<T> + 'a>) {
+        self.elements.push(element);
+    }
+}
+
+impl<'a, T: Truncateable, U: Clone> HasWidth for Group<'a, T, U> {
+    fn width(&self) -> Width {
+        self.elements.iter().map(|element| element.width()).fold(
+            Width::Bounded(0),
+            |total, width| match (total, width) {
+                (Width::Bounded(a), Width::Bounded(b)) => Width::Bounded(a + b),
+                _ => Width::Unbounded,
+            },
+        )
+    }
+}
+
+impl<'a, T, U> Fitable<T> for Group<'a, T, U>
+where
+    T: Truncateable + WidthSliceable<Output = T> + Pushable<T> + Default,
+    U: Clone + TruncationStrategy<T>,
+{
+    /// Lays out the elements at their full widths and truncates them together.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{Fitable, Group, TextWidget, TruncationStyle};
+    /// let label: Spans<Tag> = Spans::from("Speed: ");
+    /// let value: Spans<Tag> = Spans::from("12 km/h");
+    /// let truncation = TruncationStyle::Left("…");
+    /// let mut group = Group::new(Cow::Borrowed(&truncation));
+    /// group.push(Box::new(TextWidget::new(Cow::Borrowed(&label), Cow::Borrowed(&truncation))));
+    /// group.push(Box::new(TextWidget::new(Cow::Borrowed(&value), Cow::Borrowed(&truncation))));
+    /// assert_eq!(group.truncate(14).unwrap().to_string(), "Speed: 12 km/h");
+    /// assert_eq!(group.truncate(10).unwrap().to_string(), "Speed: 12…");
+    /// ```
+    fn truncate(&self, width: usize) -> Option<T> {
+        if width == 0 {
+            return None;
+        }
+        let measures: Vec<Measure> = self.elements.iter().map(|e| e.measure(width)).collect();
+        let natural: usize = measures
+            .iter()
+            .map(|measure| match measure.preferred {
+                Width::Bounded(w) => w,
+                Width::Unbounded => 0,
+            })
+            .sum();
+        let widths = arrange(&measures, natural.max(width), Distribution::FirstWins);
+        let mut joined: T = Default::default();
+        for (element, width) in self.elements.iter().zip(widths) {
+            if let Some(output) = element.truncate(width) {
+                joined.push(&output);
+            }
+        }
+        self.truncation_strategy.truncate(&joined, width)
+    }
+    /// Measures the group as the sum of its elements.
+    fn measure(&self, width_hint: usize) -> Measure {
+        let measures: Vec<Measure> = self
+            .elements
+            .iter()
+            .map(|element| element.measure(width_hint))
+            .collect();
+        Measure {
+            min: measures.iter().map(|m| m.min).sum(),
+            preferred: measures.iter().map(|m| m.preferred).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    use crate::widget::fixtures::make_spans;
+    use crate::widget::{HBox, PathWidget, Spacer, TextWidget, TruncationStyle};
+    #[test]
+    fn group_shrinks_as_unit() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let fmt_3 = Tag::new("<3>", "</3>");
+        let label = make_spans(&fmt_1, "ab:");
+        let value = make_spans(&fmt_2, "cdef");
+        let other = make_spans(&fmt_3, "ghijklm");
+        let truncation = TruncationStyle::Left(make_spans(&fmt_3, "…"));
+        let mut group = Group::new(Cow::Borrowed(&truncation));
+        group.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&label),
+            Cow::Borrowed(&truncation),
+        )));
+        group.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&value),
+            Cow::Borrowed(&truncation),
+        )));
+        assert_eq!(Width::Bounded(7), group.width());
+        let mut hbox = HBox::new();
+        hbox.push(Box::new(group));
+        hbox.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&other),
+            Cow::Borrowed(&truncation),
+        )));
+        let expected = "<1>ab:</1><2>c</2><3>…ghi…</3>";
+        assert_eq!(expected, hbox.truncate(9).to_string());
+    }
+    #[test]
+    fn group_unbounded() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let label = make_spans(&fmt_1, "ab");
+        let value = make_spans(&fmt_2, "cd");
+        let truncation = TruncationStyle::Left(make_spans(&fmt_1, "…"));
+        let mut group = Group::new(Cow::Borrowed(&truncation));
+        group.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&label),
+            Cow::Borrowed(&truncation),
+        )));
+        group.push(Box::new(Spacer::new()));
+        group.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&value),
+            Cow::Borrowed(&truncation),
+        )));
+        assert_eq!(Width::Unbounded, group.width());
+        let actual = group.truncate(7).unwrap().to_string();
+        assert_eq!("<1>ab</1>   <2>cd</2>", actual);
+        let actual = group.truncate(3).unwrap().to_string();
+        assert_eq!("<1>ab…</1>", actual);
+        assert_eq!(None, group.truncate(0));
+    }
+    #[test]
+    fn group_measures_elements() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let label = make_spans(&fmt_1, "in ");
+        let path = make_spans(&fmt_1, "src/text/mod.rs");
+        let truncation = TruncationStyle::Left(make_spans(&fmt_1, "…"));
+        let mut widget = PathWidget::new(Cow::Borrowed(&path));
+        widget.set_abbreviation_length(1);
+        let mut group = Group::new(Cow::Borrowed(&truncation));
+        group.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&label),
+            Cow::Borrowed(&truncation),
+        )));
+        group.push(Box::new(widget));
+        let measure = group.measure(0);
+        assert_eq!(measure.min, 10);
+        assert_eq!(measure.preferred, Width::Bounded(18));
+    }
+}
@@ -1,119 +1,1030 @@
-use crate::text::{Pushable, Width};
-use crate::widget::{Fitable, Truncateable};
+use crate::text::Spans;
+use crate::text::{
+    BoundedWidth, HasWidth, PaintedWidth, Pushable, RawText, Sliceable, Width, WidthPolicy,
+    WidthSliceable,
+};
+use crate::widget::pad::edge_styles;
+use crate::widget::truncatable::fill_gap;
+use crate::widget::{Fitable, Measure, StyleProvider, Truncateable};
 use std::iter::FromIterator;
 
 /// A displayable box of text widgets.
 pub struct HBox<'a, T: Truncateable> {
     elements: Vec<Box<dyn Fitable<T> + 'a>>,
+    painted_width: Option<fn(&T) -> usize>,
+    width_policy: Option<WidthPolicy>,
+    justify: Justify,
+    filler: Option<Box<dyn Fitable<T> + 'a>>,
+    separator: Option<T>,
+    pad: Option<PadFn<'a, T>>,
+    distribution: Distribution,
+    overflow: Option<Box<dyn Fn(usize) -> T + 'a>>,
 }
 
-impl<'a, T: Truncateable> Default for HBox<'a, T> {
+/// How an [`HBox`] measures the width of its elements.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WidthMode {
+    /// Only text content occupies columns. This suits terminals, where markup
+    /// such as ANSI escape codes is invisible.
+    Content,
+    /// Markup occupies columns along with the text content. This suits
+    /// backends that display markup literally.
+    Painted,
+}
+
+/// Fills the columns an element of an [`HBox`] leaves empty.
+type PadFn<'a, T> = Box<dyn Fn(&mut T, usize) + 'a>;
+
+/// The output of an [`HBox`] together with how its width was divided, as
+/// returned by [`HBox::layout`].
+#[derive(Clone, Debug)]
+pub struct Layout<T> {
+    /// The truncated output.
+    pub output: T,
+    /// The width given to each element, in order. Elements given no width
+    /// are not shown.
+    pub widths: Vec<usize>,
+    /// Whether each element was given less width than it wanted.
+    pub truncated: Vec<bool>,
+    /// The width left over after the output.
+    pub unused: usize,
+}
+
+impl<T> Layout<T> {
+    /// Returns the number of elements that were given no width, and so are
+    /// not shown.
+    pub fn hidden(&self) -> usize {
+        self.widths.iter().filter(|width| **width == 0).count()
+    }
+}
+
+/// Which elements of an [`HBox`] get the extra columns when width cannot be
+/// divided evenly between them.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Distribution {
+    /// The first elements get the extra columns.
+    #[default]
+    FirstWins,
+    /// The last elements get the extra columns.
+    LastWins,
+    /// The extra columns go alternately to the first and last elements,
+    /// working inwards.
+    Alternate,
+    /// Elements that are too wide share the width in proportion to the width
+    /// they want rather than equally, and the extra columns go to the
+    /// elements whose shares were rounded down the most.
+    LargestRemainder,
+}
+
+impl Distribution {
+    /// Whether the element at a position among `count` elements gets one of
+    /// `extra` leftover columns.
+    fn gets_extra(self, position: usize, count: usize, extra: usize) -> bool {
+        let rank = match self {
+            Distribution::FirstWins | Distribution::LargestRemainder => position,
+            Distribution::LastWins => count - 1 - position,
+            Distribution::Alternate if 2 * position < count => 2 * position,
+            Distribution::Alternate => 2 * (count - 1 - position) + 1,
+        };
+        rank < extra
+    }
+    /// Divides width evenly between a number of elements.
+    fn even(self, width: usize, count: usize) -> impl Iterator<Item = usize> {
+        (0..count).map(move |position| {
+            width / count + usize::from(self.gets_extra(position, count, width % count))
+        })
+    }
+    /// Divides width between elements that want more than it in total, in
+    /// proportion to the width they want.
+    fn proportional(width: usize, wanted: &[usize]) -> Vec<usize> {
+        let total: usize = wanted.iter().sum();
+        if total == 0 {
+            return vec![0; wanted.len()];
+        }
+        let mut shares: Vec<usize> = wanted.iter().map(|w| width * w / total).collect();
+        let mut order: Vec<usize> = (0..wanted.len()).collect();
+        // Stable, so ties go to the first elements.
+        order.sort_by_key(|&index| std::cmp::Reverse(width * wanted[index] % total));
+        let extra = width - shares.iter().sum::<usize>();
+        for &index in order.iter().take(extra) {
+            shares[index] += 1;
+        }
+        shares
+    }
+}
+
+/// How an [`HBox`] distributes width left over when its elements are narrower
+/// than the target width.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Justify {
+    /// Elements are packed to the left and the leftover width is unused.
+    #[default]
+    Packed,
+    /// Leftover width is divided between adjacent elements.
+    SpaceBetween,
+    /// Each element gets an equal gap on either side, so gaps between elements
+    /// are twice as wide as gaps at the edges.
+    SpaceAround,
+    /// Leftover width is divided equally between the edges and the gaps
+    /// between elements.
+    SpaceEvenly,
+}
+
+impl Justify {
+    /// Splits surplus width into gaps before, between and after a number of
+    /// elements.
+    fn gaps(self, count: usize, surplus: usize) -> Vec<usize> {
+        let weights: Vec<usize> = (0..=count)
+            .map(|index| {
+                let edge = index == 0 || index == count;
+                match self {
+                    Justify::Packed => 0,
+                    Justify::SpaceBetween if edge => 0,
+                    Justify::SpaceBetween => 1,
+                    Justify::SpaceAround if edge => 1,
+                    Justify::SpaceAround => 2,
+                    Justify::SpaceEvenly => 1,
+                }
+            })
+            .collect();
+        let total: usize = weights.iter().sum();
+        if total == 0 || count == 0 {
+            return vec![0; count + 1];
+        }
+        // Rounding the running total keeps the gaps as even as possible.
+        let mut cumulative = 0;
+        let mut placed = 0;
+        let mut gaps = Vec::with_capacity(weights.len());
+        for weight in weights {
+            cumulative += weight;
+            let end = (2 * surplus * cumulative + total) / (2 * total);
+            gaps.push(end - placed);
+            placed = end;
+        }
+        gaps
+    }
+}
+
+impl<'a, T> Default for HBox<'a, T>
+where
+    T: Truncateable + Pushable<T> + for<'s> From<&'s str>,
+{
     fn default() -> Self {
-        HBox { elements: vec![] }
+        HBox {
+            elements: vec![],
+            painted_width: None,
+            width_policy: None,
+            justify: Justify::Packed,
+            filler: None,
+            separator: None,
+            pad: Some(pad_spaces()),
+            distribution: Distribution::FirstWins,
+            overflow: None,
+        }
     }
 }
 
+/// Pads with spaces in the default style, as [`HBox`] does unless told
+/// otherwise with [`HBox::pad_with`].
+fn pad_spaces<'a, T>() -> PadFn<'a, T>
+where
+    T: Pushable<T> + for<'s> From<&'s str>,
+{
+    Box::new(|output, columns| {
+        if columns > 0 {
+            output.push(&T::from(&" ".repeat(columns)));
+        }
+    })
+}
+
 impl<'a, T: Truncateable> HBox<'a, T> {
-    pub fn new() -> Self {
+    /// Creates an empty box. Elements that come out narrower than their
+    /// share of the width are padded with spaces in the default style.
+    pub fn new() -> Self
+    where
+        T: Pushable<T> + for<'s> From<&'s str>,
+    {
         HBox {
             elements: Vec::new(),
+            painted_width: None,
+            width_policy: None,
+            justify: Justify::Packed,
+            filler: None,
+            separator: None,
+            pad: Some(pad_spaces()),
+            distribution: Distribution::FirstWins,
+            overflow: None,
         }
     }
+    /// Creates an empty box that inserts a separator between its elements.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle};
+    /// let dim = Tag::new("<d>", "</d>");
+    /// let mut separator: Spans<Tag> = Default::default();
+    /// separator.push(&Span::borrowed(&dim, " | "));
+    /// let texts: Vec<Spans<Tag>> = vec![Spans::from("foo"), Spans::from("bar")];
+    /// let truncation = TruncationStyle::Left("…");
+    /// let mut hbox = HBox::with_separator(separator);
+    /// for text in &texts {
+    ///     hbox.push(Box::new(TextWidget::new(
+    ///         Cow::Borrowed(text),
+    ///         Cow::Borrowed(&truncation),
+    ///     )));
+    /// }
+    /// assert_eq!(hbox.truncate(9).to_string(), "foo<d> | </d>bar");
+    /// assert_eq!(hbox.truncate(7).to_string(), "f…<d> | </d>b…");
+    /// ```
+    pub fn with_separator(separator: T) -> Self
+    where
+        T: Pushable<T> + for<'s> From<&'s str>,
+    {
+        let mut hbox = HBox::new();
+        hbox.set_separator(separator);
+        hbox
+    }
+    /// Sets a separator inserted between elements. The width of the
+    /// separators is set aside before the elements are allocated any width.
+    pub fn set_separator(&mut self, separator: T) {
+        self.separator = Some(separator);
+    }
     /// Adds an element.
     pub fn push(&mut self, element: Box<dyn Fitable<T> + 'a>) {
         self.elements.push(element);
     }
-    /// Truncates this widget to a given size.
+    /// Sets how the width of elements is measured. Defaults to [`WidthMode::Content`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle, WidthMode};
+    /// let bold = Tag::new("*", "*");
+    /// let mut spans: Spans<Tag> = Default::default();
+    /// spans.push(&Span::borrowed(&bold, "foobar"));
+    /// let truncation = TruncationStyle::Left("");
+    /// let mut hbox = HBox::new();
+    /// hbox.push(Box::new(TextWidget::new(
+    ///     Cow::Borrowed(&spans),
+    ///     Cow::Borrowed(&truncation),
+    /// )));
+    /// assert_eq!(hbox.truncate(5).to_string(), "*fooba*");
+    /// hbox.set_width_mode(WidthMode::Painted);
+    /// assert_eq!(hbox.truncate(5).to_string(), "*foo*");
+    /// ```
+    pub fn set_width_mode(&mut self, mode: WidthMode)
+    where
+        T: PaintedWidth,
+    {
+        self.painted_width = match mode {
+            WidthMode::Content => None,
+            WidthMode::Painted => Some(T::painted_width),
+        };
+    }
+    /// Sets the [`WidthPolicy`] used to measure and truncate elements,
+    /// overriding the policy current on the calling thread.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag, WidthPolicy};
+    /// use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle};
+    /// let text: Spans<Tag> = Spans::from("±1.5°");
+    /// let truncation = TruncationStyle::Left("…");
+    /// let mut hbox = HBox::new();
+    /// hbox.push(Box::new(TextWidget::new(
+    ///     Cow::Borrowed(&text),
+    ///     Cow::Borrowed(&truncation),
+    /// )));
+    /// assert_eq!(hbox.truncate(5).to_string(), "±1.5°");
+    /// hbox.set_width_policy(WidthPolicy::Wide);
+    /// // The ellipsis is ambiguous too, so it takes two columns.
+    /// assert_eq!(hbox.truncate(5).to_string(), "±1…");
+    /// ```
+    pub fn set_width_policy(&mut self, policy: WidthPolicy) {
+        self.width_policy = Some(policy);
+    }
+    /// Runs a function with this box's width policy, if it has one.
+    fn with_policy<R>(&self, f: impl FnOnce() -> R) -> R {
+        match self.width_policy {
+            Some(policy) => policy.scope(f),
+            None => f(),
+        }
+    }
+    /// Sets how width left over by the elements is distributed, filling gaps
+    /// with a filler such as a [`crate::widget::Spacer`]. Defaults to
+    /// [`Justify::Packed`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{HBox, Justify, Spacer, TextWidget, TruncationStyle};
+    /// let texts: Vec<Spans<Tag>> = vec![Spans::from("a"), Spans::from("b"), Spans::from("c")];
+    /// let truncation = TruncationStyle::Left("…");
+    /// let mut hbox = HBox::new();
+    /// for text in &texts {
+    ///     hbox.push(Box::new(TextWidget::new(
+    ///         Cow::Borrowed(text),
+    ///         Cow::Borrowed(&truncation),
+    ///     )));
+    /// }
+    /// hbox.set_justify(Justify::SpaceBetween, Box::new(Spacer::new()));
+    /// assert_eq!(hbox.truncate(7).to_string(), "a  b  c");
+    /// hbox.set_justify(Justify::SpaceEvenly, Box::new(Spacer::new()));
+    /// assert_eq!(hbox.truncate(7).to_string(), " a b c ");
+    /// ```
+    pub fn set_justify(&mut self, justify: Justify, filler: Box<dyn Fitable<T> + 'a>) {
+        self.justify = justify;
+        self.filler = Some(filler);
+    }
+    /// Sets the filler that pads elements that come out narrower than their
+    /// share of the width, as when a wide character at the cut point does
+    /// not fit, so that columns of boxes stay aligned. Elements are padded
+    /// with spaces in the default style unless this is called. The filler is
+    /// repeated as many whole times as fit, as by
+    /// [`crate::widget::ExactWidth`], so a single-column filler always fills
+    /// the gap exactly. A filler with no width, such as `""`, turns padding
+    /// off.
+    ///
+    /// Text widgets can be padded by their own truncation strategy with
+    /// [`crate::widget::TruncationStyle::exact_width`] instead; this pads
+    /// every element, whatever truncates it.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle};
+    /// let texts: Vec<Spans<Tag>> = vec![Spans::from("🙈🙉🙊"), Spans::from("|")];
+    /// let truncation = TruncationStyle::Left("");
+    /// let mut hbox = HBox::new();
+    /// for text in &texts {
+    ///     hbox.push(Box::new(TextWidget::new(
+    ///         Cow::Borrowed(text),
+    ///         Cow::Borrowed(&truncation),
+    ///     )));
+    /// }
+    /// assert_eq!(hbox.truncate(4).to_string(), "🙈 |");
+    /// hbox.pad_with(Spans::styled(Tag::new("<f>", "</f>"), "."));
+    /// assert_eq!(hbox.truncate(4).to_string(), "🙈<f>.</f>|");
+    /// hbox.pad_with("");
+    /// assert_eq!(hbox.truncate(4).to_string(), "🙈|");
+    /// ```
+    pub fn pad_with<F>(&mut self, filler: F)
+    where
+        F: BoundedWidth + WidthSliceable + 'a,
+        T: Pushable<F::Output>,
+    {
+        self.pad = if filler.bounded_width() == 0 {
+            None
+        } else {
+            Some(Box::new(move |output, columns| {
+                fill_gap(output, columns, &filler)
+            }))
+        };
+    }
+    /// Sets which elements get the extra columns when the width cannot be
+    /// divided evenly. Defaults to [`Distribution::FirstWins`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{Distribution, HBox, TextWidget, TruncationStyle};
+    /// let texts: Vec<Spans<Tag>> = vec![Spans::from("aaaa"), Spans::from("bbbbbbbb")];
+    /// let truncation = TruncationStyle::Left("");
+    /// let mut hbox = HBox::new();
+    /// for text in &texts {
+    ///     hbox.push(Box::new(TextWidget::new(
+    ///         Cow::Borrowed(text),
+    ///         Cow::Borrowed(&truncation),
+    ///     )));
+    /// }
+    /// assert_eq!(hbox.truncate(7).to_string(), "aaaabbb");
+    /// hbox.set_distribution(Distribution::LastWins);
+    /// assert_eq!(hbox.truncate(5).to_string(), "aabbb");
+    /// hbox.set_distribution(Distribution::LargestRemainder);
+    /// assert_eq!(hbox.truncate(6).to_string(), "aabbbb");
+    /// ```
+    pub fn set_distribution(&mut self, distribution: Distribution) {
+        self.distribution = distribution;
+    }
+    /// Truncates this widget to a given size. The elements share a single
+    /// line, so only the first line of an element that breaks lines is
+    /// shown, and it is measured by that line alone.
     pub fn truncate(&self, width: usize) -> T
     where
-        T: Pushable<T> + Pushable<T::Output> + Default,
+        T: Pushable<T> + Pushable<T::Output> + RawText + Sliceable + Default,
     {
-        let mut space = width;
-        let mut todo: Vec<(usize, _)> = self
-            .elements
+        self.with_policy(|| {
+            let (widths, breaks, indicator) = self.plan(width);
+            self.render(width, &widths, &breaks, indicator)
+        })
+    }
+    /// Drops trailing elements that do not fit, rather than giving them no
+    /// width, and shows an indicator made from the number of elements
+    /// dropped in their place.
+    ///
+    /// Every element must get its minimum width from [`Fitable::measure`],
+    /// or one column if it has none and wants any width, so that it shows
+    /// something. Elements are dropped from the end until the rest fit along
+    /// with the indicator and a separator before it.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle};
+    /// let tags: Vec<Spans<Tag>> = ["rust", "cli", "terminal", "unicode"]
+    ///     .iter()
+    ///     .map(|tag| Spans::from(*tag))
+    ///     .collect();
+    /// let truncation = TruncationStyle::Left("…");
+    /// let mut hbox = HBox::with_separator(Spans::from(" "));
+    /// for tag in &tags {
+    ///     let mut widget = TextWidget::new(Cow::Borrowed(tag), Cow::Borrowed(&truncation));
+    ///     widget.set_min_width(3);
+    ///     hbox.push(Box::new(widget));
+    /// }
+    /// let dim = Tag::new("<d>", "</d>");
+    /// hbox.set_overflow_indicator(move |hidden| Spans::styled(dim.clone(), &format!("+{}", hidden)));
+    /// assert_eq!(hbox.truncate(20).to_string(), "rust cli term… unic…");
+    /// assert_eq!(hbox.truncate(14).to_string(), "ru… cli te… <d>+1</d>");
+    /// let layout = hbox.layout(12);
+    /// assert_eq!(layout.output.to_string(), "rust cli <d>+2</d>");
+    /// assert_eq!(layout.hidden(), 2);
+    /// ```
+    pub fn set_overflow_indicator<F>(&mut self, indicator: F)
+    where
+        F: Fn(usize) -> T + 'a,
+    {
+        self.overflow = Some(Box::new(indicator));
+    }
+    /// Truncates this widget to a given size, reporting how the width was
+    /// divided between the elements along with the output.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle};
+    /// let texts: Vec<Spans<Tag>> = vec![Spans::from("foo"), Spans::from("barbaz")];
+    /// let truncation = TruncationStyle::Left("…");
+    /// let mut hbox = HBox::new();
+    /// for text in &texts {
+    ///     hbox.push(Box::new(TextWidget::new(
+    ///         Cow::Borrowed(text),
+    ///         Cow::Borrowed(&truncation),
+    ///     )));
+    /// }
+    /// let layout = hbox.layout(7);
+    /// assert_eq!(layout.output.to_string(), "foobar…");
+    /// assert_eq!(layout.widths, [3, 4]);
+    /// assert_eq!(layout.truncated, [false, true]);
+    /// assert_eq!(hbox.layout(12).unused, 3);
+    /// ```
+    pub fn layout(&self, width: usize) -> Layout<T>
+    where
+        T: Pushable<T> + Pushable<T::Output> + RawText + Sliceable + Default,
+    {
+        self.with_policy(|| {
+            let (widths, breaks, indicator) = self.plan(width);
+            let output = self.render(width, &widths, &breaks, indicator);
+            let truncated = self
+                .measure(width)
+                .iter()
+                .zip(&widths)
+                .map(|(measure, width)| match measure.preferred {
+                    Width::Bounded(wanted) => wanted > *width,
+                    Width::Unbounded => false,
+                })
+                .collect();
+            let unused = match output.width() {
+                Width::Bounded(_) => width.saturating_sub(self.used_width(&output)),
+                Width::Unbounded => 0,
+            };
+            Layout {
+                output,
+                widths,
+                truncated,
+                unused,
+            }
+        })
+    }
+    /// Fills columns an element left empty.
+    fn pad(&self, output: &mut T, columns: usize) {
+        if let Some(ref pad) = self.pad {
+            pad(output, columns);
+        }
+    }
+    /// Divides a width between the elements, after setting aside the width
+    /// of the separators, and makes the overflow indicator if elements had to
+    /// be dropped. Dropped elements get no width. Also returns the width at
+    /// which each element that breaks lines shows all of its first line, as
+    /// found by [`HBox::measure_lines`].
+    fn plan(&self, width: usize) -> (Vec<usize>, Vec<Option<usize>>, Option<T>)
+    where
+        T: RawText + Sliceable + Default,
+    {
+        let count = self.elements.len();
+        let separator_width = self.separator_width();
+        let separators = separator_width * count.saturating_sub(1);
+        let (measures, breaks) = self.measure_lines(width.saturating_sub(separators));
+        let (widths, indicator) = self.plan_widths(width, &measures);
+        (widths, breaks, indicator)
+    }
+    /// Divides a width between elements of given measures, as for
+    /// [`HBox::plan`].
+    fn plan_widths(&self, width: usize, measures: &[Measure]) -> (Vec<usize>, Option<T>) {
+        let count = self.elements.len();
+        let separator_width = self.separator_width();
+        let separators = separator_width * count.saturating_sub(1);
+        let allocate = |measures: &[Measure], width: usize| {
+            let mut widths = arrange(measures, width, self.distribution);
+            widths.resize(count, 0);
+            widths
+        };
+        let overflow = match self.overflow {
+            Some(ref overflow) => overflow,
+            None => return (allocate(measures, width.saturating_sub(separators)), None),
+        };
+        // The width an element needs to show anything.
+        let needed = |measure: &Measure| match measure.preferred {
+            Width::Bounded(w) => measure.min.max(1).min(w),
+            Width::Unbounded => measure.min,
+        };
+        let mut required: Vec<usize> = measures
             .iter()
-            .enumerate()
-            .filter_map(|(index, element)| {
-                if let Width::Bounded(_w) = element.width() {
-                    Some((index, element))
-                } else {
-                    None
-                }
+            .scan(0, |total, measure| {
+                *total += needed(measure);
+                Some(*total)
             })
             .collect();
-        let mut to_fit = todo.len();
-        let mut widths: std::collections::HashMap<usize, usize> = Default::default();
-        while to_fit > 0 {
-            let target_width: f32 = space as f32 / to_fit as f32;
-            let mut to_pop = vec![];
-            for (rel_index, (index, element)) in todo.iter().enumerate() {
-                if let Width::Bounded(w) = element.width() {
-                    if (w as f32) <= target_width {
-                        space -= w;
-                        to_fit -= 1;
-                        widths.insert(*index, w);
-                        to_pop.push(rel_index)
-                    }
-                }
+        required.insert(0, 0);
+        if required[count] + separators <= width {
+            return (allocate(measures, width - separators), None);
+        }
+        for shown in (0..count).rev() {
+            let indicator = overflow(count - shown);
+            let reserved = self.used_width(&indicator) + separator_width * shown;
+            if required[shown] + reserved <= width {
+                let widths = allocate(&measures[..shown], width - reserved);
+                return (widths, Some(indicator));
+            }
+        }
+        (allocate(measures, width.saturating_sub(separators)), None)
+    }
+    /// Finds the width of one separator.
+    fn separator_width(&self) -> usize {
+        self.separator
+            .as_ref()
+            .map_or(0, |separator| self.used_width(separator))
+    }
+    /// Lays out the elements in a given width, truncating each to its share
+    /// of the width, followed by an overflow indicator.
+    fn render(
+        &self,
+        width: usize,
+        widths: &[usize],
+        breaks: &[Option<usize>],
+        indicator: Option<T>,
+    ) -> T
+    where
+        T: Pushable<T> + Pushable<T::Output> + RawText + Sliceable + Default,
+    {
+        let mut res = match indicator {
+            Some(ref indicator) => {
+                let reserved = self.used_width(indicator) + self.separator_width();
+                self.render_elements(width.saturating_sub(reserved), widths, breaks)
             }
-            for index in to_pop.iter().rev() {
-                todo.remove(*index);
+            None => self.render_elements(width, widths, breaks),
+        };
+        if let Some(indicator) = indicator {
+            if widths.iter().any(|width| *width > 0) {
+                res.push(&self.separator);
             }
-            if to_pop.is_empty() {
-                let target_width = space / todo.len();
-                let rem = space % todo.len();
-                for (i, (index, _widget)) in todo.iter().enumerate() {
-                    let w = if i < rem {
-                        target_width + 1
-                    } else {
-                        target_width
-                    };
-                    space -= w;
-                    widths.insert(*index, w);
+            res.push(&indicator);
+        }
+        res
+    }
+    /// Lays out the elements in a given width, truncating each to its share
+    /// of the width.
+    fn render_elements(&self, width: usize, widths: &[usize], breaks: &[Option<usize>]) -> T
+    where
+        T: Pushable<T> + Pushable<T::Output> + RawText + Sliceable + Default,
+    {
+        let separator_width = self.separator_width();
+        if self.separator.is_none() && self.filler.is_none() {
+            // Nothing depends on the output of the other elements, so each
+            // is added to the result as soon as it is made.
+            let mut res: T = Default::default();
+            for ((widget, &width), &natural) in self.elements.iter().zip(widths).zip(breaks) {
+                if let Some(elem) = self.element(widget.as_ref(), width, natural) {
+                    res.push(&elem);
                 }
-                break;
             }
+            return res;
         }
-        let infinite_widths: Vec<(usize, _)> = self
+        let elements: Vec<T> = self
             .elements
             .iter()
-            .enumerate()
-            .filter_map(|(index, element)| {
-                if let Width::Unbounded = element.width() {
-                    Some((index, element))
-                } else {
-                    None
-                }
+            .zip(widths)
+            .zip(breaks)
+            .filter_map(|((widget, &width), &natural)| {
+                self.element(widget.as_ref(), width, natural)
             })
             .collect();
-        if !infinite_widths.is_empty() {
-            let target_width = space / infinite_widths.len();
-            let rem = space % infinite_widths.len();
-            for (rel_index, (abs_index, _element)) in infinite_widths.iter().enumerate() {
-                let w = if rel_index < rem {
-                    target_width + 1
-                } else {
-                    target_width
+        let used: usize = elements
+            .iter()
+            .map(|elem| self.used_width(elem))
+            .sum::<usize>()
+            + separator_width * elements.len().saturating_sub(1);
+        let gaps = match self.filler {
+            Some(_) => self
+                .justify
+                .gaps(elements.len(), width.saturating_sub(used)),
+            None => vec![],
+        };
+        let mut gaps = gaps.into_iter();
+        let mut res: T = Default::default();
+        for (index, elem) in elements.into_iter().enumerate() {
+            let gap = gaps.next().unwrap_or(0);
+            match self.separator {
+                // Separators sit in the middle of the gaps around them.
+                Some(ref separator) if index > 0 => {
+                    res.push(&self.fill(gap / 2));
+                    res.push(separator);
+                    res.push(&self.fill(gap - gap / 2));
+                }
+                _ => res.push(&self.fill(gap)),
+            }
+            res.push(&elem)
+        }
+        res.push(&self.fill(gaps.next().unwrap_or(0)));
+        res
+    }
+    /// Makes filler of a given width for gaps between elements.
+    fn fill(&self, width: usize) -> Option<T> {
+        self.filler
+            .as_ref()
+            .and_then(|filler| filler.truncate(width))
+    }
+    /// Finds the width an element occupies once truncated.
+    fn used_width(&self, elem: &T) -> usize {
+        match (self.painted_width, elem.width()) {
+            (Some(painted_width), _) => painted_width(elem),
+            (None, Width::Bounded(w)) => w,
+            (None, Width::Unbounded) => 0,
+        }
+    }
+    /// Measures the widths each element wants to occupy, given the width
+    /// available to all of them.
+    /// Elements with bounded widths are measured by what they show at their
+    /// preferred width, which is only their first line.
+    fn measure(&self, width_hint: usize) -> Vec<Measure>
+    where
+        T: RawText + Sliceable + Default,
+    {
+        self.measure_lines(width_hint).0
+    }
+    /// Measures the elements as [`HBox::measure`] does, along with the
+    /// preferred width of each element that breaks lines there.
+    fn measure_lines(&self, width_hint: usize) -> (Vec<Measure>, Vec<Option<usize>>)
+    where
+        T: RawText + Sliceable + Default,
+    {
+        self.elements
+            .iter()
+            .map(|element| {
+                let measure = element.measure(width_hint);
+                let natural = match measure.preferred {
+                    Width::Bounded(w) => w,
+                    Width::Unbounded => return (measure, None),
                 };
-                widths.insert(*abs_index, w);
+                let shown = element.truncate(natural);
+                let breaks = matches!(&shown, Some(t) if line_break(t.raw_ref()).is_some());
+                let preferred = shown.map(first_line).map_or(0, |t| self.used_width(&t));
+                let measure = Measure {
+                    min: measure.min.min(preferred),
+                    preferred: Width::Bounded(preferred),
+                };
+                (measure, if breaks { Some(natural) } else { None })
+            })
+            .unzip()
+    }
+    /// Truncates an element to its share of the width, cutting it at its
+    /// first line break and padding it to fill the share. An element that
+    /// breaks lines at its `natural` width is first tried at that width, so
+    /// that its first line is shown whole if it fits.
+    fn element(
+        &self,
+        element: &(dyn Fitable<T> + 'a),
+        width: usize,
+        natural: Option<usize>,
+    ) -> Option<T>
+    where
+        T: Pushable<T> + Pushable<T::Output> + RawText + Sliceable + Default,
+    {
+        let whole = natural
+            .and_then(|natural| element.truncate(natural))
+            .map(first_line)
+            .filter(|line| self.used_width(line) <= width);
+        let mut elem = match (whole, self.painted_width) {
+            (Some(line), _) => line,
+            (None, Some(_)) => self.fit(element, width)?,
+            (None, None) => {
+                let mut elem: T = Default::default();
+                if !element.truncate_into(width, &mut elem) {
+                    return None;
+                }
+                first_line(elem)
+            }
+        };
+        if let Width::Bounded(_) = elem.width() {
+            let used = self.used_width(&elem);
+            self.pad(&mut elem, width.saturating_sub(used));
+        }
+        Some(elem)
+    }
+    /// Truncates an element to a width, shrinking it further while the
+    /// markup of its first line overflows the width.
+    fn fit(&self, element: &(dyn Fitable<T> + 'a), width: usize) -> Option<T>
+    where
+        T: RawText + Sliceable + Default,
+    {
+        let mut target = width;
+        loop {
+            let output = element.truncate(target).map(first_line);
+            let (painted, content) = match (self.painted_width, &output) {
+                (Some(painted_width), Some(t)) => (painted_width(t), t.width()),
+                _ => return output,
+            };
+            if painted <= width || target == 0 {
+                return output;
             }
+            // Markup is not monotonic in the content width (runs and truncation
+            // symbols come and go), so step down one column at a time, skipping
+            // targets that would produce the same output.
+            target = match content {
+                Width::Bounded(w) => w.min(target),
+                Width::Unbounded => target,
+            }
+            .saturating_sub(1);
         }
+    }
+    /// Truncates this widget to the width of the terminal attached to standard
+    /// output, or to `default` if there is no terminal. A width set in the
+    /// `COLUMNS` environment variable overrides the terminal's.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle};
+    /// let status: Spans<Tag> = Spans::from("compiling stylish-stringlike");
+    /// let truncation = TruncationStyle::Left("…");
+    /// let mut hbox = HBox::new();
+    /// hbox.push(Box::new(TextWidget::new(Cow::Borrowed(&status), Cow::Borrowed(&truncation))));
+    /// println!("{}", hbox.truncate_to_terminal(80));
+    /// ```
+    #[cfg(feature = "term-size")]
+    pub fn truncate_to_terminal(&self, default: usize) -> T
+    where
+        T: Pushable<T> + Pushable<T::Output> + RawText + Sliceable + Default,
+    {
+        let columns = std::env::var("COLUMNS").ok();
+        let detected =
+            terminal_size::terminal_size().map(|(terminal_size::Width(w), _)| w as usize);
+        self.truncate(terminal_width(columns.as_deref(), detected, default))
+    }
+}
 
-        let mut res: T = Default::default();
-        let elements = self
-            .elements
-            .iter()
-            .enumerate()
-            .map(move |(i, widget)| widget.truncate(widths[&i]))
-            .flatten();
-        for elem in elements {
-            res.push(&elem)
+/// Resolves the width to fit to the terminal: a valid width in `columns`,
+/// the value of the `COLUMNS` environment variable, wins over the `detected`
+/// width of the terminal, which wins over `default`.
+#[cfg(feature = "term-size")]
+fn terminal_width(columns: Option<&str>, detected: Option<usize>, default: usize) -> usize {
+    columns
+        .and_then(|columns| columns.trim().parse().ok())
+        .or(detected)
+        .unwrap_or(default)
+}
+
+impl<'a, S: Clone + Default + PartialEq + 'a> HBox<'a, Spans<S>> {
+    /// Pads elements like [`HBox::pad_with`], styling the filler from the
+    /// style at the end of the element it follows, as for [`crate::widget::Pad`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Color, Format, Spans};
+    /// use stylish_stringlike::widget::{HBox, InheritBackground, TextWidget, TruncationStyle};
+    /// let status = Spans::styled(Format::new().bg(Color::Blue), "🙈🙉");
+    /// let truncation = TruncationStyle::Left("");
+    /// let mut hbox = HBox::new();
+    /// hbox.push(Box::new(TextWidget::new(Cow::Borrowed(&status), Cow::Borrowed(&truncation))));
+    /// hbox.pad_with_style(Spans::from(" "), InheritBackground);
+    /// let actual = hbox.truncate(3);
+    /// assert_eq!(actual.style_at(4), Some(&Format::new().bg(Color::Blue)));
+    /// ```
+    pub fn pad_with_style<P>(&mut self, filler: Spans<S>, provider: P)
+    where
+        P: StyleProvider<S> + 'a,
+    {
+        self.pad = Some(Box::new(move |output: &mut Spans<S>, columns| {
+            let before = edge_styles(output).1.cloned();
+            let filler =
+                filler.map_style(|style| provider.fill_style(style, before.as_ref(), None));
+            fill_gap(output, columns, &filler);
+        }));
+    }
+}
+
+impl<'a, T> HasWidth for HBox<'a, T>
+where
+    T: Truncateable + RawText + Sliceable + Default,
+{
+    /// The width the elements and separators want to occupy, which is
+    /// unbounded if any element is.
+    fn width(&self) -> Width {
+        self.with_policy(|| {
+            let separators = self.separator_width() * self.elements.len().saturating_sub(1);
+            let preferred = self.measure(usize::MAX).into_iter().map(|m| m.preferred);
+            preferred.sum::<Width>() + Width::Bounded(separators)
+        })
+    }
+}
+
+impl<'a, T> Fitable<T> for HBox<'a, T>
+where
+    T: Truncateable + Pushable<T> + Pushable<T::Output> + RawText + Sliceable + Default,
+{
+    /// Truncates the box to a given width, so that boxes can be nested in
+    /// other boxes.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{Fitable, HBox, TextWidget, TruncationStyle};
+    /// let texts: Vec<Spans<Tag>> = vec![Spans::from("abc"), Spans::from("def"), Spans::from("ghijkl")];
+    /// let truncation = TruncationStyle::Left("…");
+    /// let mut inner = HBox::with_separator(Spans::from("/"));
+    /// for text in &texts[..2] {
+    ///     inner.push(Box::new(TextWidget::new(Cow::Borrowed(text), Cow::Borrowed(&truncation))));
+    /// }
+    /// let mut outer = HBox::with_separator(Spans::from(" "));
+    /// outer.push(Box::new(inner));
+    /// outer.push(Box::new(TextWidget::new(Cow::Borrowed(&texts[2]), Cow::Borrowed(&truncation))));
+    /// assert_eq!(outer.truncate(14).to_string(), "abc/def ghijkl");
+    /// assert_eq!(outer.truncate(11).to_string(), "a…/d… ghij…");
+    /// ```
+    fn truncate(&self, width: usize) -> Option<T> {
+        if width == 0 {
+            None
+        } else {
+            Some(HBox::truncate(self, width))
+        }
+    }
+    /// Measures the box as the sum of its elements and separators. A box
+    /// whose elements have no minimum width has none either.
+    fn measure(&self, width_hint: usize) -> Measure {
+        self.with_policy(|| {
+            let separators = self.separator_width() * self.elements.len().saturating_sub(1);
+            let measures = self.measure(width_hint.saturating_sub(separators));
+            let min = measures.iter().map(|m| m.min).sum::<usize>();
+            Measure {
+                min: if min == 0 { 0 } else { min + separators },
+                preferred: measures.iter().map(|m| m.preferred).sum::<Width>()
+                    + Width::Bounded(separators),
+            }
+        })
+    }
+}
+
+/// Cuts text at its first line break, since an [`HBox`] lays out a single
+/// line.
+fn first_line<T: RawText + Sliceable + Default>(text: T) -> T {
+    match line_break(text.raw_ref()) {
+        Some(end) => text.slice(..end).unwrap_or_default(),
+        None => text,
+    }
+}
+
+/// Finds the byte index of the first line break in text.
+fn line_break(text: &str) -> Option<usize> {
+    text.find(['\n', '\r'])
+}
+
+/// Splits the available width between elements measured by
+/// [`Fitable::measure`]. Each element first gets its minimum width, if there is
+/// room for all of them, and the rest is split by [`allocate_with`] according
+/// to the width each element wants beyond its minimum.
+pub(crate) fn arrange(
+    measures: &[Measure],
+    width: usize,
+    distribution: Distribution,
+) -> Vec<usize> {
+    let min_total: usize = measures.iter().map(|m| m.min).sum();
+    if min_total == 0 || min_total > width {
+        let preferred: Vec<Width> = measures.iter().map(|m| m.preferred).collect();
+        return allocate_with(&preferred, width, distribution);
+    }
+    let rest: Vec<Width> = measures
+        .iter()
+        .map(|m| match m.preferred {
+            Width::Bounded(w) => Width::Bounded(w.saturating_sub(m.min)),
+            Width::Unbounded => Width::Unbounded,
+        })
+        .collect();
+    allocate_with(&rest, width - min_total, distribution)
+        .into_iter()
+        .zip(measures)
+        .map(|(w, m)| w + m.min)
+        .collect()
+}
+
+/// Splits the available width between elements of measured widths.
+///
+/// Bounded elements that fit in an even share get their full width, and the
+/// rest divide the remaining space evenly. Whatever bounded elements leave over
+/// is divided evenly between unbounded elements.
+pub(crate) fn allocate(measured: &[Width], width: usize) -> Vec<usize> {
+    allocate_with(measured, width, Distribution::FirstWins)
+}
+
+/// Splits the available width between elements as [`allocate`] does, giving
+/// columns that cannot be divided evenly according to a [`Distribution`].
+pub(crate) fn allocate_with(
+    measured: &[Width],
+    width: usize,
+    distribution: Distribution,
+) -> Vec<usize> {
+    let mut widths = vec![0; measured.len()];
+    let mut space = width;
+    let mut todo: Vec<(usize, usize)> = measured
+        .iter()
+        .enumerate()
+        .filter_map(|(index, width)| match width {
+            Width::Bounded(w) => Some((index, *w)),
+            Width::Unbounded => None,
+        })
+        .collect();
+    let mut to_fit = todo.len();
+    while to_fit > 0 {
+        let target_width: f32 = space as f32 / to_fit as f32;
+        let mut to_pop = vec![];
+        for (rel_index, (index, w)) in todo.iter().enumerate() {
+            if (*w as f32) <= target_width {
+                space -= w;
+                to_fit -= 1;
+                widths[*index] = *w;
+                to_pop.push(rel_index)
+            }
+        }
+        for index in to_pop.iter().rev() {
+            todo.remove(*index);
+        }
+        if to_pop.is_empty() {
+            let shares: Vec<usize> = match distribution {
+                Distribution::LargestRemainder => {
+                    let wanted: Vec<usize> = todo.iter().map(|(_, w)| *w).collect();
+                    Distribution::proportional(space, &wanted)
+                }
+                _ => distribution.even(space, todo.len()).collect(),
+            };
+            for ((index, _w), w) in todo.iter().zip(shares) {
+                space -= w;
+                widths[*index] = w;
+            }
+            break;
         }
-        res
     }
+    let infinite_widths: Vec<usize> = measured
+        .iter()
+        .enumerate()
+        .filter_map(|(index, width)| match width {
+            Width::Unbounded => Some(index),
+            Width::Bounded(_) => None,
+        })
+        .collect();
+    if !infinite_widths.is_empty() {
+        let shares = distribution.even(space, infinite_widths.len());
+        for (abs_index, w) in infinite_widths.iter().zip(shares) {
+            widths[*abs_index] = w;
+        }
+    }
+    widths
 }
 
-impl<'a, T: Truncateable> FromIterator<Box<dyn Fitable<T> + 'a>> for HBox<'a, T> {
+impl<'a, T> FromIterator<Box<dyn Fitable<T> + 'a>> for HBox<'a, T>
+where
+    T: Truncateable + Pushable<T> + for<'s> From<&'s str>,
+{
     fn from_iter<I>(iter: I) -> HBox<'a, T>
     where
         I: IntoIterator<Item = Box<dyn Fitable<T> + 'a>>,
@@ -126,11 +1037,69 @@ impl<'a, T: Truncateable> FromIterator<Box<dyn Fitable<T> + 'a>> for HBox<'a, T>
     }
 }
 
+impl<'a, T> FromIterator<&'a dyn Fitable<T>> for HBox<'a, T>
+where
+    T: Truncateable + Pushable<T> + for<'s> From<&'s str> + 'a,
+{
+    fn from_iter<I>(iter: I) -> HBox<'a, T>
+    where
+        I: IntoIterator<Item = &'a dyn Fitable<T>>,
+    {
+        let mut result: HBox<T> = Default::default();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<'a, T: Truncateable> Extend<Box<dyn Fitable<T> + 'a>> for HBox<'a, T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Box<dyn Fitable<T> + 'a>>,
+    {
+        self.elements.extend(iter);
+    }
+}
+
+impl<'a, T: Truncateable + 'a> Extend<&'a dyn Fitable<T>> for HBox<'a, T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a dyn Fitable<T>>,
+    {
+        for element in iter {
+            self.push(Box::new(Borrowed(element)));
+        }
+    }
+}
+
+/// A widget borrowed by an [`HBox`] rather than owned by it.
+struct Borrowed<'a, T: Truncateable>(&'a dyn Fitable<T>);
+
+impl<'a, T: Truncateable> HasWidth for Borrowed<'a, T> {
+    fn width(&self) -> Width {
+        self.0.width()
+    }
+}
+
+impl<'a, T: Truncateable> Fitable<T> for Borrowed<'a, T> {
+    fn truncate(&self, width: usize) -> Option<T> {
+        self.0.truncate(width)
+    }
+    fn measure(&self, width_hint: usize) -> Measure {
+        self.0.measure(width_hint)
+    }
+    fn truncate_into(&self, width: usize, output: &mut T) -> bool
+    where
+        T: Pushable<T>,
+    {
+        self.0.truncate_into(width, output)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::text::*;
-    use crate::widget::{Repeat, TextWidget, TruncationStyle};
+    use crate::widget::{PathWidget, Repeat, Spacer, TextWidget, TruncationStyle};
     use std::borrow::Cow;
     #[test]
     fn make_hbox() {
@@ -153,6 +1122,133 @@ mod test {
         assert_eq!(expected, actual);
     }
     #[test]
+    fn pad_wide_cuts_with_separator() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let texts = [Spans::styled(fmt_1, "🙈🙉"), Spans::from("ab🙊")];
+        let truncation = TruncationStyle::Left("");
+        let mut hbox: HBox<Spans<Tag>> = HBox::with_separator(Spans::from("|"));
+        hbox.pad_with(" ");
+        for text in texts.iter() {
+            hbox.push(Box::new(TextWidget::new(
+                Cow::Borrowed(text),
+                Cow::Borrowed(&truncation),
+            )));
+        }
+        let actual = hbox.truncate(7);
+        assert_eq!(actual.to_string(), "<1>🙈 </1>|ab ");
+        assert_eq!(actual.bounded_width(), 7);
+    }
+    #[test]
+    fn pad_wide_cuts_by_default() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let texts = [Spans::styled(fmt_1, "🙈🙉"), Spans::from("ab🙊")];
+        let truncation = TruncationStyle::Left("");
+        let mut hbox: HBox<Spans<Tag>> = HBox::new();
+        for text in texts.iter() {
+            hbox.push(Box::new(TextWidget::new(
+                Cow::Borrowed(text),
+                Cow::Borrowed(&truncation),
+            )));
+        }
+        let actual = hbox.truncate(6);
+        assert_eq!(actual.to_string(), "<1>🙈</1> ab ");
+        assert_eq!(actual.bounded_width(), 6);
+        hbox.pad_with("");
+        assert_eq!(hbox.truncate(6).to_string(), "<1>🙈</1>ab");
+    }
+    #[test]
+    fn distribute_remainders() {
+        let measured = [Width::Unbounded; 5];
+        let cases = [
+            (Distribution::FirstWins, [2, 2, 1, 1, 1]),
+            (Distribution::LastWins, [1, 1, 1, 2, 2]),
+            (Distribution::Alternate, [2, 1, 1, 1, 2]),
+            (Distribution::LargestRemainder, [2, 2, 1, 1, 1]),
+        ];
+        for (distribution, expected) in cases.iter() {
+            assert_eq!(allocate_with(&measured, 7, *distribution), expected);
+        }
+        let measured = [Width::Bounded(10), Width::Bounded(20), Width::Bounded(5)];
+        let widths = allocate_with(&measured, 14, Distribution::LargestRemainder);
+        assert_eq!(widths, [4, 8, 2]);
+    }
+    #[test]
+    fn layout_reports_hidden_elements() {
+        let texts: Vec<Spans<Tag>> = ["ab", "cd", "ef"].iter().map(|s| Spans::from(*s)).collect();
+        let truncation = TruncationStyle::Left("");
+        let mut hbox: HBox<Spans<Tag>> = HBox::new();
+        for text in &texts {
+            hbox.push(Box::new(TextWidget::new(
+                Cow::Borrowed(text),
+                Cow::Borrowed(&truncation),
+            )));
+        }
+        let layout = hbox.layout(2);
+        assert_eq!(layout.output.raw(), "ac");
+        assert_eq!(layout.widths, [1, 1, 0]);
+        assert_eq!(layout.hidden(), 1);
+        assert_eq!(layout.unused, 0);
+        assert_eq!(layout.output, hbox.truncate(2));
+    }
+    #[test]
+    fn arrange_reserves_minimum_widths() {
+        let measures = [
+            Measure {
+                min: 4,
+                preferred: Width::Bounded(10),
+            },
+            Measure {
+                min: 0,
+                preferred: Width::Bounded(10),
+            },
+        ];
+        assert_eq!(arrange(&measures, 8, Distribution::FirstWins), [6, 2]);
+        assert_eq!(arrange(&measures, 3, Distribution::FirstWins), [2, 1]);
+        let text: Spans<Tag> = Spans::from("abcdef");
+        let truncation = TruncationStyle::Left("");
+        let mut widget = TextWidget::new(Cow::Borrowed(&text), Cow::Borrowed(&truncation));
+        widget.set_min_width(3);
+        let mut hbox: HBox<Spans<Tag>> = HBox::with_separator(Spans::from("|"));
+        hbox.push(Box::new(widget));
+        hbox.push(Box::new(Spacer::new()));
+        let measure = Fitable::measure(&hbox, 10);
+        assert_eq!(measure.min, 4);
+        assert_eq!(measure.preferred, Width::Unbounded);
+    }
+    #[test]
+    fn overflow_drops_trailing_elements() {
+        let texts: Vec<Spans<Tag>> = ["abc", "def", "ghi"]
+            .iter()
+            .map(|s| Spans::from(*s))
+            .collect();
+        let truncation = TruncationStyle::Left("");
+        let mut hbox: HBox<Spans<Tag>> = HBox::new();
+        for text in &texts {
+            hbox.push(Box::new(TextWidget::new(
+                Cow::Borrowed(text),
+                Cow::Borrowed(&truncation),
+            )));
+        }
+        hbox.set_overflow_indicator(|_| Spans::from("+"));
+        assert_eq!(hbox.truncate(3).raw(), "adg");
+        assert_eq!(hbox.truncate(2).raw(), "a+");
+        let layout = hbox.layout(3);
+        assert_eq!(layout.widths, [1, 1, 1]);
+        let mut narrow: HBox<Spans<Tag>> = HBox::new();
+        narrow.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&texts[0]),
+            Cow::Borrowed(&truncation),
+        )));
+        narrow.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&texts[1]),
+            Cow::Borrowed(&truncation),
+        )));
+        narrow.set_overflow_indicator(|hidden| Spans::from(hidden.to_string().as_str()));
+        let layout = narrow.layout(1);
+        assert_eq!(layout.output.raw(), "2");
+        assert_eq!(layout.hidden(), 2);
+    }
+    #[test]
     fn make_hbox_infinite() {
         let fmt_1 = Tag::new("<1>", "</1>");
         let fmt_2 = Tag::new("<2>", "</2>");
@@ -167,6 +1263,137 @@ mod test {
         let expected = String::from("<2>==</2><1>...</1>");
         assert_eq!(expected, actual);
     }
+    #[cfg(feature = "term-size")]
+    #[test]
+    fn terminal_width_sources() {
+        assert_eq!(7, terminal_width(Some("7"), Some(40), 5));
+        assert_eq!(7, terminal_width(Some(" 7\n"), None, 5));
+        assert_eq!(40, terminal_width(Some("wide"), Some(40), 5));
+        assert_eq!(40, terminal_width(None, Some(40), 5));
+        assert_eq!(5, terminal_width(None, None, 5));
+    }
+    #[test]
+    fn painted_width_mode() {
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let fmt_3 = Tag::new("<3>", "</3>");
+        let truncator = TruncationStyle::Left("");
+        let texts: Vec<Spans<Tag>> = [&fmt_2, &fmt_3]
+            .iter()
+            .map(|fmt| {
+                let mut spans: Spans<Tag> = Default::default();
+                spans.push(&Span::new(Cow::Borrowed(*fmt), Cow::Borrowed("0123456789")));
+                spans
+            })
+            .collect();
+        let mut hbox: HBox<Spans<Tag>> = Default::default();
+        for text in &texts {
+            hbox.push(Box::new(TextWidget::new(
+                Cow::Borrowed(text),
+                Cow::Borrowed(&truncator),
+            )));
+        }
+        hbox.set_width_mode(WidthMode::Painted);
+        let actual = hbox.truncate(30);
+        assert_eq!(30, actual.painted_width());
+        let expected = String::from("<2>01234567</2><3>01234567</3>");
+        assert_eq!(expected, actual.to_string());
+    }
+    #[test]
+    fn collect_borrowed_widgets() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let texts: Vec<Spans<Tag>> = [&fmt_1, &fmt_2]
+            .iter()
+            .map(|fmt| {
+                let mut spans: Spans<Tag> = Default::default();
+                spans.push(&Span::new(Cow::Borrowed(*fmt), Cow::Borrowed("0123")));
+                spans
+            })
+            .collect();
+        let truncation = TruncationStyle::Left("");
+        let widgets: Vec<TextWidget<Spans<Tag>, TruncationStyle<&str>>> = texts
+            .iter()
+            .map(|text| TextWidget::new(Cow::Borrowed(text), Cow::Borrowed(&truncation)))
+            .collect();
+        let mut hbox: HBox<Spans<Tag>> = widgets
+            .iter()
+            .map(|widget| widget as &dyn Fitable<_>)
+            .collect();
+        assert_eq!("<1>012</1><2>012</2>", hbox.truncate(6).to_string());
+        hbox.extend(std::iter::once(
+            Box::new(Spacer::new()) as Box<dyn Fitable<Spans<Tag>>>
+        ));
+        hbox.extend(widgets.iter().map(|widget| widget as &dyn Fitable<_>));
+        let expected = "<1>0123</1><2>0123</2>  <1>0123</1><2>0123</2>";
+        assert_eq!(expected, hbox.truncate(18).to_string());
+    }
+    #[test]
+    fn separator_in_gap() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_s = Tag::new("<s>", "</s>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans.push(&Span::borrowed(&fmt_1, "ab"));
+        let mut separator: Spans<Tag> = Default::default();
+        separator.push(&Span::borrowed(&fmt_s, "|"));
+        let truncation = TruncationStyle::Left("");
+        let mut hbox = HBox::with_separator(separator);
+        for _ in 0..2 {
+            hbox.push(Box::new(TextWidget::new(
+                Cow::Borrowed(&spans),
+                Cow::Borrowed(&truncation),
+            )));
+        }
+        assert_eq!("<1>a</1><s>|</s><1>a</1>", hbox.truncate(3).to_string());
+        hbox.set_justify(Justify::SpaceBetween, Box::new(Spacer::new()));
+        let expected = "<1>ab</1>  <s>|</s>  <1>ab</1>";
+        assert_eq!(expected, hbox.truncate(9).to_string());
+    }
+    #[test]
+    fn justify_gaps() {
+        assert_eq!(vec![0, 0, 0], Justify::Packed.gaps(2, 5));
+        assert_eq!(vec![0, 3, 2, 0], Justify::SpaceBetween.gaps(3, 5));
+        assert_eq!(vec![2, 3, 2], Justify::SpaceAround.gaps(2, 7));
+        assert_eq!(vec![2, 1, 2], Justify::SpaceEvenly.gaps(2, 5));
+        assert_eq!(vec![0, 0], Justify::SpaceBetween.gaps(1, 5));
+        assert_eq!(vec![0], Justify::SpaceEvenly.gaps(0, 5));
+    }
+    #[test]
+    fn justify_space_around() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_d = Tag::new("<d>", "</d>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans.push(&Span::borrowed(&fmt_1, "ab"));
+        let truncation = TruncationStyle::Left("");
+        let mut hbox: HBox<Spans<Tag>> = Default::default();
+        for _ in 0..2 {
+            hbox.push(Box::new(TextWidget::new(
+                Cow::Borrowed(&spans),
+                Cow::Borrowed(&truncation),
+            )));
+        }
+        let filler = Spacer::with_fill(Span::borrowed(&fmt_d, "."));
+        hbox.set_justify(Justify::SpaceAround, Box::new(filler));
+        let expected = "<d>..</d><1>ab</1><d>...</d><1>ab</1><d>..</d>";
+        assert_eq!(expected, hbox.truncate(11).to_string());
+        let expected = "<1>aba</1>";
+        assert_eq!(expected, hbox.truncate(3).to_string());
+    }
+    #[test]
+    fn painted_width_mode_fits() {
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans.push(&Span::new(Cow::Borrowed(&fmt_2), Cow::Borrowed("0123")));
+        let truncator = TruncationStyle::Left("");
+        let mut hbox: HBox<Spans<Tag>> = Default::default();
+        hbox.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&spans),
+            Cow::Borrowed(&truncator),
+        )));
+        hbox.set_width_mode(WidthMode::Painted);
+        assert_eq!("<2>0123</2>", hbox.truncate(11).to_string());
+        assert_eq!("<2>012</2>", hbox.truncate(10).to_string());
+        assert_eq!("", hbox.truncate(7).to_string());
+    }
     #[test]
     fn make_hbox_literal() {
         let fmt_2 = Tag::new("<2>", "</2>");
@@ -182,4 +1409,39 @@ mod test {
         let expected = String::from("<2>01234</2><3>5...</3>");
         assert_eq!(expected, actual);
     }
+    #[test]
+    fn line_breaks_cut_to_first_line() {
+        let texts: Vec<Spans<Tag>> = vec![Spans::from("c\nd"), Spans::from("xyz")];
+        let truncation = TruncationStyle::Left("…");
+        let mut hbox: HBox<Spans<Tag>> = HBox::new();
+        for text in &texts {
+            hbox.push(Box::new(TextWidget::new(
+                Cow::Borrowed(text),
+                Cow::Borrowed(&truncation),
+            )));
+        }
+        assert_eq!(Width::Bounded(4), hbox.width());
+        assert_eq!("cxyz", hbox.truncate(6).to_string());
+        assert_eq!("c", hbox.truncate(1).to_string());
+        assert_eq!([1, 3], hbox.layout(6).widths.as_slice());
+    }
+    #[test]
+    fn nested_line_breaks() {
+        let texts: Vec<Spans<Tag>> = vec![Spans::from("ab"), Spans::from("c\nd")];
+        let truncation = TruncationStyle::Left("…");
+        let mut inner: HBox<Spans<Tag>> = HBox::new();
+        inner.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&texts[0]),
+            Cow::Borrowed(&truncation),
+        )));
+        inner.push(Box::new(PathWidget::new(Cow::Borrowed(&texts[1]))));
+        assert_eq!("abc", inner.truncate(10).to_string());
+        let mut outer: HBox<Spans<Tag>> = HBox::new();
+        outer.push(Box::new(inner));
+        outer.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&texts[1]),
+            Cow::Borrowed(&truncation),
+        )));
+        assert_eq!("abcc", outer.truncate(10).to_string());
+    }
 }
@@ -0,0 +1,179 @@
+use super::hbox::allocate;
+use crate::text::{BoundedWidth, Pushable, Spans, Width};
+#[cfg(doc)]
+use crate::widget::HBox;
+use crate::widget::{Align, Pad, Wrap};
+use std::borrow::Cow;
+
+/// Formats command line help as a column of flags beside wrapped
+/// descriptions:
+/// ```text
+///   -v, --verbose  Print more about what is
+///                  going on
+///   -q, --quiet    Print nothing
+/// ```
+/// The flag column and the descriptions share the width as a [`HBox`] would
+/// share it between two widgets, so the flag column is as wide as the widest
+/// flag unless that would leave the descriptions less than half the width.
+/// Descriptions wrap to their share. A flag too wide for its column
+/// gets lines of its own, with its description starting on the line below.
+pub struct HelpFormatter<'a, T: Clone> {
+    entries: Vec<Entry<'a, T>>,
+    indent: usize,
+    gap: usize,
+}
+
+/// A flag and its description.
+type Entry<'a, T> = (Cow<'a, Spans<T>>, Cow<'a, Spans<T>>);
+
+impl<'a, T: Clone> Default for HelpFormatter<'a, T> {
+    fn default() -> Self {
+        HelpFormatter {
+            entries: vec![],
+            indent: 2,
+            gap: 2,
+        }
+    }
+}
+
+impl<'a, T: Clone + Default + PartialEq> HelpFormatter<'a, T> {
+    /// Creates a formatter indented by two spaces, with two spaces between
+    /// the flags and the descriptions.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Adds a flag and its description.
+    pub fn push(&mut self, flag: Cow<'a, Spans<T>>, description: Cow<'a, Spans<T>>) {
+        self.entries.push((flag, description));
+    }
+    /// Sets the number of spaces before each flag.
+    pub fn set_indent(&mut self, indent: usize) {
+        self.indent = indent;
+    }
+    /// Sets the number of spaces between the flag column and the descriptions.
+    pub fn set_gap(&mut self, gap: usize) {
+        self.gap = gap;
+    }
+    /// Formats the help into lines at most `width` columns wide.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::HelpFormatter;
+    /// let verbose: Spans<Tag> = Spans::from("-v, --verbose");
+    /// let verbose_help: Spans<Tag> = Spans::from("Print more about what is going on");
+    /// let quiet: Spans<Tag> = Spans::from("-q, --quiet");
+    /// let quiet_help: Spans<Tag> = Spans::from("Print nothing");
+    /// let mut help = HelpFormatter::new();
+    /// help.push(Cow::Borrowed(&verbose), Cow::Borrowed(&verbose_help));
+    /// help.push(Cow::Borrowed(&quiet), Cow::Borrowed(&quiet_help));
+    /// let lines: Vec<String> = help.format(41).iter().map(|line| line.to_string()).collect();
+    /// assert_eq!(
+    ///     lines,
+    ///     vec![
+    ///         "  -v, --verbose  Print more about what is",
+    ///         "                 going on",
+    ///         "  -q, --quiet    Print nothing",
+    ///     ]
+    /// );
+    /// ```
+    pub fn format(&self, width: usize) -> Vec<Spans<T>> {
+        let flag_width = self
+            .entries
+            .iter()
+            .map(|(flag, _)| flag.bounded_width())
+            .max()
+            .unwrap_or_default();
+        let description_width = self
+            .entries
+            .iter()
+            .map(|(_, description)| description.bounded_width())
+            .max()
+            .unwrap_or_default();
+        let available = width.saturating_sub(self.indent + self.gap);
+        let measured = [
+            Width::Bounded(flag_width),
+            Width::Bounded(description_width),
+        ];
+        let widths = allocate(&measured, available);
+        let (flag_column, description_column) = (widths[0], widths[1]);
+        let pad = Pad::<T>::spaces(Align::Left);
+        let blank = Spans::default();
+        let indent = pad.pad(&blank, self.indent);
+        let hanging = pad.pad(&blank, self.indent + flag_column + self.gap);
+        let mut lines = vec![];
+        for (flag, description) in &self.entries {
+            let mut descriptions = Wrap::new(Cow::Borrowed(description.as_ref()))
+                .lines(description_column)
+                .into_iter();
+            if flag.bounded_width() <= flag_column {
+                let mut line = indent.clone();
+                line.push(&pad.pad(flag, flag_column));
+                if let Some(first) = descriptions.next() {
+                    line.push(&pad.pad(&blank, self.gap));
+                    line.push(&first);
+                }
+                lines.push(line);
+            } else {
+                let flag_lines = Wrap::new(Cow::Borrowed(flag.as_ref()))
+                    .lines(width.saturating_sub(self.indent).max(1));
+                for flag_line in flag_lines {
+                    let mut line = indent.clone();
+                    line.push(&flag_line);
+                    lines.push(line);
+                }
+            }
+            for description_line in descriptions {
+                let mut line = hanging.clone();
+                line.push(&description_line);
+                lines.push(line);
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    use crate::widget::fixtures::make_spans;
+    #[test]
+    fn help_wraps_descriptions() {
+        let fmt_f = Tag::new("<f>", "</f>");
+        let fmt_d = Tag::new("<d>", "</d>");
+        let all = make_spans(&fmt_f, "-a");
+        let all_help = make_spans(&fmt_d, "show all of it");
+        let long = make_spans(&fmt_f, "--long");
+        let long_help = make_spans(&fmt_d, "details");
+        let mut help = HelpFormatter::new();
+        help.set_indent(1);
+        help.set_gap(1);
+        help.push(Cow::Borrowed(&all), Cow::Borrowed(&all_help));
+        help.push(Cow::Borrowed(&long), Cow::Borrowed(&long_help));
+        let actual: Vec<String> = help.format(17).iter().map(ToString::to_string).collect();
+        let expected = vec![
+            String::from(" <f>-a</f>     <d>show all</d>"),
+            String::from("        <d>of it</d>"),
+            String::from(" <f>--long</f> <d>details</d>"),
+        ];
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn help_long_flag_own_line() {
+        let fmt_f = Tag::new("<f>", "</f>");
+        let fmt_d = Tag::new("<d>", "</d>");
+        let flag = make_spans(&fmt_f, "--flag");
+        let flag_help = make_spans(&fmt_d, "abc def");
+        let mut help = HelpFormatter::new();
+        help.push(Cow::Borrowed(&flag), Cow::Borrowed(&flag_help));
+        let actual: Vec<String> = help.format(10).iter().map(ToString::to_string).collect();
+        let expected = vec![
+            String::from("  <f>--flag</f>"),
+            String::from("       <d>abc</d>"),
+            String::from("       <d>def</d>"),
+        ];
+        assert_eq!(expected, actual);
+    }
+}
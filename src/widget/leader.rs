@@ -0,0 +1,119 @@
+use super::hbox::allocate;
+use crate::text::{BoundedWidth, Pushable, Span, Spans, Width, WidthSliceable};
+use crate::widget::{Repeat, TruncationStrategy};
+use std::borrow::Cow;
+
+/// Two pieces of text joined by a repeated leader, as in a table of contents:
+/// ```text
+/// Introduction.........1
+/// ```
+/// The right text is kept whole where possible, and the left text is truncated
+/// before the leader disappears entirely.
+pub struct Leader<'a, T: Clone, U: Clone> {
+    left: Cow<'a, Spans<T>>,
+    right: Cow<'a, Spans<T>>,
+    fill: Repeat<'a, Spans<T>>,
+    truncation_strategy: Cow<'a, U>,
+}
+
+impl<'a, T: Clone, U: Clone> Leader<'a, T, U> {
+    pub fn new(
+        left: Cow<'a, Spans<T>>,
+        fill: Spans<T>,
+        right: Cow<'a, Spans<T>>,
+        truncation_strategy: Cow<'a, U>,
+    ) -> Self {
+        Leader {
+            left,
+            right,
+            fill: Repeat::new(fill),
+            truncation_strategy,
+        }
+    }
+    /// Lays out the text in exactly a given width.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{Leader, TruncationStyle};
+    /// let title: Spans<Tag> = Spans::from("Introduction");
+    /// let page: Spans<Tag> = Spans::from("1");
+    /// let truncation = TruncationStyle::Left("…");
+    /// let leader = Leader::new(
+    ///     Cow::Borrowed(&title),
+    ///     Spans::from("."),
+    ///     Cow::Borrowed(&page),
+    ///     Cow::Borrowed(&truncation),
+    /// );
+    /// assert_eq!(leader.truncate(20).to_string(), "Introduction.......1");
+    /// assert_eq!(leader.truncate(8).to_string(), "Introd…1");
+    /// ```
+    pub fn truncate(&self, width: usize) -> Spans<T>
+    where
+        T: Default + PartialEq,
+        U: TruncationStrategy<Spans<T>>,
+    {
+        let right_width = self.right.bounded_width().min(width);
+        let measured = [Width::Bounded(self.left.bounded_width()), Width::Unbounded];
+        let widths = allocate(&measured, width - right_width);
+        let mut result: Spans<T> = Default::default();
+        result.push(&self.truncation_strategy.truncate(&self.left, widths[0]));
+        let fill_width = width - right_width - result.bounded_width();
+        result.push(&self.fill.slice_width(..fill_width));
+        let padding = width - right_width - result.bounded_width();
+        if padding > 0 {
+            result.push(&Span::new(
+                Cow::Owned(T::default()),
+                Cow::Owned(" ".repeat(padding)),
+            ));
+        }
+        result.push(&self.truncation_strategy.truncate(&self.right, right_width));
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    use crate::widget::fixtures::make_spans;
+    use crate::widget::TruncationStyle;
+    #[test]
+    fn leader_fills() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let fmt_d = Tag::new("<d>", "</d>");
+        let left = make_spans(&fmt_1, "name");
+        let right = make_spans(&fmt_2, "value");
+        let truncation = TruncationStyle::Left(make_spans(&fmt_1, "…"));
+        let leader = Leader::new(
+            Cow::Borrowed(&left),
+            make_spans(&fmt_d, ". "),
+            Cow::Borrowed(&right),
+            Cow::Borrowed(&truncation),
+        );
+        let actual = leader.truncate(16).to_string();
+        let expected = "<1>name</1><d>. . . .</d><2>value</2>";
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn leader_truncates_left_first() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let fmt_d = Tag::new("<d>", "</d>");
+        let left = make_spans(&fmt_1, "name");
+        let right = make_spans(&fmt_2, "value");
+        let truncation = TruncationStyle::Left(make_spans(&fmt_1, "…"));
+        let leader = Leader::new(
+            Cow::Borrowed(&left),
+            make_spans(&fmt_d, "."),
+            Cow::Borrowed(&right),
+            Cow::Borrowed(&truncation),
+        );
+        assert_eq!("<1>name</1><2>value</2>", leader.truncate(9).to_string());
+        assert_eq!("<1>na…</1><2>value</2>", leader.truncate(8).to_string());
+        assert_eq!("<2>value</2>", leader.truncate(5).to_string());
+        assert_eq!("<2>val</2><1>…</1>", leader.truncate(4).to_string());
+    }
+}
@@ -0,0 +1,124 @@
+use crate::text::{BoundedWidth, HasWidth, Pushable, Span, Spans, Width, WidthSliceable};
+use crate::widget::{Fitable, Repeat};
+use std::borrow::Cow;
+
+/// Text that scrolls through a window narrower than itself, wrapping around
+/// to its start, as in a news ticker.
+///
+/// The widget draws a single frame; incrementing the offset between frames
+/// makes the text scroll to the left. Text that fits in the window is drawn
+/// as it is.
+///
+/// # Example
+/// ```
+/// use std::borrow::Cow;
+/// use stylish_stringlike::text::{Spans, Tag};
+/// use stylish_stringlike::widget::{Fitable, Marquee};
+/// let text: Spans<Tag> = Spans::from("breaking news");
+/// let mut marquee = Marquee::new(Cow::Borrowed(&text));
+/// marquee.set_gap(Spans::from(" • "));
+/// assert_eq!(marquee.truncate(8).unwrap().to_string(), "breaking");
+/// marquee.set_offset(9);
+/// assert_eq!(marquee.truncate(8).unwrap().to_string(), "news • b");
+/// // The text wraps around once the offset passes its width and the gap.
+/// marquee.set_offset(17);
+/// assert_eq!(marquee.truncate(8).unwrap().to_string(), "reaking ");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Marquee<'a, T: Clone> {
+    content: Cow<'a, Spans<T>>,
+    gap: Spans<T>,
+    offset: usize,
+}
+
+impl<'a, T: Clone + Default> Marquee<'a, T> {
+    /// Creates a marquee showing the start of its content, with no gap
+    /// between the end of the content and its start.
+    pub fn new(content: Cow<'a, Spans<T>>) -> Self {
+        Marquee {
+            content,
+            gap: Default::default(),
+            offset: 0,
+        }
+    }
+    /// Sets text shown between the end of the content and its start as it
+    /// wraps around.
+    pub fn set_gap(&mut self, gap: Spans<T>) {
+        self.gap = gap;
+    }
+    /// Sets how many columns the content has scrolled to the left.
+    pub fn set_offset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+    /// Returns how many columns the content has scrolled to the left.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, T: Clone> HasWidth for Marquee<'a, T> {
+    /// The width of the content, which is the widest window worth scrolling
+    /// through.
+    fn width(&self) -> Width {
+        Width::Bounded(self.content.bounded_width())
+    }
+}
+
+impl<'a, T: Clone + Default + PartialEq> Fitable<Spans<T>> for Marquee<'a, T> {
+    /// Draws the window of the content at the current offset. Columns left
+    /// empty by wide characters cut at the edges of the window are filled
+    /// with spaces at its end.
+    fn truncate(&self, width: usize) -> Option<Spans<T>> {
+        let content_width = self.content.bounded_width();
+        if width == 0 || content_width == 0 {
+            return None;
+        }
+        if content_width <= width {
+            return Some(self.content.as_ref().clone());
+        }
+        let mut cycle = self.content.as_ref().clone();
+        cycle.push(&self.gap);
+        let start = self.offset % cycle.bounded_width();
+        let mut result = Repeat::new(&cycle)
+            .slice_width(start..start + width)
+            .unwrap_or_default();
+        let rest = width.saturating_sub(result.bounded_width());
+        if rest > 0 {
+            result.push(&Span::new(
+                Cow::Owned(T::default()),
+                Cow::Owned(" ".repeat(rest)),
+            ));
+        }
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::Tag;
+    #[test]
+    fn marquee_scrolls_through_wide_text() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = Spans::styled(fmt_1, "a🙈b");
+        let mut marquee = Marquee::new(Cow::Owned(text));
+        marquee.set_gap(Spans::styled(fmt_2, "-"));
+        let frames: Vec<String> = (0..6)
+            .map(|offset| {
+                marquee.set_offset(offset);
+                marquee.truncate(3).unwrap().to_string()
+            })
+            .collect();
+        let expected = [
+            "<1>a🙈</1>",
+            "<1>🙈b</1>",
+            "<1>b</1><2>-</2> ",
+            "<1>b</1><2>-</2><1>a</1>",
+            "<2>-</2><1>a</1> ",
+            "<1>a🙈</1>",
+        ];
+        assert_eq!(expected.to_vec(), frames);
+        assert_eq!(None, marquee.truncate(0));
+    }
+}
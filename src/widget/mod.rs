@@ -1,12 +1,44 @@
 //! Provides some widgets for displaying text objects in the [`crate::text`] module.
+mod border;
+mod diagnostic;
+#[cfg(test)]
+mod fixtures;
+mod group;
 mod hbox;
+mod help;
+mod leader;
+mod marquee;
+mod numeric_column;
+mod pad;
+mod path;
 mod repeat;
+mod scrollbar;
+mod segments;
+mod spacer;
+mod table;
 mod text_widget;
 mod truncatable;
+mod vbox;
+mod wrap;
+pub use border::*;
+pub use diagnostic::*;
+pub use group::*;
 pub use hbox::*;
+pub use help::*;
+pub use leader::*;
+pub use marquee::*;
+pub use numeric_column::*;
+pub use pad::*;
+pub use path::*;
 pub use repeat::*;
+pub use scrollbar::*;
+pub use segments::*;
+pub use spacer::*;
+pub use table::*;
 pub use text_widget::*;
 pub use truncatable::*;
+pub use vbox::*;
+pub use wrap::*;
 
 #[cfg(test)]
 mod test {
@@ -111,7 +143,7 @@ mod test {
         let ellipsis = "…";
         let ellipsis_span = make_spans(&ellipsis_style, ellipsis);
         let truncation = TruncationStyle::Left(Some(ellipsis_span));
-        let hbox = vec![&first_span, &second_span]
+        let hbox = [&first_span, &second_span]
             .iter()
             .map(|s| {
                 let foo: Box<dyn Fitable<_>> =
@@ -273,7 +305,7 @@ mod test {
         let ellipsis = "…";
         let ellipsis_span = make_spans(&ellipsis_style, ellipsis);
         let truncation = TruncationStyle::Right(Some(ellipsis_span));
-        let hbox = vec![&first_span, &second_span]
+        let hbox = [&first_span, &second_span]
             .iter()
             .map(|s| {
                 let b: Box<dyn Fitable<_>> =
@@ -439,7 +471,7 @@ mod test {
         let ellipsis = "…";
         let ellipsis_span = make_spans(&ellipsis_style, ellipsis);
         let truncation = TruncationStyle::Inner(Some(ellipsis_span));
-        let hbox = vec![&first_span, &second_span]
+        let hbox = [&first_span, &second_span]
             .iter()
             .map(|s| {
                 let b: Box<dyn Fitable<_>> =
@@ -0,0 +1,192 @@
+use crate::text::{BoundedWidth, Pushable, RawText, Sliceable, Span, Spans, WidthSliceable};
+use std::borrow::Cow;
+
+/// A column of numbers right-aligned on their decimal points.
+///
+/// Numbers are padded with spaces in the padding style. When the column is too
+/// narrow, digits after the decimal point are dropped before anything else.
+pub struct NumericColumn<'a, T: Clone> {
+    values: Vec<Cow<'a, Spans<T>>>,
+    padding: Cow<'a, T>,
+    decimal_point: char,
+}
+
+impl<'a, T: Clone> NumericColumn<'a, T> {
+    /// Creates an empty column using `.` as the decimal point.
+    pub fn new(padding: Cow<'a, T>) -> Self {
+        Self::with_decimal_point(padding, '.')
+    }
+    /// Creates an empty column using a custom decimal point, such as `,`.
+    pub fn with_decimal_point(padding: Cow<'a, T>, decimal_point: char) -> Self {
+        NumericColumn {
+            values: vec![],
+            padding,
+            decimal_point,
+        }
+    }
+    /// Adds a number to the bottom of the column.
+    pub fn push(&mut self, value: Cow<'a, Spans<T>>) {
+        self.values.push(value);
+    }
+    /// Aligns every number in the column to fit in a given width.
+    ///
+    /// Returns [`None`] if the integer part of some number does not fit.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::NumericColumn;
+    /// let pad = Tag::new("", "");
+    /// let mut column: NumericColumn<Tag> = NumericColumn::new(Cow::Borrowed(&pad));
+    /// column.push(Cow::Owned(Spans::from("3.14159")));
+    /// column.push(Cow::Owned(Spans::from("100.5")));
+    /// let rows: Vec<String> = column
+    ///     .truncate(8)
+    ///     .unwrap()
+    ///     .iter()
+    ///     .map(|row| row.to_string())
+    ///     .collect();
+    /// assert_eq!(rows, vec!["  3.1415", "100.5   "]);
+    /// ```
+    pub fn truncate(&self, width: usize) -> Option<Vec<Spans<T>>>
+    where
+        T: Default + PartialEq,
+    {
+        let parts: Vec<(Spans<T>, Option<Spans<T>>)> = self
+            .values
+            .iter()
+            .map(|value| match value.raw_ref().find(self.decimal_point) {
+                Some(point) => (
+                    value.slice(..point).unwrap_or_default(),
+                    value.slice(point..),
+                ),
+                None => (value.clone().into_owned(), None),
+            })
+            .collect();
+        let integer_width = parts
+            .iter()
+            .map(|(integer, _)| integer.bounded_width())
+            .max()
+            .unwrap_or(0);
+        if integer_width > width {
+            return None;
+        }
+        let point_width = self.decimal_point.to_string().bounded_width();
+        let fraction_width = parts
+            .iter()
+            .map(|(_, fraction)| fraction.bounded_width())
+            .max()
+            .unwrap_or(0)
+            .min(width - integer_width);
+        // A lone decimal point carries no information.
+        let fraction_width = if fraction_width > point_width {
+            fraction_width
+        } else {
+            0
+        };
+        let lead = width - integer_width - fraction_width;
+        let rows = parts
+            .iter()
+            .map(|(integer, fraction)| {
+                let mut row: Spans<T> = Default::default();
+                self.pad(&mut row, lead + integer_width - integer.bounded_width());
+                row.push(integer);
+                let fraction = if fraction_width > 0 {
+                    fraction.slice_width(..fraction_width)
+                } else {
+                    None
+                };
+                row.push(&fraction);
+                self.pad(&mut row, fraction_width - fraction.bounded_width());
+                row
+            })
+            .collect();
+        Some(rows)
+    }
+    fn pad(&self, row: &mut Spans<T>, width: usize)
+    where
+        T: PartialEq,
+    {
+        if width > 0 {
+            row.push(&Span::new(
+                self.padding.clone(),
+                Cow::Owned(" ".repeat(width)),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    use crate::widget::fixtures::make_spans;
+    #[test]
+    fn align_points() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let pad = Tag::new("<p>", "</p>");
+        let mut column = NumericColumn::new(Cow::Borrowed(&pad));
+        column.push(Cow::Owned(make_spans(&fmt_1, "1.5")));
+        column.push(Cow::Owned(make_spans(&fmt_1, "10.25")));
+        column.push(Cow::Owned(make_spans(&fmt_1, "7")));
+        let actual: Vec<String> = column
+            .truncate(6)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let expected = vec![
+            String::from("<p>  </p><1>1.5</1><p> </p>"),
+            String::from("<p> </p><1>10.25</1>"),
+            String::from("<p>  </p><1>7</1><p>   </p>"),
+        ];
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn drop_precision() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let pad = Tag::new("<p>", "</p>");
+        let mut column = NumericColumn::new(Cow::Borrowed(&pad));
+        column.push(Cow::Owned(make_spans(&fmt_1, "1.5")));
+        column.push(Cow::Owned(make_spans(&fmt_1, "10.25")));
+        let actual: Vec<String> = column
+            .truncate(4)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let expected = vec![
+            String::from("<p> </p><1>1.5</1>"),
+            String::from("<1>10.2</1>"),
+        ];
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn drop_lone_point() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let pad = Tag::new("<p>", "</p>");
+        let mut column = NumericColumn::with_decimal_point(Cow::Borrowed(&pad), ',');
+        column.push(Cow::Owned(make_spans(&fmt_1, "1,5")));
+        column.push(Cow::Owned(make_spans(&fmt_1, "10,25")));
+        let actual: Vec<String> = column
+            .truncate(3)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let expected = vec![
+            String::from("<p>  </p><1>1</1>"),
+            String::from("<p> </p><1>10</1>"),
+        ];
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn integer_too_wide() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let pad = Tag::new("<p>", "</p>");
+        let mut column = NumericColumn::new(Cow::Borrowed(&pad));
+        column.push(Cow::Owned(make_spans(&fmt_1, "12345.6")));
+        assert!(column.truncate(4).is_none());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::text::{BoundedWidth, Format, Pushable, RawText, Span, Spans, WidthSliceable};
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+/// Where text sits within a wider space.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Align {
+    /// Text on the left, fill on the right.
+    Left,
+    /// Text on the right, fill on the left.
+    Right,
+    /// Text in the middle, with any odd column of fill on the right.
+    Center,
+}
+
+/// Chooses the style of fill from the styles of the content beside it, so
+/// that fill can carry on a background through padding, as in status lines.
+///
+/// Any function taking the fill style and the styles before and after the
+/// fill is a provider.
+pub trait StyleProvider<T> {
+    /// Returns the style for fill with style `fill` that follows content
+    /// ending in `before` and precedes content starting with `after`. Either
+    /// is [`None`] where the fill is at an end of the text.
+    fn fill_style(&self, fill: &T, before: Option<&T>, after: Option<&T>) -> T;
+}
+
+impl<T, F> StyleProvider<T> for F
+where
+    F: Fn(&T, Option<&T>, Option<&T>) -> T,
+{
+    fn fill_style(&self, fill: &T, before: Option<&T>, after: Option<&T>) -> T {
+        self(fill, before, after)
+    }
+}
+
+/// Gives fill without a background of its own the background of the content
+/// before it, or failing that the content after it. Other attributes, such as
+/// underlines, are not carried into the fill.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct InheritBackground;
+
+impl StyleProvider<Format> for InheritBackground {
+    fn fill_style(&self, fill: &Format, before: Option<&Format>, after: Option<&Format>) -> Format {
+        let inherited = before
+            .and_then(|s| s.bg)
+            .or_else(|| after.and_then(|s| s.bg));
+        Format {
+            bg: fill.bg.or(inherited),
+            ..*fill
+        }
+    }
+}
+
+/// Returns the styles at the start and end of some text.
+pub(crate) fn edge_styles<T: Clone>(text: &Spans<T>) -> (Option<&T>, Option<&T>) {
+    let last = text.len().checked_sub(1).and_then(|i| text.style_at(i));
+    (text.style_at(0), last)
+}
+
+/// Pads text to a width by repeating a fill, such as a space, a figure space
+/// (`'\u{2007}'`) or a dotted leader.
+///
+/// The fill is repeated grapheme by grapheme, so a multi-grapheme fill like
+/// `"…."` may be cut short. Any columns the fill cannot occupy exactly, such as
+/// a single column left over by a double-width fill, are filled with unstyled
+/// spaces, unless a [`StyleProvider`] styles them along with the fill.
+#[derive(Clone)]
+pub struct Pad<'a, T: Clone> {
+    fill: Span<'a, T>,
+    align: Align,
+    provider: Option<Arc<dyn StyleProvider<T> + Send + Sync + 'a>>,
+}
+
+impl<'a, T: Clone + fmt::Debug> fmt::Debug for Pad<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pad")
+            .field("fill", &self.fill)
+            .field("align", &self.align)
+            .field("provider", &self.provider.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+impl<'a, T: Clone + Default + PartialEq> Pad<'a, T> {
+    pub fn new(fill: Span<'a, T>, align: Align) -> Self {
+        Pad {
+            fill,
+            align,
+            provider: None,
+        }
+    }
+    /// Pads with unstyled spaces.
+    pub fn spaces(align: Align) -> Self {
+        Pad::new(
+            Span::new(Cow::Owned(T::default()), Cow::Borrowed(" ")),
+            align,
+        )
+    }
+    /// Styles the fill on each side of the text from the style of the text
+    /// beside it.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Color, Format, Span, Spans};
+    /// use stylish_stringlike::widget::{Align, InheritBackground, Pad};
+    /// let status = Format::new().fg(Color::Black).bg(Color::Blue);
+    /// let mut pad = Pad::spaces(Align::Center);
+    /// pad.set_style_provider(InheritBackground);
+    /// let padded = pad.pad(&Spans::styled(status, "main"), 8);
+    /// assert_eq!(padded.style_at(0), Some(&Format::new().bg(Color::Blue)));
+    /// assert_eq!(padded.style_at(7), Some(&Format::new().bg(Color::Blue)));
+    /// ```
+    pub fn set_style_provider<P>(&mut self, provider: P)
+    where
+        P: StyleProvider<T> + Send + Sync + 'a,
+    {
+        self.provider = Some(Arc::new(provider));
+    }
+    /// Fills a width on one side of text, styled by the provider if any.
+    fn fill_beside(&self, width: usize, before: Option<&T>, after: Option<&T>) -> Spans<T> {
+        match &self.provider {
+            Some(provider) => {
+                let style = provider.fill_style(self.fill.style(), before, after);
+                let fill = Span::new(Cow::Borrowed(&style), Cow::Borrowed(self.fill.raw_ref()));
+                fill_with_rest(&fill, width, &style)
+            }
+            None => fill(&self.fill, width),
+        }
+    }
+    /// Pads text to a width. Text already at least as wide is returned
+    /// unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Span, Spans, Tag};
+    /// use stylish_stringlike::widget::{Align, Pad};
+    /// let dim = Tag::new("<d>", "</d>");
+    /// let pad = Pad::new(Span::borrowed(&dim, "."), Align::Left);
+    /// let padded = pad.pad(&Spans::from("Chapter 1"), 14);
+    /// assert_eq!(padded.to_string(), "Chapter 1<d>.....</d>");
+    /// ```
+    pub fn pad(&self, text: &Spans<T>, width: usize) -> Spans<T> {
+        let space = width.saturating_sub(text.bounded_width());
+        let (left, right) = match self.align {
+            Align::Left => (0, space),
+            Align::Right => (space, 0),
+            Align::Center => (space / 2, space - space / 2),
+        };
+        let (first, last) = edge_styles(text);
+        let mut result = self.fill_beside(left, None, first);
+        result.push(text);
+        result.push(&self.fill_beside(right, last, None));
+        result
+    }
+}
+
+/// Repeats a fill to exactly a given width, completing it with unstyled spaces
+/// where the fill does not fit exactly.
+pub(crate) fn fill<T: Clone + Default + PartialEq>(fill: &Span<T>, width: usize) -> Spans<T> {
+    fill_with_rest(fill, width, &T::default())
+}
+
+/// Repeats a fill to exactly a given width, completing it with spaces in the
+/// style `rest_style`.
+fn fill_with_rest<T: Clone + Default + PartialEq>(
+    fill: &Span<T>,
+    width: usize,
+    rest_style: &T,
+) -> Spans<T> {
+    let mut result: Spans<T> = Default::default();
+    let fill_width = fill.bounded_width();
+    if width == 0 {
+        return result;
+    }
+    if let Some(count) = width.checked_div(fill_width) {
+        let repeated = Span::new(
+            fill.style().clone(),
+            Cow::Owned(fill.raw_ref().repeat(count + 1)),
+        );
+        result.push(&repeated.slice_width(..width));
+    }
+    let rest = width - result.bounded_width();
+    if rest > 0 {
+        result.push(&Span::new(
+            Cow::Borrowed(rest_style),
+            Cow::Owned(" ".repeat(rest)),
+        ));
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    use crate::widget::fixtures::make_spans;
+    #[test]
+    fn pad_alignments() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_p = Tag::new("<p>", "</p>");
+        let text = make_spans(&fmt_1, "ab");
+        let fill = Span::borrowed(&fmt_p, "-");
+        let left = Pad::new(fill.clone(), Align::Left).pad(&text, 5);
+        let right = Pad::new(fill.clone(), Align::Right).pad(&text, 5);
+        let center = Pad::new(fill, Align::Center).pad(&text, 5);
+        assert_eq!("<1>ab</1><p>---</p>", left.to_string());
+        assert_eq!("<p>---</p><1>ab</1>", right.to_string());
+        assert_eq!("<p>-</p><1>ab</1><p>--</p>", center.to_string());
+    }
+    #[test]
+    fn pad_too_wide() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let text = make_spans(&fmt_1, "abc");
+        let actual = Pad::<Tag>::spaces(Align::Right).pad(&text, 2);
+        assert_eq!(text, actual);
+    }
+    #[test]
+    fn pad_figure_space() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let text = make_spans(&fmt_1, "42");
+        let fill = Span::new(Cow::Owned(Tag::default()), Cow::Borrowed("\u{2007}"));
+        let actual = Pad::new(fill, Align::Right).pad(&text, 4);
+        assert_eq!(4, actual.bounded_width());
+        assert_eq!("\u{2007}\u{2007}42", actual.raw());
+    }
+    #[test]
+    fn pad_multi_grapheme_fill() {
+        let fmt_p = Tag::new("<p>", "</p>");
+        let fill = Span::borrowed(&fmt_p, "….");
+        let actual = Pad::new(fill, Align::Left).pad(&Spans::from("a"), 6);
+        assert_eq!("a<p>….….…</p>", actual.to_string());
+    }
+    #[test]
+    fn pad_wide_fill() {
+        let fmt_p = Tag::new("<p>", "</p>");
+        let fill = Span::borrowed(&fmt_p, "\u{3000}");
+        let actual = Pad::new(fill, Align::Left).pad(&Spans::from("a"), 4);
+        assert_eq!(4, actual.bounded_width());
+        assert_eq!("a<p>\u{3000}</p> ", actual.to_string());
+    }
+    #[test]
+    fn pad_provider_sees_neighbours() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let text = make_spans(&fmt_1, "a").then(fmt_2, "b");
+        let fill = Span::new(Cow::Owned(Tag::default()), Cow::Borrowed("\u{3000}"));
+        let mut pad = Pad::new(fill, Align::Center);
+        pad.set_style_provider(|_: &Tag, before: Option<&Tag>, after: Option<&Tag>| {
+            before.or(after).cloned().unwrap_or_default()
+        });
+        let actual = pad.pad(&text, 7);
+        assert_eq!("<1>\u{3000}a</1><2>b\u{3000} </2>", actual.to_string());
+    }
+    #[test]
+    fn pad_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+        let mut pad = Pad::<Tag>::spaces(Align::Left);
+        pad.set_style_provider(|fill: &Tag, _: Option<&Tag>, _: Option<&Tag>| fill.clone());
+        assert_send_sync(&pad);
+    }
+}
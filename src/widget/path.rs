@@ -0,0 +1,197 @@
+use crate::text::{
+    BoundedWidth, HasWidth, Pushable, RawText, Spans, Split, Splitable, Width, WidthSliceable,
+};
+use crate::widget::{Fitable, Measure};
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A path that is abbreviated to fit, in the way the fish shell shortens
+/// paths in its prompt: segments are cut to their first letter, from the
+/// left, until the path fits, and the last segment is kept whole. Leading
+/// dots are kept with the letters of hidden segments, so `.config` becomes
+/// `.c`.
+///
+/// The path keeps its styles, and separators are never abbreviated. A path
+/// too wide to fit once every segment it may abbreviate is abbreviated is
+/// cut from the left, keeping its end.
+///
+/// # Example
+/// ```
+/// use std::borrow::Cow;
+/// use stylish_stringlike::text::{Spans, Tag};
+/// use stylish_stringlike::widget::{Fitable, PathWidget};
+/// let path: Spans<Tag> = Spans::from("~/.config/fish/functions");
+/// let mut widget = PathWidget::new(Cow::Borrowed(&path));
+/// assert_eq!(widget.truncate(24).unwrap().to_string(), "~/.config/fish/functions");
+/// assert_eq!(widget.truncate(20).unwrap().to_string(), "~/.c/fish/functions");
+/// assert_eq!(widget.truncate(0).map(|path| path.to_string()), None);
+/// assert_eq!(widget.abbreviate().to_string(), "~/.c/f/functions");
+/// widget.set_keep_last(2);
+/// assert_eq!(widget.abbreviate().to_string(), "~/.c/fish/functions");
+/// ```
+#[derive(Clone, Debug)]
+pub struct PathWidget<'a, T: Clone> {
+    path: Cow<'a, Spans<T>>,
+    separator: String,
+    length: usize,
+    keep_first: bool,
+    keep_last: usize,
+}
+
+impl<'a, T: Clone + Default + PartialEq> PathWidget<'a, T> {
+    /// Creates a widget for a path separated by `/` that abbreviates
+    /// segments to one letter, keeping the last segment whole.
+    pub fn new(path: Cow<'a, Spans<T>>) -> Self {
+        PathWidget {
+            path,
+            separator: String::from("/"),
+            length: 1,
+            keep_first: false,
+            keep_last: 1,
+        }
+    }
+    /// Sets the text separating segments of the path.
+    pub fn set_separator(&mut self, separator: &str) {
+        self.separator = separator.to_string();
+    }
+    /// Sets how many graphemes of each abbreviated segment are kept, not
+    /// counting leading dots.
+    pub fn set_abbreviation_length(&mut self, length: usize) {
+        self.length = length;
+    }
+    /// Sets whether the first segment, such as a drive or a home directory,
+    /// is kept whole.
+    pub fn set_keep_first(&mut self, keep_first: bool) {
+        self.keep_first = keep_first;
+    }
+    /// Sets how many segments at the end of the path are kept whole.
+    pub fn set_keep_last(&mut self, keep_last: usize) {
+        self.keep_last = keep_last;
+    }
+    /// Splits the path into its segments and separators.
+    fn splits(&self) -> Vec<Split<Spans<T>, Spans<T>>> {
+        self.path
+            .split(self.separator.as_str())
+            .map(|split| Split {
+                segment: split.segment.filter(|segment| !segment.is_empty()),
+                delim: split.delim,
+            })
+            .collect()
+    }
+    /// Returns the indices of the splits whose segments may be abbreviated,
+    /// from the left.
+    fn abbreviable(&self, splits: &[Split<Spans<T>, Spans<T>>]) -> Vec<usize> {
+        let segments: Vec<usize> = (0..splits.len())
+            .filter(|&i| splits[i].segment.is_some())
+            .collect();
+        let first = usize::from(self.keep_first);
+        let last = segments.len().saturating_sub(self.keep_last);
+        segments.get(first..last).unwrap_or_default().to_vec()
+    }
+    /// Cuts a segment to its leading dots and the abbreviation length.
+    fn abbreviate_segment(&self, segment: &Spans<T>) -> Spans<T> {
+        let dots = segment
+            .raw_ref()
+            .graphemes(true)
+            .take_while(|g| *g == ".")
+            .count();
+        segment
+            .slice_graphemes(..dots + self.length)
+            .unwrap_or_else(|| segment.clone())
+    }
+    /// Abbreviates the segments at `indices`, then joins the path back
+    /// together.
+    fn join(&self, mut splits: Vec<Split<Spans<T>, Spans<T>>>, indices: &[usize]) -> Spans<T> {
+        for &i in indices {
+            splits[i].segment = splits[i]
+                .segment
+                .as_ref()
+                .map(|segment| self.abbreviate_segment(segment));
+        }
+        let mut result = Spans::default();
+        for split in splits {
+            result.push(&split.segment);
+            result.push(&split.delim);
+        }
+        result
+    }
+    /// Abbreviates every segment that may be abbreviated, as fish does.
+    pub fn abbreviate(&self) -> Spans<T> {
+        let splits = self.splits();
+        let abbreviable = self.abbreviable(&splits);
+        self.join(splits, &abbreviable)
+    }
+}
+
+impl<'a, T: Clone> HasWidth for PathWidget<'a, T> {
+    fn width(&self) -> Width {
+        Width::Bounded(self.path.bounded_width())
+    }
+}
+
+impl<'a, T: Clone + Default + PartialEq> Fitable<Spans<T>> for PathWidget<'a, T> {
+    /// Abbreviates as few segments as fit the path in the width, or
+    /// returns [`None`] for a width of zero.
+    fn truncate(&self, width: usize) -> Option<Spans<T>> {
+        if width == 0 {
+            return None;
+        }
+        let splits = self.splits();
+        let abbreviable = self.abbreviable(&splits);
+        let mut excess = self.path.bounded_width().saturating_sub(width);
+        let mut count = 0;
+        while excess > 0 && count < abbreviable.len() {
+            if let Some(segment) = &splits[abbreviable[count]].segment {
+                let saved =
+                    segment.bounded_width() - self.abbreviate_segment(segment).bounded_width();
+                excess = excess.saturating_sub(saved);
+            }
+            count += 1;
+        }
+        let result = self.join(splits, &abbreviable[..count]);
+        let result_width = result.bounded_width();
+        if result_width <= width {
+            Some(result)
+        } else {
+            result.slice_width(result_width - width..)
+        }
+    }
+    /// The path wants its full width, and needs at least the width of its
+    /// abbreviation.
+    fn measure(&self, _width_hint: usize) -> Measure {
+        Measure {
+            min: self.abbreviate().bounded_width(),
+            preferred: self.width(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::Tag;
+    #[test]
+    fn styled_path_abbreviates() {
+        let dir = Tag::new("<d>", "</d>");
+        let sep = Tag::new("<s>", "</s>");
+        let path = Spans::styled(sep.clone(), "::")
+            .then(dir.clone(), "Ünïcode")
+            .then(sep.clone(), "::")
+            .then(dir.clone(), "tests")
+            .then(sep, "::")
+            .then(dir, "main");
+        let mut widget = PathWidget::new(Cow::Borrowed(&path));
+        widget.set_separator("::");
+        widget.set_abbreviation_length(2);
+        widget.set_keep_first(true);
+        assert_eq!(
+            widget.truncate(19).unwrap().to_string(),
+            "<s>::</s><d>Ünïcode</d><s>::</s><d>te</d><s>::</s><d>main</d>"
+        );
+        assert_eq!(
+            widget.truncate(8).unwrap().to_string(),
+            "<d>te</d><s>::</s><d>main</d>"
+        );
+        assert_eq!(widget.measure(0).min, 19);
+    }
+}
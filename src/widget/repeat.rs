@@ -1,12 +1,17 @@
-use crate::text::{BoundedWidth, HasWidth, Joinable, Width, WidthSliceable};
-use std::ops::{Bound, RangeBounds};
+use crate::text::{bounds, BoundedWidth, HasWidth, Joinable, Spans, Width, WidthSliceable};
+use crate::widget::pad::edge_styles;
+use crate::widget::StyleProvider;
+use crate::Error;
+use std::ops::RangeBounds;
 
 use std::marker::PhantomData;
 
-/// A text widget that repeats its content arbitrarily many times.
+/// A text widget that repeats its content arbitrarily many times, or a
+/// fixed number of times.
 #[derive(Clone, Debug)]
 pub struct Repeat<'a, T> {
     content: T,
+    count: Option<usize>,
     _marker: PhantomData<&'a ()>,
 }
 
@@ -14,66 +19,106 @@ impl<'a, T> Repeat<'a, T> {
     pub fn new(content: T) -> Repeat<'a, T> {
         Repeat {
             content,
+            count: None,
+            _marker: Default::default(),
+        }
+    }
+    /// Creates a widget that repeats its content a fixed number of times, so
+    /// that it has a bounded width and takes part in width allocation like
+    /// any other text.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{HasWidth, Spans, Tag, Width, WidthSliceable};
+    /// use stylish_stringlike::widget::{HBox, Repeat, TextWidget, TruncationStyle};
+    /// let dots: Spans<Tag> = Spans::from(". ");
+    /// let leader = Repeat::times(dots, 3);
+    /// assert_eq!(leader.width(), Width::Bounded(6));
+    /// assert_eq!(leader.slice_width(3..).unwrap().to_string(), " . ");
+    /// let title: Spans<Tag> = Spans::from("Title");
+    /// let truncation = TruncationStyle::Left("");
+    /// let mut hbox: HBox<Spans<Tag>> = HBox::new();
+    /// hbox.push(Box::new(TextWidget::new(Cow::Borrowed(&title), Cow::Borrowed(&truncation))));
+    /// hbox.push(Box::new(TextWidget::new(Cow::Borrowed(&leader), Cow::Borrowed(&truncation))));
+    /// assert_eq!(hbox.truncate(20).to_string(), "Title. . . ");
+    /// ```
+    pub fn times(content: T, count: usize) -> Repeat<'a, T> {
+        Repeat {
+            content,
+            count: Some(count),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<'a, S: Clone + Default + PartialEq> Repeat<'a, Spans<S>> {
+    /// Creates a widget that repeats this content restyled to fill the space
+    /// between two pieces of text, so that the fill can carry on the styles
+    /// of the text around it.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Color, Format, Spans, WidthSliceable};
+    /// use stylish_stringlike::widget::{InheritBackground, Repeat};
+    /// let title = Spans::styled(Format::new().bold().bg(Color::Blue), "Title");
+    /// let page = Spans::styled(Format::new().bg(Color::Blue), "12");
+    /// let dots = Repeat::new(Spans::styled(Format::new().fg(Color::White), "."));
+    /// let leader = dots.between(&title, &page, &InheritBackground);
+    /// assert_eq!(
+    ///     leader.slice_width(..3).unwrap(),
+    ///     Spans::styled(Format::new().fg(Color::White).bg(Color::Blue), "...")
+    /// );
+    /// ```
+    pub fn between<P>(
+        &self,
+        before: &Spans<S>,
+        after: &Spans<S>,
+        provider: &P,
+    ) -> Repeat<'a, Spans<S>>
+    where
+        P: StyleProvider<S>,
+    {
+        let before = edge_styles(before).1;
+        let after = edge_styles(after).0;
+        Repeat {
+            content: self
+                .content
+                .map_style(|style| provider.fill_style(style, before, after)),
+            count: self.count,
             _marker: Default::default(),
         }
     }
 }
 
-impl<'a, T> HasWidth for Repeat<'a, T> {
+impl<'a, T: BoundedWidth> HasWidth for Repeat<'a, T> {
     fn width(&self) -> Width {
-        Width::Unbounded
+        match self.count {
+            Some(count) => Width::Bounded(count * self.content.bounded_width()),
+            None => Width::Unbounded,
+        }
     }
 }
 
 impl<'a, T, U> WidthSliceable for Repeat<'a, T>
 where
-    T: BoundedWidth + WidthSliceable<Output = T> + Joinable<T, Output = U>,
-    U: Default + Joinable<U, Output = U> + Joinable<T, Output = U> + BoundedWidth + 'a,
+    T: BoundedWidth + WidthSliceable,
+    T::Output: Joinable<T::Output, Output = U>,
+    U: Default + Joinable<U, Output = U> + Joinable<T::Output, Output = U> + BoundedWidth + 'a,
 {
     type Output = U;
     fn slice_width<R>(&self, range: R) -> Option<Self::Output>
     where
         R: RangeBounds<usize>,
     {
-        use std::ops::Bound::*;
-        fn shift_range<R: RangeBounds<usize>>(
-            range: &R,
-            shift: i32,
-        ) -> Option<(Bound<usize>, Bound<usize>)> {
-            fn ss(target: usize, shift: i32) -> usize {
-                if shift < 0 {
-                    target.saturating_sub(shift.abs() as usize)
-                } else {
-                    target + shift as usize
-                }
-            }
-            let start = match range.start_bound() {
-                Excluded(s) => Excluded(ss(*s, shift)),
-                Included(s) => Included(ss(*s, shift)),
-                Unbounded => Unbounded,
-            };
-            let end = match range.end_bound() {
-                Excluded(e) => Excluded(ss(*e, shift)),
-                Included(e) => {
-                    if *e as i32 + shift < 0 {
-                        Excluded(0)
-                    } else {
-                        Included(ss(*e, shift))
-                    }
-                }
-                Unbounded => return None,
-            };
-            Some((start, end))
-        }
-        let target_width = match (range.start_bound(), range.end_bound()) {
-            (_, Unbounded) => return None,
-            (Unbounded, Excluded(e)) => *e,
-            (Unbounded, Included(e)) => *e + 1,
-            (Included(s), Excluded(e)) => e.saturating_sub(*s),
-            (Included(s), Included(e)) => (*e + 1).saturating_sub(*s),
-            (Excluded(s), Excluded(e)) => e.saturating_sub(*s + 1),
-            (Excluded(s), Included(e)) => (*e + 1).saturating_sub(*s + 1),
+        let total = self.count.map(|count| count * self.content.bounded_width());
+        let (start, end) = match (bounds::normalize(&range), total) {
+            ((start, Some(end)), Some(total)) => (start, end.min(total)),
+            ((start, Some(end)), None) => (start, end),
+            ((start, None), Some(total)) => (start, total),
+            ((_, None), None) => return None,
         };
+        let target_width = end.saturating_sub(start);
         if target_width == 0 {
             return None;
         }
@@ -82,30 +127,53 @@ where
         if self_width == 0 {
             return None;
         }
-        let mut res: U = Default::default();
-
-        let mut segment = 0;
-        let mut started = false;
-        loop {
-            let shifted_range = shift_range(&range, -((segment * self_width) as i32));
-            if let Some(shifted_range) = shifted_range {
-                let sliced = self.content.slice_width(shifted_range);
-                if let Some(sliced) = sliced {
-                    started = true;
-                    res = res.join(&sliced)
-                } else if started {
-                    break;
-                }
+        // Only the segments at the ends are cut; the ones between are whole.
+        let first = start / self_width;
+        let last = (end - 1) / self_width;
+        let whole = self.content.slice_width(..);
+        let mut res: Option<U> = None;
+        for segment in first..=last {
+            let shift = segment * self_width;
+            let segment_start = start.saturating_sub(shift);
+            let segment_end = (end - shift).min(self_width);
+            let sliced = if segment_start == 0 && segment_end == self_width {
+                whole.as_ref().map(|whole| U::default().join(whole))
             } else {
-                return None;
-            }
-            segment += 1;
-            if segment > 10 {
-                return Some(res);
-            }
+                self.content
+                    .slice_width(segment_start..segment_end)
+                    .map(|sliced| U::default().join(&sliced))
+            };
+            res = match (res, sliced) {
+                (Some(res), Some(sliced)) => Some(res.join(&sliced)),
+                (res, sliced) => res.or(sliced),
+            };
         }
-
-        Some(res)
+        res
+    }
+    fn try_slice_width<R>(&self, range: R) -> Result<Self::Output, Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        if self.content.bounded_width() == 0 {
+            return Err(Error::ZeroWidthSymbol);
+        }
+        let (start, end) = bounds::normalize(&range);
+        let total = self.count.map(|count| count * self.content.bounded_width());
+        let end = match (end, total) {
+            (Some(end), _) if start > end => return Err(Error::InvertedRange { start, end }),
+            (_, Some(total)) if start > total => {
+                return Err(Error::OutOfBounds {
+                    index: start,
+                    len: total,
+                })
+            }
+            (Some(end), Some(total)) => end.min(total),
+            (Some(end), None) | (None, Some(end)) => end,
+            (None, None) => return Err(Error::UnboundedRange),
+        };
+        self.slice_width(range)
+            .filter(|sliced| sliced.bounded_width() > 0)
+            .ok_or(Error::WidthTooSmall { width: end - start })
     }
 }
 
@@ -161,6 +229,17 @@ mod test {
         assert_eq!(expected, actual);
     }
     #[test]
+    fn make_repeat_included_max() {
+        let span = Span::<Style>::new(
+            Cow::Owned(Color::Yellow.normal()),
+            Cow::Owned(String::from("0")),
+        );
+        let repeat = Repeat::new(span);
+        let actual = repeat.slice_width(3..=usize::MAX);
+        let expected = None;
+        assert_eq!(expected, actual);
+    }
+    #[test]
     fn make_repeat_trivial_single() {
         let span = Span::<Style>::new(
             Cow::Owned(Color::Yellow.normal()),
@@ -173,6 +252,16 @@ mod test {
         assert_eq!(expected, actual);
     }
     #[test]
+    fn make_repeat_borrowed() {
+        let span: Span<Style> = Color::Yellow.paint("01").into();
+        let mut spans: Spans<Style> = Default::default();
+        spans.push(&span);
+        let repeat = Repeat::new(&spans);
+        let actual = format!("{}", repeat.slice_width(1..4).unwrap());
+        let expected = format!("{}", Color::Yellow.paint("101"));
+        assert_eq!(expected, actual);
+    }
+    #[test]
     fn make_repeat_trivial_multiple() {
         let span = Span::<Style>::new(
             Cow::Owned(Color::Yellow.normal()),
@@ -282,4 +371,86 @@ mod test {
         let expected = format!("{}", Color::Yellow.paint("2340123401234012"));
         assert_eq!(expected, actual);
     }
+    #[test]
+    fn repeat_errors() {
+        let span = Span::<Style>::new(
+            Cow::Owned(Color::Yellow.normal()),
+            Cow::Owned(String::from("🙈")),
+        );
+        let repeat = Repeat::new(span);
+        assert_eq!(Err(Error::UnboundedRange), repeat.try_slice_width(2..));
+        assert_eq!(
+            Err(Error::WidthTooSmall { width: 1 }),
+            repeat.try_slice_width(1..2)
+        );
+        let empty = Span::<Style>::new(
+            Cow::Owned(Color::Yellow.normal()),
+            Cow::Owned(String::new()),
+        );
+        assert_eq!(
+            Err(Error::ZeroWidthSymbol),
+            Repeat::new(empty).try_slice_width(..4)
+        );
+    }
+    #[test]
+    fn repeat_times() {
+        let span = Span::<Style>::new(
+            Cow::Owned(Color::Yellow.normal()),
+            Cow::Owned(String::from("012")),
+        );
+        let repeat = Repeat::times(span, 2);
+        assert_eq!(Width::Bounded(6), repeat.width());
+        let actual = format!("{}", repeat.slice_width(1..).unwrap());
+        assert_eq!(format!("{}", Color::Yellow.paint("12012")), actual);
+        let actual = format!("{}", repeat.slice_width(4..100).unwrap());
+        assert_eq!(format!("{}", Color::Yellow.paint("12")), actual);
+        assert_eq!(None, repeat.slice_width(6..));
+        assert_eq!(
+            Err(Error::OutOfBounds { index: 7, len: 6 }),
+            repeat.try_slice_width(7..)
+        );
+        assert_eq!(
+            None,
+            Repeat::times(Span::<Style>::default(), 0).slice_width(..)
+        );
+    }
+    #[test]
+    fn repeat_matches_repeated_text() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut pattern: Spans<Tag> = Default::default();
+        pattern.push(&Span::borrowed(&fmt_1, "a🙈"));
+        pattern.push(&Span::borrowed(&fmt_2, "b"));
+        let repeat = Repeat::new(&pattern);
+        let mut repeated: Spans<Tag> = Default::default();
+        for _ in 0..40 {
+            repeated.push(&pattern);
+        }
+        for start in 0..30 {
+            for end in start..90 {
+                assert_eq!(
+                    repeated.slice_width(start..end),
+                    repeat.slice_width(start..end),
+                    "{}..{}",
+                    start,
+                    end
+                );
+            }
+        }
+        let wide = repeat.slice_width(3..3 + 4 * 100).unwrap();
+        assert_eq!(400, wide.bounded_width());
+    }
+    #[test]
+    fn between_implicitly_styled_text() {
+        let fill = Tag::new("<f>", "</f>");
+        let mut dots: Spans<Tag> = Default::default();
+        dots.push(&".");
+        let title = Spans::styled(fill.clone(), "Title");
+        let leader = Repeat::new(dots).between(
+            &title,
+            &Spans::from("1"),
+            &|_: &Tag, before: Option<&Tag>, _: Option<&Tag>| before.cloned().unwrap_or_default(),
+        );
+        assert_eq!(leader.slice_width(..2).unwrap(), Spans::styled(fill, ".."));
+    }
 }
@@ -0,0 +1,132 @@
+use super::pad::fill;
+use crate::text::{HasWidth, Pushable, Span, Spans, Width};
+use crate::widget::Fitable;
+
+/// A one-line scroll indicator: a thumb on a track, sized and placed in
+/// proportion to the part of some content shown in a viewport.
+/// ```text
+/// ───━━━━─────
+/// ```
+/// A scrollbar takes up whatever width it is given, so it can sit in an
+/// [`crate::widget::HBox`] beside the content it scrolls.
+#[derive(Clone, Debug)]
+pub struct Scrollbar<'a, T: Clone> {
+    track: Span<'a, T>,
+    thumb: Span<'a, T>,
+    length: usize,
+    viewport: usize,
+    offset: usize,
+}
+
+impl<'a, T: Clone> Scrollbar<'a, T> {
+    /// Creates a scrollbar drawn with repeated track and thumb fills, showing
+    /// all of some empty content.
+    pub fn new(track: Span<'a, T>, thumb: Span<'a, T>) -> Self {
+        Scrollbar {
+            track,
+            thumb,
+            length: 0,
+            viewport: 0,
+            offset: 0,
+        }
+    }
+    /// Sets the length of the content, the length of it that is visible, and
+    /// the offset of the visible part. The offset is clamped so the viewport
+    /// stays within the content.
+    pub fn set_position(&mut self, length: usize, viewport: usize, offset: usize) {
+        self.length = length;
+        self.viewport = viewport;
+        self.offset = offset.min(length.saturating_sub(viewport));
+    }
+    /// Returns the start and width of the thumb in a track of a given width.
+    fn thumb_range(&self, width: usize) -> (usize, usize) {
+        if self.viewport >= self.length {
+            return (0, width);
+        }
+        let thumb_width = divide_rounded(width * self.viewport, self.length).clamp(1, width);
+        let max_offset = self.length - self.viewport;
+        let start = divide_rounded((width - thumb_width) * self.offset, max_offset);
+        (start, thumb_width)
+    }
+}
+
+/// Divides, rounding halves up.
+fn divide_rounded(numerator: usize, denominator: usize) -> usize {
+    (numerator + denominator / 2) / denominator
+}
+
+impl<'a, T: Clone> HasWidth for Scrollbar<'a, T> {
+    fn width(&self) -> Width {
+        Width::Unbounded
+    }
+}
+
+impl<'a, T: Clone + Default + PartialEq> Fitable<Spans<T>> for Scrollbar<'a, T> {
+    /// Draws the scrollbar in exactly a given width.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Span, Tag};
+    /// use stylish_stringlike::widget::{Fitable, Scrollbar};
+    /// let plain = Tag::new("", "");
+    /// let mut scrollbar = Scrollbar::new(Span::borrowed(&plain, "─"), Span::borrowed(&plain, "━"));
+    /// scrollbar.set_position(100, 25, 50);
+    /// assert_eq!(scrollbar.truncate(12).unwrap().to_string(), "──────━━━───");
+    /// ```
+    fn truncate(&self, width: usize) -> Option<Spans<T>> {
+        if width == 0 {
+            return None;
+        }
+        let (start, thumb_width) = self.thumb_range(width);
+        let mut result = fill(&self.track, start);
+        result.push(&fill(&self.thumb, thumb_width));
+        result.push(&fill(&self.track, width - start - thumb_width));
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    fn make_scrollbar<'a>(track: &'a Tag, thumb: &'a Tag) -> Scrollbar<'a, Tag> {
+        Scrollbar::new(Span::borrowed(track, "-"), Span::borrowed(thumb, "#"))
+    }
+    #[test]
+    fn scrollbar_positions() {
+        let fmt_t = Tag::new("<t>", "</t>");
+        let fmt_h = Tag::new("<h>", "</h>");
+        let mut scrollbar = make_scrollbar(&fmt_t, &fmt_h);
+        scrollbar.set_position(20, 5, 0);
+        let actual = scrollbar.truncate(8).unwrap().to_string();
+        assert_eq!("<h>##</h><t>------</t>", actual);
+        scrollbar.set_position(20, 5, 15);
+        let actual = scrollbar.truncate(8).unwrap().to_string();
+        assert_eq!("<t>------</t><h>##</h>", actual);
+        scrollbar.set_position(20, 5, 100);
+        assert_eq!(scrollbar.offset, 15);
+        let actual = scrollbar.truncate(8).unwrap().to_string();
+        assert_eq!("<t>------</t><h>##</h>", actual);
+        scrollbar.set_position(20, 5, 7);
+        let actual = scrollbar.truncate(8).unwrap().to_string();
+        assert_eq!("<t>---</t><h>##</h><t>---</t>", actual);
+    }
+    #[test]
+    fn scrollbar_small_thumb() {
+        let fmt_t = Tag::new("<t>", "</t>");
+        let fmt_h = Tag::new("<h>", "</h>");
+        let mut scrollbar = make_scrollbar(&fmt_t, &fmt_h);
+        scrollbar.set_position(1000, 1, 500);
+        let actual = scrollbar.truncate(5).unwrap().to_string();
+        assert_eq!("<t>--</t><h>#</h><t>--</t>", actual);
+        assert_eq!(None, scrollbar.truncate(0));
+    }
+    #[test]
+    fn scrollbar_everything_visible() {
+        let fmt_t = Tag::new("<t>", "</t>");
+        let fmt_h = Tag::new("<h>", "</h>");
+        let mut scrollbar = make_scrollbar(&fmt_t, &fmt_h);
+        scrollbar.set_position(3, 10, 2);
+        assert_eq!("<h>####</h>", scrollbar.truncate(4).unwrap().to_string());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::text::{BoundedWidth, Format, HasWidth, Spans, Width, WidthSliceable};
+use crate::widget::Fitable;
+
+/// Which side of a status line segments are on, which decides which way
+/// their separators point.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Side {
+    /// Segments on the left, with separators pointing right and a cap after
+    /// the last segment.
+    #[default]
+    Left,
+    /// Segments on the right, with separators pointing left and a cap
+    /// before the first segment.
+    Right,
+}
+
+/// Computes the style of a separator from the styles of the segments to its
+/// left and right, which are [`None`] for caps at the ends.
+type SeparatorStyle<'a, T> = Box<dyn Fn(Option<&T>, Option<&T>) -> T + 'a>;
+
+/// Styled segments joined by separators whose styles are computed from the
+/// segments on either side of them, as in powerline status lines, where
+/// each arrow takes its color from the background of the segment it points
+/// out of and its background from the segment it points into.
+///
+/// When the segments do not fit, the segments pushed last are dropped first.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::Tag;
+/// use stylish_stringlike::widget::{Fitable, Segments, Side};
+/// let mut status = Segments::new(Side::Left, |_: Option<&Tag>, right: Option<&Tag>| match right {
+///     Some(_) => Tag::new("<s>", "</s>"),
+///     None => Tag::new("<cap>", "</cap>"),
+/// });
+/// status.set_separator(">");
+/// status.push(Tag::new("<a>", "</a>"), " main ");
+/// status.push(Tag::new("<b>", "</b>"), " 3 ");
+/// assert_eq!(
+///     status.render().to_string(),
+///     "<a> main </a><s>></s><b> 3 </b><cap>></cap>"
+/// );
+/// assert_eq!(status.truncate(8).unwrap().to_string(), "<a> main </a><cap>></cap>");
+/// ```
+pub struct Segments<'a, T: Clone> {
+    segments: Vec<(T, String)>,
+    side: Side,
+    separator: String,
+    caps: bool,
+    separator_style: SeparatorStyle<'a, T>,
+}
+
+impl<'a, T: Clone + Default + PartialEq> Segments<'a, T> {
+    /// Creates an empty set of segments for one side of a status line, with
+    /// powerline arrows as separators and caps at the open end.
+    pub fn new<F>(side: Side, separator_style: F) -> Self
+    where
+        F: Fn(Option<&T>, Option<&T>) -> T + 'a,
+    {
+        let separator = match side {
+            Side::Left => "\u{e0b0}",
+            Side::Right => "\u{e0b2}",
+        };
+        Segments {
+            segments: vec![],
+            side,
+            separator: separator.to_string(),
+            caps: true,
+            separator_style: Box::new(separator_style),
+        }
+    }
+    /// Adds a segment of text in a style.
+    pub fn push(&mut self, style: T, text: &str) {
+        self.segments.push((style, text.to_string()));
+    }
+    /// Sets the glyph drawn between segments and as the cap.
+    pub fn set_separator(&mut self, separator: &str) {
+        self.separator = separator.to_string();
+    }
+    /// Sets whether a separator is drawn at the open end of the segments.
+    pub fn set_caps(&mut self, caps: bool) {
+        self.caps = caps;
+    }
+    /// Joins the first `count` segments.
+    fn render_first(&self, count: usize) -> Spans<T> {
+        let segments = &self.segments[..count.min(self.segments.len())];
+        let mut result = Spans::default();
+        let separator = |left: Option<&T>, right: Option<&T>, result: &mut Spans<T>| {
+            result.push_styled((self.separator_style)(left, right), &self.separator);
+        };
+        for (i, (style, text)) in segments.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|i| &segments[i].0);
+            if self.side == Side::Right && (previous.is_some() || self.caps) {
+                separator(previous, Some(style), &mut result);
+            }
+            result.push_styled(style.clone(), text);
+            let next = segments.get(i + 1).map(|segment| &segment.0);
+            if self.side == Side::Left && (next.is_some() || self.caps) {
+                separator(Some(style), next, &mut result);
+            }
+        }
+        result
+    }
+    /// Joins all of the segments.
+    pub fn render(&self) -> Spans<T> {
+        self.render_first(self.segments.len())
+    }
+}
+
+impl<'a> Segments<'a, Format> {
+    /// Creates segments styled in the powerline way: each separator is
+    /// colored with the background of the segment it points out of, on the
+    /// background of the segment it points into.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Color, Format};
+    /// use stylish_stringlike::widget::{Segments, Side};
+    /// let mut status = Segments::powerline(Side::Left);
+    /// status.push(Format::new().fg(Color::Black).bg(Color::Blue), " ~ ");
+    /// status.push(Format::new().bg(Color::Green), " main ");
+    /// let line = status.render();
+    /// assert_eq!(line.style_at(3), Some(&Format::new().fg(Color::Blue).bg(Color::Green)));
+    /// assert_eq!(line.style_at(12), Some(&Format::new().fg(Color::Green)));
+    /// ```
+    pub fn powerline(side: Side) -> Self {
+        Segments::new(
+            side,
+            move |left: Option<&Format>, right: Option<&Format>| {
+                let (from, into) = match side {
+                    Side::Left => (left, right),
+                    Side::Right => (right, left),
+                };
+                Format {
+                    fg: from.and_then(|format| format.bg),
+                    bg: into.and_then(|format| format.bg),
+                    ..Format::default()
+                }
+            },
+        )
+    }
+}
+
+impl<'a, T: Clone + Default + PartialEq> HasWidth for Segments<'a, T> {
+    fn width(&self) -> Width {
+        Width::Bounded(self.render().bounded_width())
+    }
+}
+
+impl<'a, T: Clone + Default + PartialEq> Fitable<Spans<T>> for Segments<'a, T> {
+    /// Joins as many segments as fit, dropping the segments pushed last, or
+    /// cuts the first segment if not even it fits.
+    fn truncate(&self, width: usize) -> Option<Spans<T>> {
+        (1..=self.segments.len())
+            .rev()
+            .map(|count| self.render_first(count))
+            .find(|rendered| rendered.bounded_width() <= width)
+            .or_else(|| self.render_first(1).slice_width(..width))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::{Color, RawText};
+    #[test]
+    fn right_side_caps_first_segment() {
+        let blue = Format::new().bg(Color::Blue);
+        let red = Format::new().bg(Color::Red);
+        let mut status = Segments::powerline(Side::Right);
+        status.set_separator("<");
+        status.push(blue, "a");
+        status.push(red, "b");
+        let line = status.render();
+        assert_eq!(line.raw_ref(), "<a<b");
+        assert_eq!(line.style_at(0), Some(&Format::new().fg(Color::Blue)));
+        assert_eq!(
+            line.style_at(2),
+            Some(&Format::new().fg(Color::Red).bg(Color::Blue))
+        );
+        status.set_caps(false);
+        assert_eq!(status.truncate(2).unwrap().raw_ref(), "a");
+        assert_eq!(status.width(), Width::Bounded(3));
+    }
+}
@@ -0,0 +1,85 @@
+use super::pad::fill;
+use crate::text::{HasWidth, Span, Spans, Width};
+use crate::widget::Fitable;
+use std::borrow::Cow;
+
+/// A flexible gap that takes up whatever width it is given, for pushing
+/// apart the elements of an [`crate::widget::HBox`].
+#[derive(Clone, Debug)]
+pub struct Spacer<'a, T: Clone> {
+    fill: Span<'a, T>,
+}
+
+impl<'a, T: Clone + Default> Default for Spacer<'a, T> {
+    fn default() -> Self {
+        Spacer {
+            fill: Span::new(Cow::Owned(T::default()), Cow::Borrowed(" ")),
+        }
+    }
+}
+
+impl<'a, T: Clone + Default> Spacer<'a, T> {
+    /// Creates a spacer of spaces in the default style.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Creates a spacer of repeated fill. An empty fill falls back to spaces
+    /// in the default style.
+    pub fn with_fill(fill: Span<'a, T>) -> Self {
+        Spacer { fill }
+    }
+}
+
+impl<'a, T: Clone> HasWidth for Spacer<'a, T> {
+    fn width(&self) -> Width {
+        Width::Unbounded
+    }
+}
+
+impl<'a, T: Clone + Default + PartialEq> Fitable<Spans<T>> for Spacer<'a, T> {
+    fn truncate(&self, width: usize) -> Option<Spans<T>> {
+        if width == 0 {
+            None
+        } else {
+            Some(fill(&self.fill, width))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    use crate::widget::{HBox, TextWidget, TruncationStyle};
+    #[test]
+    fn spacer_between_widgets() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut left: Spans<Tag> = Default::default();
+        left.push(&Span::borrowed(&fmt_1, "foo"));
+        let mut right: Spans<Tag> = Default::default();
+        right.push(&Span::borrowed(&fmt_2, "bar"));
+        let truncation = TruncationStyle::Left("…");
+        let mut hbox = HBox::new();
+        hbox.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&left),
+            Cow::Borrowed(&truncation),
+        )));
+        hbox.push(Box::new(Spacer::new()));
+        hbox.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&right),
+            Cow::Borrowed(&truncation),
+        )));
+        assert_eq!("<1>foo</1>    <2>bar</2>", hbox.truncate(10).to_string());
+        assert_eq!("<1>foo</1><2>bar</2>", hbox.truncate(6).to_string());
+    }
+    #[test]
+    fn spacer_fill() {
+        let fmt_d = Tag::new("<d>", "</d>");
+        let spacer = Spacer::with_fill(Span::borrowed(&fmt_d, "-"));
+        assert_eq!("<d>---</d>", spacer.truncate(3).unwrap().to_string());
+        assert_eq!(None, spacer.truncate(0));
+        let empty = Spacer::with_fill(Span::borrowed(&fmt_d, ""));
+        assert_eq!("  ", empty.truncate(2).unwrap().to_string());
+    }
+}
@@ -0,0 +1,341 @@
+use super::hbox::allocate;
+use crate::text::{BoundedWidth, Pushable, Span, Spans, Width};
+use crate::widget::TruncationStrategy;
+use std::borrow::Cow;
+
+/// How the width of a [`Column`] is decided.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColumnWidth {
+    /// Occupies exactly this many columns when space allows. Minimum and
+    /// maximum widths are ignored.
+    Fixed(usize),
+    /// Occupies the width of its widest cell when space allows, sharing space
+    /// between columns the same way as [`crate::widget::HBox`].
+    Content,
+    /// Shares space left over by the other columns in proportion to its weight.
+    Weighted(usize),
+}
+
+/// A column of a [`Table`].
+pub struct Column<'a, U: Clone> {
+    width: ColumnWidth,
+    min: usize,
+    max: Option<usize>,
+    truncation_strategy: Cow<'a, U>,
+}
+
+impl<'a, U: Clone> Column<'a, U> {
+    pub fn new(width: ColumnWidth, truncation_strategy: Cow<'a, U>) -> Self {
+        Column {
+            width,
+            min: 0,
+            max: None,
+            truncation_strategy,
+        }
+    }
+    /// Sets a width this column is given before any other column is
+    /// considered, space permitting.
+    pub fn set_min(&mut self, min: usize) {
+        self.min = min;
+    }
+    /// Sets a width this column never grows past.
+    pub fn set_max(&mut self, max: usize) {
+        self.max = Some(max);
+    }
+}
+
+/// A table of styled text, truncating and padding each cell to the width of
+/// its column.
+pub struct Table<'a, T: Clone, U: Clone> {
+    columns: Vec<Column<'a, U>>,
+    rows: Vec<Vec<Cow<'a, Spans<T>>>>,
+    separator: Option<Cow<'a, Spans<T>>>,
+}
+
+impl<'a, T: Clone, U: Clone> Default for Table<'a, T, U> {
+    fn default() -> Self {
+        Table {
+            columns: vec![],
+            rows: vec![],
+            separator: None,
+        }
+    }
+}
+
+impl<'a, T: Clone, U: Clone> Table<'a, T, U> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Adds a column to the right of the table.
+    pub fn push_column(&mut self, column: Column<'a, U>) {
+        self.columns.push(column);
+    }
+    /// Adds a row to the bottom of the table. Missing cells are left blank and
+    /// extra cells are ignored.
+    pub fn push_row(&mut self, row: Vec<Cow<'a, Spans<T>>>) {
+        self.rows.push(row);
+    }
+    /// Sets text displayed between adjacent columns.
+    pub fn set_separator(&mut self, separator: Cow<'a, Spans<T>>) {
+        self.separator = Some(separator);
+    }
+    /// Truncates every row of the table to a given width.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{Column, ColumnWidth, Table, TruncationStyle};
+    /// let truncation: TruncationStyle<Option<Spans<Tag>>> = TruncationStyle::Left(None);
+    /// let mut table = Table::new();
+    /// table.push_column(Column::new(ColumnWidth::Content, Cow::Borrowed(&truncation)));
+    /// table.push_column(Column::new(ColumnWidth::Weighted(1), Cow::Borrowed(&truncation)));
+    /// table.set_separator(Cow::Owned(Spans::from("|")));
+    /// table.push_row(vec![
+    ///     Cow::Owned(Spans::from("name")),
+    ///     Cow::Owned(Spans::from("description")),
+    /// ]);
+    /// table.push_row(vec![
+    ///     Cow::Owned(Spans::from("foo")),
+    ///     Cow::Owned(Spans::from("a foo")),
+    /// ]);
+    /// let rows: Vec<String> = table.truncate(12).iter().map(|row| row.to_string()).collect();
+    /// assert_eq!(rows, vec!["name|descrip", "foo |a foo  "]);
+    /// ```
+    pub fn truncate(&self, width: usize) -> Vec<Spans<T>>
+    where
+        T: Default + PartialEq,
+        U: TruncationStrategy<Spans<T>>,
+    {
+        let widths = self.widths(width);
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut result: Spans<T> = Default::default();
+                for (index, (column, width)) in self.columns.iter().zip(&widths).enumerate() {
+                    match &self.separator {
+                        Some(separator) if index > 0 => result.push(separator.as_ref()),
+                        _ => {}
+                    }
+                    let cell = row
+                        .get(index)
+                        .and_then(|cell| column.truncation_strategy.truncate(cell, *width));
+                    let padding = width.saturating_sub(cell.bounded_width());
+                    result.push(&cell);
+                    if padding > 0 {
+                        result.push(&Span::new(
+                            Cow::Owned(T::default()),
+                            Cow::Owned(" ".repeat(padding)),
+                        ));
+                    }
+                }
+                result
+            })
+            .collect()
+    }
+    /// Splits the available width between the columns.
+    fn widths(&self, width: usize) -> Vec<usize> {
+        let separators = self
+            .separator
+            .as_ref()
+            .map_or(0, |separator| separator.bounded_width())
+            * self.columns.len().saturating_sub(1);
+        let mut space = width.saturating_sub(separators);
+        // Fixed widths and minimums are reserved first, from left to right.
+        let mut widths: Vec<usize> = self
+            .columns
+            .iter()
+            .map(|column| {
+                let reserved = match column.width {
+                    ColumnWidth::Fixed(w) => w,
+                    _ => column.min,
+                }
+                .min(space);
+                space -= reserved;
+                reserved
+            })
+            .collect();
+        let measured: Vec<Width> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| match column.width {
+                ColumnWidth::Fixed(_) => Width::Bounded(0),
+                ColumnWidth::Content => {
+                    let natural = self
+                        .rows
+                        .iter()
+                        .filter_map(|row| row.get(index))
+                        .map(|cell| cell.bounded_width())
+                        .max()
+                        .unwrap_or(0);
+                    let natural = column.max.map_or(natural, |max| natural.min(max));
+                    Width::Bounded(natural.saturating_sub(widths[index]))
+                }
+                ColumnWidth::Weighted(_) => Width::Unbounded,
+            })
+            .collect();
+        let mut shares = allocate(&measured, space);
+        // Unbounded elements share space evenly, so pool the weighted shares
+        // and divide them again by weight.
+        let mut pool: usize = self
+            .columns
+            .iter()
+            .zip(&mut shares)
+            .filter(|(column, _)| matches!(column.width, ColumnWidth::Weighted(_)))
+            .map(|(_, share)| std::mem::take(share))
+            .sum();
+        let mut open: Vec<(usize, usize)> = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(index, column)| match column.width {
+                ColumnWidth::Weighted(weight) if weight > 0 => Some((index, weight)),
+                _ => None,
+            })
+            .collect();
+        while pool > 0 && !open.is_empty() {
+            let total: usize = open.iter().map(|(_, weight)| weight).sum();
+            let mut given: Vec<usize> = open.iter().map(|(_, w)| pool * w / total).collect();
+            let rem = pool - given.iter().sum::<usize>();
+            for share in given.iter_mut().take(rem) {
+                *share += 1;
+            }
+            let mut capped = vec![];
+            for (rel_index, ((index, _), share)) in open.iter().zip(given).enumerate() {
+                let room = self.columns[*index].max.map_or(usize::MAX, |max| {
+                    max.saturating_sub(widths[*index] + shares[*index])
+                });
+                let share = share.min(room);
+                shares[*index] += share;
+                pool -= share;
+                if share == room {
+                    capped.push(rel_index);
+                }
+            }
+            for rel_index in capped.iter().rev() {
+                open.remove(*rel_index);
+            }
+            if capped.is_empty() {
+                break;
+            }
+        }
+        for (width, share) in widths.iter_mut().zip(shares) {
+            *width += share;
+        }
+        widths
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    use crate::widget::fixtures::make_spans;
+    use crate::widget::TruncationStyle;
+    fn render<U>(table: &Table<Tag, U>, width: usize) -> Vec<String>
+    where
+        U: Clone + TruncationStrategy<Spans<Tag>>,
+    {
+        table
+            .truncate(width)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+    #[test]
+    fn content_columns() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let truncation = TruncationStyle::Left(make_spans(&fmt_2, "…"));
+        let mut table = Table::new();
+        table.push_column(Column::new(
+            ColumnWidth::Content,
+            Cow::Borrowed(&truncation),
+        ));
+        table.push_column(Column::new(
+            ColumnWidth::Content,
+            Cow::Borrowed(&truncation),
+        ));
+        table.push_row(vec![
+            Cow::Owned(make_spans(&fmt_1, "ab")),
+            Cow::Owned(make_spans(&fmt_1, "0123456789")),
+        ]);
+        table.push_row(vec![Cow::Owned(make_spans(&fmt_1, "abcd"))]);
+        let expected = vec![
+            String::from("<1>ab</1>  <1>01234</1><2>…</2>"),
+            String::from("<1>abcd</1>      "),
+        ];
+        assert_eq!(expected, render(&table, 10));
+    }
+    #[test]
+    fn fixed_and_weighted_columns() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let sep = Tag::new("<s>", "</s>");
+        let truncation: TruncationStyle<Option<Spans<Tag>>> = TruncationStyle::Left(None);
+        let mut table = Table::new();
+        table.push_column(Column::new(
+            ColumnWidth::Fixed(2),
+            Cow::Borrowed(&truncation),
+        ));
+        table.push_column(Column::new(
+            ColumnWidth::Weighted(1),
+            Cow::Borrowed(&truncation),
+        ));
+        table.push_column(Column::new(
+            ColumnWidth::Weighted(2),
+            Cow::Borrowed(&truncation),
+        ));
+        table.set_separator(Cow::Owned(make_spans(&sep, "|")));
+        table.push_row(vec![
+            Cow::Owned(make_spans(&fmt_1, "abc")),
+            Cow::Owned(make_spans(&fmt_1, "0123456789")),
+            Cow::Owned(make_spans(&fmt_1, "0123456789")),
+        ]);
+        let expected = vec![String::from(
+            "<1>ab</1><s>|</s><1>012</1><s>|</s><1>012345</1>",
+        )];
+        assert_eq!(expected, render(&table, 13));
+    }
+    #[test]
+    fn min_and_max() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let truncation: TruncationStyle<Option<Spans<Tag>>> = TruncationStyle::Left(None);
+        let mut table = Table::new();
+        let mut first = Column::new(ColumnWidth::Content, Cow::Borrowed(&truncation));
+        first.set_min(3);
+        let mut second = Column::new(ColumnWidth::Weighted(1), Cow::Borrowed(&truncation));
+        second.set_max(2);
+        let third = Column::new(ColumnWidth::Weighted(1), Cow::Borrowed(&truncation));
+        table.push_column(first);
+        table.push_column(second);
+        table.push_column(third);
+        table.push_row(vec![
+            Cow::Owned(make_spans(&fmt_1, "a")),
+            Cow::Owned(make_spans(&fmt_1, "0123456789")),
+            Cow::Owned(make_spans(&fmt_1, "0123456789")),
+        ]);
+        let expected = vec![String::from("<1>a</1>  <1>0101234</1>")];
+        assert_eq!(expected, render(&table, 10));
+    }
+    #[test]
+    fn too_narrow() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let truncation: TruncationStyle<Option<Spans<Tag>>> = TruncationStyle::Left(None);
+        let mut table = Table::new();
+        table.push_column(Column::new(
+            ColumnWidth::Fixed(4),
+            Cow::Borrowed(&truncation),
+        ));
+        table.push_column(Column::new(
+            ColumnWidth::Content,
+            Cow::Borrowed(&truncation),
+        ));
+        table.push_row(vec![
+            Cow::Owned(make_spans(&fmt_1, "abcdef")),
+            Cow::Owned(make_spans(&fmt_1, "0123")),
+        ]);
+        let expected = vec![String::from("<1>abc</1>")];
+        assert_eq!(expected, render(&table, 3));
+    }
+}
@@ -1,18 +1,59 @@
-use crate::text::{HasWidth, Width};
+use crate::text::{Direction, HasWidth, Pushable, Width};
 use crate::widget::{Truncateable, TruncationStrategy};
 use std::borrow::Cow;
 use std::ops::Deref;
 
+/// The widths a widget wants to occupy, as found by [`Fitable::measure`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Measure {
+    /// The width the widget should not be shrunk below while there is room
+    /// for the minimum widths of all the widgets it is laid out with.
+    pub min: usize,
+    /// The width the widget wants to occupy.
+    pub preferred: Width,
+}
+
 /// Widgets that can be truncated to fit in a provided width.
 pub trait Fitable<T: Truncateable>: HasWidth {
     /// Truncate self to fit in a given width.
     fn truncate(&self, width: usize) -> Option<T>;
+    /// Measures the widths this widget wants, given a hint of the width that
+    /// is available. Layouts measure every widget before dividing width
+    /// between them. Defaults to no minimum width and the widget's
+    /// [`HasWidth::width`] as its preferred width.
+    fn measure(&self, _width_hint: usize) -> Measure {
+        Measure {
+            min: 0,
+            preferred: self.width(),
+        }
+    }
+    /// Truncate self to fit in a given width, pushing the output onto
+    /// existing text. Returns whether there was any output.
+    fn truncate_into(&self, width: usize, output: &mut T) -> bool
+    where
+        T: Pushable<T>,
+    {
+        match self.truncate(width) {
+            Some(truncated) => {
+                output.push(&truncated);
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 /// A widget that can be truncated
+///
+/// The text is treated as a single line, and a [`crate::widget::HBox`]
+/// shows only the first line of text with line breaks. Such text should be
+/// split with [`crate::text::Spans::lines`] first, or added to a
+/// [`crate::widget::VBox`] with [`crate::widget::VBox::push_lines`].
 pub struct TextWidget<'a, T: Clone, U: Clone> {
     text: Cow<'a, T>,
     truncation_strategy: Cow<'a, U>,
+    direction: Direction,
+    min_width: usize,
 }
 
 impl<'a, T: Clone, U: Clone> TextWidget<'a, T, U> {
@@ -20,8 +61,55 @@ impl<'a, T: Clone, U: Clone> TextWidget<'a, T, U> {
         TextWidget {
             text,
             truncation_strategy,
+            direction: Direction::Ltr,
+            min_width: 0,
         }
     }
+    /// Sets the direction the text is read in, so that truncation keeps the
+    /// right end of right-to-left text. Defaults to [`Direction::Ltr`]. With
+    /// the `bidi` feature, the direction of [`crate::text::Spans`] can be
+    /// found with `Spans::direction`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Direction, Spans, Tag};
+    /// use stylish_stringlike::widget::{Fitable, TextWidget, TruncationStyle};
+    /// let text: Spans<Tag> = Spans::from("שלום עולם");
+    /// let truncation = TruncationStyle::Right("…");
+    /// let mut widget = TextWidget::new(Cow::Borrowed(&text), Cow::Borrowed(&truncation));
+    /// widget.set_direction(Direction::Rtl);
+    /// assert_eq!(widget.truncate(5).unwrap().to_string(), "שלום…");
+    /// ```
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+    /// Sets the width the text should not be truncated below while there is
+    /// room for the minimum widths of the other widgets in a layout. Defaults
+    /// to zero.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle};
+    /// let texts: Vec<Spans<Tag>> = vec![Spans::from("/usr/local/bin"), Spans::from(" error: not found")];
+    /// let truncation = TruncationStyle::Left("…");
+    /// let layout = |min_width| {
+    ///     let mut path = TextWidget::new(Cow::Borrowed(&texts[0]), Cow::Borrowed(&truncation));
+    ///     path.set_min_width(min_width);
+    ///     let message = TextWidget::new(Cow::Borrowed(&texts[1]), Cow::Borrowed(&truncation));
+    ///     let mut hbox = HBox::new();
+    ///     hbox.push(Box::new(path));
+    ///     hbox.push(Box::new(message));
+    ///     hbox.truncate(20).to_string()
+    /// };
+    /// assert_eq!(layout(0), "/usr/loca… error: n…");
+    /// assert_eq!(layout(12), "/usr/local/bin erro…");
+    /// ```
+    pub fn set_min_width(&mut self, min_width: usize) {
+        self.min_width = min_width;
+    }
 }
 
 impl<'a, T: Clone, U: Clone> Fitable<T::Output> for TextWidget<'a, T, U>
@@ -31,7 +119,23 @@ where
     T::Output: Truncateable,
 {
     fn truncate(&self, width: usize) -> Option<T::Output> {
-        self.truncation_strategy.truncate(self.text.deref(), width)
+        self.truncation_strategy
+            .truncate_in(self.text.deref(), width, self.direction)
+    }
+    fn measure(&self, _width_hint: usize) -> Measure {
+        let preferred = self.text.width();
+        let min = match preferred {
+            Width::Bounded(w) => self.min_width.min(w),
+            Width::Unbounded => self.min_width,
+        };
+        Measure { min, preferred }
+    }
+    fn truncate_into(&self, width: usize, output: &mut T::Output) -> bool
+    where
+        T::Output: Pushable<T::Output>,
+    {
+        self.truncation_strategy
+            .truncate_into(self.text.deref(), width, self.direction, output)
     }
 }
 
@@ -1,9 +1,17 @@
-use crate::text::{BoundedWidth, HasWidth, Pushable, Width, WidthSliceable};
+use crate::text::{
+    BoundedWidth, Direction, HasWidth, Pushable, RawText, Spans, Splitable, Width, WidthPolicy,
+    WidthSliceable,
+};
+use crate::widget::{HBox, TextWidget};
+use crate::Error;
+use std::borrow::Cow;
+use std::ops::{Bound, RangeBounds};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Objects that have width and are sliceable on width are truncateable.
 pub trait Truncateable: HasWidth + WidthSliceable {}
 
-impl<'a, T> Truncateable for T where T: WidthSliceable + HasWidth {}
+impl<T> Truncateable for T where T: WidthSliceable + HasWidth {}
 
 /// Functionality for truncating objects using some strategy.
 pub trait TruncationStrategy<T>
@@ -12,6 +20,78 @@ where
 {
     /// Truncates target to width. Output should have a width equal to width.
     fn truncate(&self, target: &T, width: usize) -> Option<T::Output>;
+    /// Truncates target to width, returning why it could not be truncated if
+    /// [`TruncationStrategy::truncate`] would return [`None`].
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::Error;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{TruncationStrategy, TruncationStyle};
+    /// let text: Spans<Tag> = Spans::from("foobar");
+    /// let truncation = TruncationStyle::Left("…");
+    /// assert_eq!(truncation.try_truncate(&text, 4).unwrap().to_string(), "foo…");
+    /// assert_eq!(truncation.try_truncate(&text, 0), Err(Error::WidthTooSmall { width: 0 }));
+    /// ```
+    fn try_truncate(&self, target: &T, width: usize) -> Result<T::Output, Error> {
+        self.truncate(target, width)
+            .ok_or(Error::WidthTooSmall { width })
+    }
+    /// Truncates target to width, given the direction the target is read in.
+    /// Strategies that keep a visual end of the text, such as
+    /// [`TruncationStyle::Left`], keep the other end of the text in reading
+    /// order when it is read right to left.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Direction, Spans, Tag};
+    /// use stylish_stringlike::widget::{TruncationStrategy, TruncationStyle};
+    /// let text: Spans<Tag> = Spans::from("שלום עולם");
+    /// let truncation = TruncationStyle::Left("…");
+    /// assert_eq!(truncation.truncate(&text, 5).unwrap().to_string(), "שלום…");
+    /// let actual = truncation.truncate_in(&text, 5, Direction::Rtl).unwrap();
+    /// assert_eq!(actual.to_string(), "…עולם");
+    /// let end = TruncationStyle::End("…");
+    /// assert_eq!(end.truncate_in(&text, 5, Direction::Rtl).unwrap().to_string(), "…עולם");
+    /// ```
+    fn truncate_in(&self, target: &T, width: usize, direction: Direction) -> Option<T::Output> {
+        let _ = direction;
+        self.truncate(target, width)
+    }
+    /// Truncates target to width, pushing the output onto existing text
+    /// rather than returning it. Returns whether there was any output.
+    /// [`TruncationStyle`] copies the columns it keeps straight from the
+    /// target with [`WidthSliceable::push_width`] rather than slicing them
+    /// out and joining the slices into a result of its own.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Direction, Spans, Tag};
+    /// use stylish_stringlike::widget::{TruncationStrategy, TruncationStyle};
+    /// let text: Spans<Tag> = Spans::from("foobar");
+    /// let mut line: Spans<Tag> = Spans::from("> ");
+    /// let truncation = TruncationStyle::Left("…");
+    /// assert!(truncation.truncate_into(&text, 4, Direction::Ltr, &mut line));
+    /// assert_eq!(line.to_string(), "> foo…");
+    /// ```
+    fn truncate_into(
+        &self,
+        target: &T,
+        width: usize,
+        direction: Direction,
+        output: &mut T::Output,
+    ) -> bool
+    where
+        T::Output: Pushable<T::Output>,
+    {
+        match self.truncate_in(target, width, direction) {
+            Some(truncated) => {
+                output.push(&truncated);
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 /// Styles for simple truncation.
@@ -23,86 +103,434 @@ pub enum TruncationStyle<T: BoundedWidth> {
     /// Keeps the right text, truncates text on the left. Optional symbol added when truncation occurs.
     #[allow(dead_code)]
     Right(T),
+    /// Keeps the left text, truncating text on the right after the last whole
+    /// word that fits. A word too wide to fit on its own is cut. Optional
+    /// symbol added when truncation occurs.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{TruncationStrategy, TruncationStyle};
+    /// let text: Spans<Tag> = Spans::from("the quick brown fox");
+    /// let truncation = TruncationStyle::WordLeft("…");
+    /// assert_eq!(truncation.truncate(&text, 13).unwrap().to_string(), "the quick…");
+    /// assert_eq!(truncation.truncate(&text, 3).unwrap().to_string(), "th…");
+    /// let truncation = TruncationStyle::WordRight("…");
+    /// assert_eq!(truncation.truncate(&text, 13).unwrap().to_string(), "…brown fox");
+    /// ```
+    WordLeft(T),
+    /// Keeps the right text, truncating text on the left before the first
+    /// whole word that fits. A word too wide to fit on its own is cut.
+    /// Optional symbol added when truncation occurs.
+    WordRight(T),
+    /// Keeps the start of the text in reading order, truncates text at its
+    /// end. This is [`TruncationStyle::Left`] for left-to-right text and
+    /// [`TruncationStyle::Right`] for right-to-left text.
+    Start(T),
+    /// Keeps the end of the text in reading order, truncates text at its
+    /// start.
+    End(T),
     /// Keeps the outside text, truncates text on the inside. Optional symbol added when truncation occurs.
     #[allow(dead_code)]
     Inner(T),
+    /// Keeps the inside text, truncates text on both ends. Optional symbol added on both ends when truncation occurs.
+    Outer(T),
+    /// Keeps the outside text, truncating text at a position given as a fraction
+    /// of the kept width. A position of `0.2` keeps 20% of the text on the left
+    /// and 80% on the right. Optional symbol added when truncation occurs.
+    Position(T, f32),
 }
 
-impl<T, S> TruncationStrategy<T> for TruncationStyle<S>
-where
-    T: Truncateable,
-    S: BoundedWidth + WidthSliceable,
-    T::Output: Pushable<T::Output> + Pushable<S::Output> + Default + WidthSliceable,
-{
-    fn truncate(&self, target: &T, width: usize) -> Option<T::Output> {
+/// A range of columns, as taken by [`WidthSliceable::slice_width`].
+type Columns = (Bound<usize>, Bound<usize>);
+
+/// Converts a range of columns to [`Columns`].
+fn columns<R: RangeBounds<usize>>(range: R) -> Columns {
+    (range.start_bound().cloned(), range.end_bound().cloned())
+}
+
+/// A piece of truncated output: the columns of the target to keep with the
+/// width they were meant to have, or the truncation symbol. Columns kept from
+/// the end of the target are tails, and any gap in them is filled before
+/// rather than after.
+enum Piece {
+    Head(Columns, usize),
+    Tail(Columns, usize),
+    Symbol,
+}
+
+impl<S: BoundedWidth + WidthSliceable> TruncationStyle<S> {
+    /// Pads any columns left empty by slicing through a wide character with a
+    /// filler, so that truncated output is exactly the requested width.
+    ///
+    /// # Example
+    /// ```
+    /// use stylish_stringlike::text::{BoundedWidth, Spans, Tag};
+    /// use stylish_stringlike::widget::{TruncationStrategy, TruncationStyle};
+    /// let text: Spans<Tag> = Spans::from("🙈🙉🙊");
+    /// let truncation = TruncationStyle::Left("…");
+    /// assert_eq!(truncation.truncate(&text, 4).unwrap().bounded_width(), 3);
+    /// let exact = truncation.exact_width(" ");
+    /// let actual = exact.truncate(&text, 4).unwrap();
+    /// assert_eq!(actual.to_string(), "🙈 …");
+    /// ```
+    pub fn exact_width<F: BoundedWidth + WidthSliceable>(self, filler: F) -> ExactWidth<S, F> {
+        ExactWidth {
+            style: self,
+            filler,
+        }
+    }
+    /// Returns the truncation symbol.
+    fn symbol(&self) -> &S {
+        use TruncationStyle::*;
+        match self {
+            Left(sym) | Right(sym) | WordLeft(sym) | WordRight(sym) => sym,
+            Start(sym) | End(sym) | Inner(sym) | Outer(sym) | Position(sym, _) => sym,
+        }
+    }
+    fn pieces<T>(&self, target: &T, width: usize, direction: Direction) -> Option<Vec<Piece>>
+    where
+        T: Truncateable,
+        T::Output: RawText,
+    {
         if width == 0 {
             return None;
         }
+        use Piece::*;
         use TruncationStyle::*;
-        let mut result: T::Output = Default::default();
+        // Left and right are the start and end of the text only when it is
+        // read left to right.
+        let rtl = direction == Direction::Rtl;
+        let keep_start = match self {
+            Start(_) => Some(true),
+            End(_) => Some(false),
+            Left(_) | WordLeft(_) => Some(!rtl),
+            Right(_) | WordRight(_) => Some(rtl),
+            Inner(_) | Outer(_) | Position(..) => None,
+        };
+        let position = match self {
+            Position(_, position) if rtl => 1.0 - position,
+            Position(_, position) => *position,
+            _ => 0.5,
+        };
+        let words = matches!(self, WordLeft(_) | WordRight(_));
+        let sym = self.symbol();
         if let Width::Bounded(w) = target.width() {
             if width >= w {
-                result.push(&target.slice_width(..));
-                return Some(result);
+                return Some(vec![Head(columns(..), w)]);
             }
-            match self {
-                Left(ref sym) => {
-                    result.push(&target.slice_width(..width.saturating_sub(sym.bounded_width())));
-                    result.push(&sym.slice_width(..));
+            let pieces = match (keep_start, self) {
+                (Some(true), _) => {
+                    let target_width = width.saturating_sub(sym.bounded_width());
+                    let kept = match words {
+                        true => word_head_width(target, target_width),
+                        false => target_width,
+                    };
+                    vec![Head(columns(..kept), target_width), Symbol]
                 }
-                Right(ref sym) => {
-                    result.push(&sym.slice_width(..));
-                    result.push(&target.slice_width(
-                        w.saturating_sub(width.saturating_sub(sym.bounded_width()))..,
-                    ));
+                (Some(false), _) => {
+                    let target_width = width.saturating_sub(sym.bounded_width());
+                    let kept = match words {
+                        true => word_tail_width(target, target_width, w),
+                        false => target_width,
+                    };
+                    vec![
+                        Symbol,
+                        Tail(columns(w.saturating_sub(kept)..), target_width),
+                    ]
                 }
-                Inner(ref sym) => {
-                    let inner_width = sym.bounded_width();
-                    let target_width = width.saturating_sub(inner_width);
-                    let left_width = target_width / 2 + target_width % 2;
-                    let right_width = target_width / 2;
-                    let left_slice = target.slice_width(..left_width);
-                    let right_slice = target.slice_width(w.saturating_sub(right_width)..);
-                    result.push(&left_slice);
-                    result.push(&sym.slice_width(..));
-                    result.push(&right_slice);
+                (None, Outer(_)) => {
+                    let target_width = width.saturating_sub(2 * sym.bounded_width());
+                    let start = (w - target_width) / 2;
+                    vec![
+                        Symbol,
+                        Head(columns(start..start + target_width), target_width),
+                        Symbol,
+                    ]
                 }
-            }
+                (None, _) => {
+                    let target_width = width.saturating_sub(sym.bounded_width());
+                    let left_width = split_width(target_width, position);
+                    let right_width = target_width - left_width;
+                    let left_columns = columns(..left_width);
+                    let right_columns = columns(w.saturating_sub(right_width)..);
+                    vec![
+                        Head(left_columns, left_width),
+                        Symbol,
+                        Tail(right_columns, right_width),
+                    ]
+                }
+            };
+            Some(pieces)
         } else {
-            match self {
-                Left(ref symbol) => {
-                    result
-                        .push(&target.slice_width(..width.saturating_sub(symbol.bounded_width())));
-                    result.push(&symbol.slice_width(..));
+            let pieces = match (keep_start, self) {
+                (Some(true), _) => {
+                    let target_width = width.saturating_sub(sym.bounded_width());
+                    vec![Head(columns(..target_width), target_width), Symbol]
+                }
+                (Some(false), _) => {
+                    let target_width = width.saturating_sub(sym.bounded_width());
+                    vec![Symbol, Tail(columns(..target_width), target_width)]
                 }
-                Right(ref symbol) => {
-                    result.push(&symbol.slice_width(..));
-                    result
-                        .push(&target.slice_width(..width.saturating_sub(symbol.bounded_width())));
+                (None, Outer(_)) => {
+                    let target_width = width.saturating_sub(2 * sym.bounded_width());
+                    vec![Symbol, Head(columns(..target_width), target_width), Symbol]
                 }
-                Inner(s) => {
-                    let inner_width = s.bounded_width();
-                    let target_width = width.saturating_sub(inner_width);
-                    let left_width = target_width / 2 + target_width % 2;
-                    let right_width = target_width / 2;
-                    let left_slice = target.slice_width(..left_width);
-                    let right_slice = target.slice_width(..right_width);
-                    result.push(&left_slice);
-                    result.push(&s.slice_width(..));
-                    result.push(&right_slice);
+                (None, _) => {
+                    let target_width = width.saturating_sub(sym.bounded_width());
+                    let left_width = split_width(target_width, position);
+                    let right_width = target_width - left_width;
+                    vec![
+                        Head(columns(..left_width), left_width),
+                        Symbol,
+                        Tail(columns(..right_width), right_width),
+                    ]
                 }
+            };
+            Some(pieces)
+        }
+    }
+}
+
+impl<T, S> TruncationStrategy<T> for TruncationStyle<S>
+where
+    T: Truncateable,
+    S: BoundedWidth + WidthSliceable,
+    T::Output: Pushable<T::Output> + Pushable<S::Output> + BoundedWidth + Default + RawText,
+{
+    fn truncate(&self, target: &T, width: usize) -> Option<T::Output> {
+        self.truncate_in(target, width, Direction::Ltr)
+    }
+    fn truncate_in(&self, target: &T, width: usize, direction: Direction) -> Option<T::Output> {
+        let mut result: T::Output = Default::default();
+        self.truncate_into(target, width, direction, &mut result)
+            .then_some(result)
+    }
+    fn truncate_into(
+        &self,
+        target: &T,
+        width: usize,
+        direction: Direction,
+        output: &mut T::Output,
+    ) -> bool {
+        let pieces = match self.pieces(target, width, direction) {
+            Some(pieces) => pieces,
+            None => return false,
+        };
+        for piece in pieces {
+            match piece {
+                Piece::Head(range, _) | Piece::Tail(range, _) => {
+                    target.push_width(range, output);
+                }
+                Piece::Symbol => output.push(&self.symbol().slice_width(..)),
             }
-            return Some(result);
         }
+        true
+    }
+}
+
+/// A [`TruncationStyle`] that fills columns left empty by wide characters, made
+/// with [`TruncationStyle::exact_width`].
+///
+/// The filler is repeated as many whole times as fit in each gap, so a
+/// single-column filler such as a space always fills gaps exactly.
+#[derive(Debug, Clone)]
+pub struct ExactWidth<S: BoundedWidth, F> {
+    style: TruncationStyle<S>,
+    filler: F,
+}
 
-        Some(result)
+impl<S: BoundedWidth, F: BoundedWidth + WidthSliceable> ExactWidth<S, F> {
+    /// Fills the gap between the width of a slice and the width it was meant
+    /// to have.
+    fn fill<T: Pushable<F::Output>>(&self, result: &mut T, expected: usize, width: usize) {
+        fill_gap(result, expected.saturating_sub(width), &self.filler);
     }
 }
 
+/// Pushes as many whole copies of a filler as fit in a gap of columns.
+pub(crate) fn fill_gap<T, F>(result: &mut T, gap: usize, filler: &F)
+where
+    T: Pushable<F::Output>,
+    F: BoundedWidth + WidthSliceable,
+{
+    let count = gap.checked_div(filler.bounded_width()).unwrap_or_default();
+    if let (Some(filler), true) = (filler.slice_width(..), count > 0) {
+        for _ in 0..count {
+            result.push(&filler);
+        }
+    }
+}
+
+impl<T, S, F> TruncationStrategy<T> for ExactWidth<S, F>
+where
+    T: Truncateable,
+    S: BoundedWidth + WidthSliceable,
+    F: BoundedWidth + WidthSliceable,
+    T::Output: Pushable<T::Output>
+        + Pushable<S::Output>
+        + Pushable<F::Output>
+        + BoundedWidth
+        + Default
+        + RawText,
+{
+    fn truncate(&self, target: &T, width: usize) -> Option<T::Output> {
+        self.truncate_in(target, width, Direction::Ltr)
+    }
+    fn truncate_in(&self, target: &T, width: usize, direction: Direction) -> Option<T::Output> {
+        let mut result: T::Output = Default::default();
+        self.truncate_into(target, width, direction, &mut result)
+            .then_some(result)
+    }
+    fn truncate_into(
+        &self,
+        target: &T,
+        width: usize,
+        direction: Direction,
+        output: &mut T::Output,
+    ) -> bool {
+        let pieces = match self.style.pieces(target, width, direction) {
+            Some(pieces) => pieces,
+            None => return false,
+        };
+        for piece in pieces {
+            match piece {
+                Piece::Head(range, expected) => {
+                    let width = target.push_width(range, output).unwrap_or_default();
+                    self.fill(output, expected, width);
+                }
+                Piece::Tail(range, expected) => {
+                    self.fill(output, expected, target.width_within(range));
+                    target.push_width(range, output);
+                }
+                Piece::Symbol => output.push(&self.style.symbol().slice_width(..)),
+            }
+        }
+        true
+    }
+}
+
+/// Truncates a string to a width with a truncation strategy, for plain text
+/// that is not displayed in widgets. Returns `None` if the strategy cannot
+/// truncate to the width, as for a width of zero.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::widget::{truncate_str, TruncationStyle};
+/// let ellipsis = TruncationStyle::Left("…");
+/// assert_eq!(truncate_str("Hello, world!", 6, &ellipsis).unwrap(), "Hello…");
+/// assert_eq!(truncate_str("🙈🙉🙊", 4, &ellipsis).unwrap(), "🙈…");
+/// let exact = TruncationStyle::Inner("…").exact_width(" ");
+/// assert_eq!(truncate_str("🙈🙉🙊", 4, &exact).unwrap(), "🙈… ");
+/// assert_eq!(truncate_str("short", 10, &ellipsis).unwrap(), "short");
+/// ```
+pub fn truncate_str<S>(s: &str, width: usize, strategy: &S) -> Option<String>
+where
+    S: TruncationStrategy<String>,
+{
+    strategy.truncate(&String::from(s), width)
+}
+
+/// Shortens delimited text, such as a path, to a width by truncating each
+/// segment between delimiters with a truncation strategy. The width is
+/// shared between the segments as a [`HBox`] would share it, so short
+/// segments are kept whole. Delimiters are not truncated while there is room
+/// for all of them.
+///
+/// # Example
+/// ```
+/// use stylish_stringlike::text::{Spans, Tag};
+/// use stylish_stringlike::widget::{shorten_delimited, TruncationStyle};
+/// let path: Spans<Tag> = Spans::from("::SomeExtremelyLong::RandomAndPoorlyNamed::Path::");
+/// let truncation = TruncationStyle::Inner("…");
+/// let short = shorten_delimited(&path, "::", 20, &truncation);
+/// assert_eq!(short.to_string(), "::So…g::Ra…d::Path::");
+/// ```
+pub fn shorten_delimited<T, S>(
+    spans: &Spans<T>,
+    delimiter: &str,
+    width: usize,
+    truncation: &S,
+) -> Spans<T>
+where
+    T: Clone + Default + PartialEq,
+    S: TruncationStrategy<Spans<T>> + Clone,
+{
+    let mut hbox = HBox::new();
+    for split in spans.split(delimiter) {
+        if let Some(segment) = split.segment {
+            let widget = TextWidget::new(Cow::Owned(segment), Cow::Borrowed(truncation));
+            hbox.push(Box::new(widget));
+        }
+        if let Some(delim) = split.delim {
+            let delim_width = delim.bounded_width();
+            let mut widget = TextWidget::new(Cow::Owned(delim), Cow::Borrowed(truncation));
+            widget.set_min_width(delim_width);
+            hbox.push(Box::new(widget));
+        }
+    }
+    hbox.truncate(width)
+}
+
+/// Finds the width of the whole words at the start of a target that fit in a
+/// width, or the width itself if not even the first word fits. Whitespace
+/// after the last word is not kept.
+fn word_head_width<T>(target: &T, width: usize) -> usize
+where
+    T: WidthSliceable,
+    T::Output: RawText,
+{
+    let (text, head) = match (target.slice_width(..), target.slice_width(..width)) {
+        (Some(text), Some(head)) => (text, head),
+        _ => return width,
+    };
+    let (text, limit) = (text.raw_ref(), head.raw_ref().len());
+    text.split_word_bound_indices()
+        .take_while(|(start, word)| start + word.len() <= limit)
+        .filter(|(_, word)| !word.trim().is_empty())
+        .last()
+        .map_or(width, |(start, word)| {
+            WidthPolicy::current().width(&text[..start + word.len()])
+        })
+}
+
+/// Finds the width of the whole words at the end of a target of a total
+/// width that fit in a width, or the width itself if not even the last word
+/// fits. Whitespace and punctuation before the first word are not kept.
+fn word_tail_width<T>(target: &T, width: usize, total: usize) -> usize
+where
+    T: WidthSliceable,
+    T::Output: RawText,
+{
+    let tail = target.slice_width(total.saturating_sub(width)..);
+    let (text, tail) = match (target.slice_width(..), tail) {
+        (Some(text), Some(tail)) => (text, tail),
+        _ => return width,
+    };
+    let text = text.raw_ref();
+    let limit = text.len() - tail.raw_ref().len();
+    text.split_word_bound_indices()
+        .find(|(start, word)| *start >= limit && word.chars().any(char::is_alphanumeric))
+        .map_or(width, |(start, _)| {
+            WidthPolicy::current().width(&text[start..])
+        })
+}
+
+/// Splits a width at a fractional position, rounding half widths to the left.
+fn split_width(width: usize, position: f32) -> usize {
+    let position = if position.is_nan() {
+        0.5
+    } else {
+        position.clamp(0.0, 1.0)
+    };
+    ((width as f32 * position).round() as usize).min(width)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::text::*;
+    use crate::widget::fixtures::make_spans;
     use std::borrow::Cow;
     #[test]
     fn truncate_text() {
@@ -122,6 +550,47 @@ mod test {
         assert_eq!(expected, actual);
     }
     #[test]
+    fn truncate_borrowed_symbol() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut spans: Spans<Tag> = Default::default();
+        spans.push(&Span::new(Cow::Borrowed(&fmt_2), Cow::Borrowed("01234")));
+        let mut ellipsis = Spans::<Tag>::default();
+        ellipsis.push(&Span::new(Cow::Borrowed(&fmt_1), Cow::Borrowed("…")));
+        let truncator = TruncationStyle::Left(&ellipsis);
+        let actual = format!("{}", truncator.truncate(&&spans, 3).unwrap());
+        let expected = String::from("<2>01</2><1>…</1>");
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn truncate_plain_strings() {
+        use crate::widget::{HBox, TextWidget};
+        let text = String::from("αβγδεζ");
+        let truncation = TruncationStyle::Right("…");
+        assert_eq!(truncation.truncate(&text, 4).unwrap(), "…δεζ");
+        let mut hbox: HBox<String> = HBox::new();
+        hbox.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&text),
+            Cow::Borrowed(&truncation),
+        )));
+        assert_eq!(hbox.truncate(3), "…εζ");
+        assert_eq!(truncate_str("αβγ", 0, &truncation), None);
+    }
+    #[test]
+    fn shorten_keeps_delimiters() {
+        let fmt_d = Tag::new("<d>", "</d>");
+        let path = Spans::from("home")
+            .then(fmt_d.clone(), "/")
+            .then(Tag::default(), "someone")
+            .then(fmt_d, "/")
+            .then(Tag::default(), "projects");
+        let truncation = TruncationStyle::Left("…");
+        let shorten = |width| shorten_delimited(&path, "/", width, &truncation).to_string();
+        assert_eq!(shorten(21), "home<d>/</d>someone<d>/</d>projects");
+        assert_eq!(shorten(11), "ho…<d>/</d>so…<d>/</d>pr…");
+        assert_eq!(shorten(3), "…<d>//</d>");
+    }
+    #[test]
     fn truncate_none() {
         let fmt_2 = Tag::new("<2>", "</2>");
         let fmt_3 = Tag::new("<3>", "</3>");
@@ -134,6 +603,55 @@ mod test {
         assert_eq!(expected, actual);
     }
     #[test]
+    fn truncate_outer() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let spans = make_spans(&fmt_2, "0123456789");
+        let truncator = TruncationStyle::Outer(make_spans(&fmt_1, "…"));
+        let actual = format!("{}", truncator.truncate(&spans, 6).unwrap());
+        let expected = String::from("<1>…</1><2>3456</2><1>…</1>");
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn truncate_outer_noop() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let spans = make_spans(&fmt_2, "0123");
+        let truncator = TruncationStyle::Outer(make_spans(&fmt_1, "…"));
+        let actual = format!("{}", truncator.truncate(&spans, 4).unwrap());
+        let expected = String::from("<2>0123</2>");
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn truncate_position() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let spans = make_spans(&fmt_2, "0123456789");
+        let truncator = TruncationStyle::Position(make_spans(&fmt_1, "…"), 0.2);
+        let actual = format!("{}", truncator.truncate(&spans, 6).unwrap());
+        let expected = String::from("<2>0</2><1>…</1><2>6789</2>");
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn truncate_position_matches_inner() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let spans = make_spans(&fmt_2, "0123456789");
+        let symbol = make_spans(&fmt_1, "…");
+        for width in 1..10 {
+            let inner = TruncationStyle::Inner(symbol.clone()).truncate(&spans, width);
+            let position = TruncationStyle::Position(symbol.clone(), 0.5).truncate(&spans, width);
+            assert_eq!(inner, position);
+        }
+    }
+    #[test]
+    fn split_width_clamps() {
+        assert_eq!(0, split_width(10, -1.0));
+        assert_eq!(10, split_width(10, 2.0));
+        assert_eq!(3, split_width(5, 0.5));
+        assert_eq!(3, split_width(5, f32::NAN));
+    }
+    #[test]
     fn truncate_one() {
         let fmt_1 = Tag::new("<1>", "</1>");
         let fmt_2 = Tag::new("<2>", "</2>");
@@ -148,4 +666,89 @@ mod test {
         let expected = String::from("<2>0</2>");
         assert_eq!(expected, actual);
     }
+    #[test]
+    fn truncate_exact_width_emoji() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let fmt_f = Tag::new("<f>", "</f>");
+        let spans = make_spans(&fmt_2, "🙈🙉🙊🙈");
+        let symbol = make_spans(&fmt_1, "…");
+        let filler = make_spans(&fmt_f, "_");
+        let exact = TruncationStyle::Left(symbol.clone()).exact_width(filler.clone());
+        let actual = format!("{}", exact.truncate(&spans, 4).unwrap());
+        assert_eq!("<2>🙈</2><f>_</f><1>…</1>", actual);
+        let exact = TruncationStyle::Inner(symbol.clone()).exact_width(filler.clone());
+        let actual = format!("{}", exact.truncate(&spans, 6).unwrap());
+        assert_eq!("<2>🙈</2><f>_</f><1>…</1><2>🙈</2>", actual);
+        let exact = TruncationStyle::Right(symbol).exact_width(filler);
+        let actual = exact.truncate(&spans, 4).unwrap();
+        assert_eq!("<1>…</1><f>_</f><2>🙈</2>", format!("{}", actual));
+        for width in 1..9 {
+            assert_eq!(
+                width,
+                exact.truncate(&spans, width).unwrap().bounded_width()
+            );
+        }
+    }
+    #[test]
+    fn truncate_zwj_sequences() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut spans = make_spans(&fmt_2, "👨‍👩‍👧");
+        spans.push(&make_spans(&fmt_1, "🏳️‍🌈a"));
+        assert_eq!(5, spans.bounded_width());
+        let truncation = TruncationStyle::Left("…");
+        let actual = format!("{}", truncation.truncate(&spans, 4).unwrap());
+        assert_eq!("<2>👨‍👩‍👧…</2>", actual);
+        let truncation = TruncationStyle::Right("…");
+        let actual = format!("{}", truncation.truncate(&spans, 4).unwrap());
+        assert_eq!("…<1>🏳️‍🌈a</1>", actual);
+        let truncation = TruncationStyle::Left("");
+        let actual = format!("{}", truncation.truncate(&spans, 3).unwrap());
+        assert_eq!("<2>👨‍👩‍👧</2>", actual);
+    }
+    #[test]
+    fn truncate_right_to_left() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let spans = make_spans(&fmt_2, "0123456789");
+        let symbol = make_spans(&fmt_1, "…");
+        for &(ref style, ltr, rtl) in [
+            (TruncationStyle::Start(symbol.clone()), "0123…", "0123…"),
+            (TruncationStyle::End(symbol.clone()), "…6789", "…6789"),
+            (TruncationStyle::Left(symbol.clone()), "0123…", "…6789"),
+            (TruncationStyle::Right(symbol.clone()), "…6789", "0123…"),
+            (
+                TruncationStyle::Position(symbol.clone(), 0.25),
+                "0…789",
+                "012…9",
+            ),
+        ]
+        .iter()
+        {
+            let actual = style.truncate_in(&spans, 5, Direction::Ltr).unwrap();
+            assert_eq!(ltr, actual.raw());
+            let actual = style.truncate_in(&spans, 5, Direction::Rtl).unwrap();
+            assert_eq!(rtl, actual.raw());
+        }
+    }
+    #[test]
+    fn truncate_words() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut spans = make_spans(&fmt_1, "lorem ipsum, ");
+        spans.push(&make_spans(&fmt_2, "dolor"));
+        let left = TruncationStyle::WordLeft("…");
+        let right = TruncationStyle::WordRight("…");
+        let truncate =
+            |style: &TruncationStyle<&str>, width| style.truncate(&spans, width).unwrap();
+        assert_eq!("<1>lorem ipsum,…</1>", truncate(&left, 16).to_string());
+        assert_eq!("lorem…", truncate(&left, 11).raw());
+        assert_eq!("lore…", truncate(&left, 5).raw());
+        assert_eq!("…<2>dolor</2>", truncate(&right, 11).to_string());
+        assert_eq!("…ipsum, dolor", truncate(&right, 17).raw());
+        assert_eq!("…olor", truncate(&right, 5).raw());
+        let exact = TruncationStyle::WordLeft("…").exact_width(" ");
+        assert_eq!("lorem     …", exact.truncate(&spans, 11).unwrap().raw());
+    }
 }
@@ -0,0 +1,140 @@
+use crate::text::{HasWidth, Spans, Width};
+use crate::widget::{Fitable, TextWidget, Truncateable, TruncationStrategy};
+use std::borrow::Cow;
+
+/// A stack of widgets, each fit to the same width on a line of its own.
+///
+/// Rows can be any widget, including an [`crate::widget::HBox`], so boxes can
+/// be nested to lay out text in two dimensions.
+pub struct VBox<'a, T: Truncateable> {
+    rows: Vec<Box<dyn Fitable<T> + 'a>>,
+}
+
+impl<'a, T: Truncateable> Default for VBox<'a, T> {
+    fn default() -> Self {
+        VBox { rows: vec![] }
+    }
+}
+
+impl<'a, T: Truncateable> VBox<'a, T> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Adds a row.
+    pub fn push(&mut self, row: Box<dyn Fitable<T> + 'a>) {
+        self.rows.push(row);
+    }
+    /// Adds a row for each line of some multi-line text, so that each line is
+    /// truncated on its own.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{TruncationStyle, VBox};
+    /// let text: Spans<Tag> = Spans::from("first line\nsecond");
+    /// let truncation = TruncationStyle::Left("…");
+    /// let mut vbox = VBox::new();
+    /// vbox.push_lines(&text, Cow::Borrowed(&truncation));
+    /// let lines: Vec<String> = vbox.truncate(7).iter().map(|line| line.to_string()).collect();
+    /// assert_eq!(lines, ["first …", "second"]);
+    /// ```
+    pub fn push_lines<S, U>(&mut self, text: &Spans<S>, truncation_strategy: Cow<'a, U>)
+    where
+        S: Clone + 'a,
+        U: Clone + TruncationStrategy<Spans<S>> + 'a,
+        TextWidget<'a, Spans<S>, U>: Fitable<T>,
+    {
+        for line in text.lines() {
+            self.push(Box::new(TextWidget::new(
+                Cow::Owned(line),
+                truncation_strategy.clone(),
+            )));
+        }
+    }
+    /// Returns the number of lines the box occupies.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+    /// Fits each row to a given width, giving one line per row. Rows that
+    /// cannot be fit, as at a width of zero, are empty lines.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Spans, Tag};
+    /// use stylish_stringlike::widget::{HBox, TextWidget, TruncationStyle, VBox};
+    /// let texts: Vec<Spans<Tag>> = vec![Spans::from("heading"), Spans::from("a"), Spans::from("b")];
+    /// let truncation = TruncationStyle::Left("…");
+    /// let mut columns = HBox::with_separator(Spans::from(" | "));
+    /// for text in &texts[1..] {
+    ///     columns.push(Box::new(TextWidget::new(Cow::Borrowed(text), Cow::Borrowed(&truncation))));
+    /// }
+    /// let mut vbox = VBox::new();
+    /// vbox.push(Box::new(TextWidget::new(Cow::Borrowed(&texts[0]), Cow::Borrowed(&truncation))));
+    /// vbox.push(Box::new(columns));
+    /// let lines: Vec<String> = vbox.truncate(5).iter().map(|line| line.to_string()).collect();
+    /// assert_eq!(lines, ["head…", "a | b"]);
+    /// ```
+    pub fn truncate(&self, width: usize) -> Vec<T>
+    where
+        T: Default,
+    {
+        self.rows
+            .iter()
+            .map(|row| row.truncate(width).unwrap_or_default())
+            .collect()
+    }
+}
+
+impl<'a, T: Truncateable> HasWidth for VBox<'a, T> {
+    /// The width of the widest row.
+    fn width(&self) -> Width {
+        self.rows
+            .iter()
+            .map(|row| row.width())
+            .fold(Width::Bounded(0), |widest, width| match (widest, width) {
+                (Width::Bounded(a), Width::Bounded(b)) => Width::Bounded(a.max(b)),
+                _ => Width::Unbounded,
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    use crate::widget::{HBox, Repeat, TextWidget, TruncationStyle};
+    use std::borrow::Cow;
+    #[test]
+    fn nested_boxes() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let label = Spans::styled(fmt_1.clone(), "label");
+        let value = Spans::styled(fmt_2.clone(), "12345");
+        let rule = Repeat::new(Span::borrowed(&fmt_1, "-"));
+        let truncation = TruncationStyle::Left("…");
+        let mut inner: HBox<Spans<Tag>> = HBox::new();
+        inner.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&label),
+            Cow::Borrowed(&truncation),
+        )));
+        inner.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&value),
+            Cow::Borrowed(&truncation),
+        )));
+        assert_eq!(Width::Bounded(10), inner.width());
+        let mut vbox = VBox::new();
+        vbox.push(Box::new(TextWidget::new(
+            Cow::Borrowed(&rule),
+            Cow::Borrowed(&truncation),
+        )));
+        vbox.push(Box::new(inner));
+        assert_eq!(Width::Unbounded, vbox.width());
+        assert_eq!(2, vbox.height());
+        let lines: Vec<String> = vbox.truncate(6).iter().map(|l| l.to_string()).collect();
+        assert_eq!(vec!["<1>-----…</1>", "<1>la…</1><2>12…</2>"], lines);
+        let lines: Vec<String> = vbox.truncate(0).iter().map(|l| l.to_string()).collect();
+        assert_eq!(vec!["", ""], lines);
+    }
+}
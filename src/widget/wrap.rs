@@ -0,0 +1,178 @@
+use crate::text::{RawText, Sliceable, Spans, WidthPolicy};
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Wraps text onto lines of a given width, breaking at whitespace.
+///
+/// Whitespace between words on the same line is kept and whitespace at line
+/// breaks is dropped. Newlines always start a new line. Words wider than a
+/// line are broken between graphemes.
+pub struct Wrap<'a, T: Clone> {
+    text: Cow<'a, Spans<T>>,
+}
+
+impl<'a, T: Clone> Wrap<'a, T> {
+    pub fn new(text: Cow<'a, Spans<T>>) -> Self {
+        Wrap { text }
+    }
+    /// Wraps the text onto lines at most `width` columns wide. A grapheme wider
+    /// than `width` gets a line of its own. Returns no lines if `width` is
+    /// zero.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use stylish_stringlike::text::{Pushable, Span, Spans, Tag};
+    /// use stylish_stringlike::widget::Wrap;
+    /// let bold = Tag::new("<b>", "</b>");
+    /// let mut text: Spans<Tag> = Default::default();
+    /// text.push(&Span::borrowed(&bold, "the quick"));
+    /// text.push(&" brown fox");
+    /// let wrap = Wrap::new(Cow::Borrowed(&text));
+    /// let lines: Vec<String> = wrap.lines(10).iter().map(|line| line.to_string()).collect();
+    /// assert_eq!(lines, vec!["<b>the quick</b>", "<b>brown fox</b>"]);
+    /// ```
+    pub fn lines(&self, width: usize) -> Vec<Spans<T>> {
+        let mut lines = vec![];
+        if width == 0 {
+            return lines;
+        }
+        let content = self.text.raw_ref();
+        let mut paragraph_start = 0;
+        for paragraph in content.split('\n') {
+            let ranges = wrap_ranges(paragraph, width);
+            if ranges.is_empty() {
+                lines.push(Default::default());
+            }
+            for (start, end) in ranges {
+                let line = self
+                    .text
+                    .slice(paragraph_start + start..paragraph_start + end)
+                    .unwrap_or_default();
+                lines.push(line);
+            }
+            paragraph_start += paragraph.len() + 1;
+        }
+        lines
+    }
+}
+
+/// Finds the byte ranges of the lines of a paragraph wrapped to a width.
+fn wrap_ranges(paragraph: &str, width: usize) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    // The current line as a byte range and its width.
+    let mut line: Option<(usize, usize)> = None;
+    let mut line_width = 0;
+    let policy = WidthPolicy::current();
+    for (word_start, word_end) in words(paragraph) {
+        let word_width = policy.width(&paragraph[word_start..word_end]);
+        if let Some((start, end)) = line {
+            let gap_width = policy.width(&paragraph[end..word_start]);
+            if line_width + gap_width + word_width <= width {
+                line = Some((start, word_end));
+                line_width += gap_width + word_width;
+                continue;
+            }
+            ranges.push((start, end));
+        }
+        if word_width <= width {
+            line = Some((word_start, word_end));
+            line_width = word_width;
+            continue;
+        }
+        // Break words too wide for a line of their own between graphemes.
+        let mut start = word_start;
+        line_width = 0;
+        for (offset, grapheme) in paragraph[word_start..word_end].grapheme_indices(true) {
+            let grapheme_start = word_start + offset;
+            let grapheme_width = policy.grapheme_width(grapheme);
+            if line_width + grapheme_width > width && grapheme_start > start {
+                ranges.push((start, grapheme_start));
+                start = grapheme_start;
+                line_width = 0;
+            }
+            line_width += grapheme_width;
+        }
+        line = Some((start, word_end));
+    }
+    if let Some(line) = line {
+        ranges.push(line);
+    }
+    ranges
+}
+
+/// Finds the byte ranges of runs of non-whitespace characters.
+fn words(text: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut start = None;
+    text.char_indices()
+        .map(Some)
+        .chain(std::iter::once(None))
+        .filter_map(move |item| match (item, start) {
+            (Some((index, c)), None) if !c.is_whitespace() => {
+                start = Some(index);
+                None
+            }
+            (Some((index, c)), Some(s)) if c.is_whitespace() => {
+                start = None;
+                Some((s, index))
+            }
+            (None, Some(s)) => Some((s, text.len())),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::*;
+    use crate::widget::fixtures::make_spans;
+    fn wrap(text: &Spans<Tag>, width: usize) -> Vec<String> {
+        Wrap::new(Cow::Borrowed(text))
+            .lines(width)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+    #[test]
+    fn wrap_words() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let fmt_2 = Tag::new("<2>", "</2>");
+        let mut text = make_spans(&fmt_1, "aa bb  cc ");
+        text.push(&make_spans(&fmt_2, "dd"));
+        let expected = vec![
+            String::from("<1>aa bb</1>"),
+            String::from("<1>cc </1><2>dd</2>"),
+        ];
+        assert_eq!(expected, wrap(&text, 5));
+        let expected = vec![String::from("<1>aa bb  cc </1><2>dd</2>")];
+        assert_eq!(expected, wrap(&text, 20));
+    }
+    #[test]
+    fn wrap_long_words() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let text = make_spans(&fmt_1, "a bcdefgh");
+        let expected = vec![
+            String::from("<1>a</1>"),
+            String::from("<1>bcd</1>"),
+            String::from("<1>efg</1>"),
+            String::from("<1>h</1>"),
+        ];
+        assert_eq!(expected, wrap(&text, 3));
+        let text = make_spans(&fmt_1, "🙈🙉");
+        let expected = vec![String::from("<1>🙈</1>"), String::from("<1>🙉</1>")];
+        assert_eq!(expected, wrap(&text, 1));
+    }
+    #[test]
+    fn wrap_newlines() {
+        let fmt_1 = Tag::new("<1>", "</1>");
+        let text = make_spans(&fmt_1, "ab\n\ncd ef");
+        let expected = vec![
+            String::from("<1>ab</1>"),
+            String::new(),
+            String::from("<1>cd</1>"),
+            String::from("<1>ef</1>"),
+        ];
+        assert_eq!(expected, wrap(&text, 3));
+        assert!(wrap(&text, 0).is_empty());
+    }
+}